@@ -0,0 +1 @@
+DROP TABLE osu_tracking_notifications;
\ No newline at end of file
@@ -0,0 +1,18 @@
+CREATE TABLE osu_tracking_notifications (
+    user_id     INT4 NOT NULL,
+    mode        INT2 NOT NULL,
+    score_id    INT8 NOT NULL,
+    username    VARCHAR(15) NOT NULL,
+    map_id      INT4 NOT NULL,
+    mapset_id   INT4 NOT NULL,
+    map_title   TEXT NOT NULL,
+    pp          FLOAT4,
+    top_idx     INT2 NOT NULL,
+    channels    INT8[] NOT NULL,
+    ended_at    TIMESTAMPTZ NOT NULL,
+    notified_at TIMESTAMPTZ DEFAULT now() NOT NULL,
+
+    PRIMARY KEY (user_id, mode, score_id)
+);
+
+CREATE INDEX osu_tracking_notifications_ended_at ON osu_tracking_notifications (ended_at);
\ No newline at end of file
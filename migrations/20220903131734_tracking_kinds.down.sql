@@ -0,0 +1,9 @@
+DELETE FROM osu_trackings WHERE kind <> 0;
+ALTER TABLE osu_trackings DROP CONSTRAINT osu_trackings_pkey;
+ALTER TABLE osu_trackings DROP COLUMN kind;
+ALTER TABLE osu_trackings ADD PRIMARY KEY (user_id, mode);
+
+DELETE FROM osu_tracking_notifications WHERE kind <> 0;
+ALTER TABLE osu_tracking_notifications DROP CONSTRAINT osu_tracking_notifications_pkey;
+ALTER TABLE osu_tracking_notifications DROP COLUMN kind;
+ALTER TABLE osu_tracking_notifications ADD PRIMARY KEY (user_id, mode, score_id);
\ No newline at end of file
@@ -0,0 +1,7 @@
+ALTER TABLE osu_trackings ADD COLUMN kind INT2 DEFAULT 0 NOT NULL;
+ALTER TABLE osu_trackings DROP CONSTRAINT osu_trackings_pkey;
+ALTER TABLE osu_trackings ADD PRIMARY KEY (user_id, mode, kind);
+
+ALTER TABLE osu_tracking_notifications ADD COLUMN kind INT2 DEFAULT 0 NOT NULL;
+ALTER TABLE osu_tracking_notifications DROP CONSTRAINT osu_tracking_notifications_pkey;
+ALTER TABLE osu_tracking_notifications ADD PRIMARY KEY (user_id, mode, score_id, kind);
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE osu_trackings DROP COLUMN check_interval;
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE osu_trackings ADD COLUMN check_interval INT4 DEFAULT 9000 NOT NULL;
\ No newline at end of file
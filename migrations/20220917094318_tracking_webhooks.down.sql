@@ -0,0 +1 @@
+DROP TABLE tracking_webhooks;
\ No newline at end of file
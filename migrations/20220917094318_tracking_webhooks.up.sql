@@ -0,0 +1,10 @@
+CREATE TABLE tracking_webhooks (
+    channel_id  INT8 NOT NULL,
+    webhook_id  INT8 NOT NULL,
+    token       BYTEA NOT NULL,
+    nonce       BYTEA NOT NULL,
+    username    VARCHAR(80),
+    avatar_url  TEXT,
+
+    PRIMARY KEY (channel_id)
+);
\ No newline at end of file
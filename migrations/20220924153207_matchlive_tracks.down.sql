@@ -0,0 +1 @@
+DROP TABLE matchlive_tracks;
\ No newline at end of file
@@ -0,0 +1,8 @@
+CREATE TABLE matchlive_tracks (
+    match_id      INT4 NOT NULL,
+    channel_id    INT8 NOT NULL,
+    msg_id        INT8 NOT NULL,
+    last_event_id INT8 NOT NULL,
+
+    PRIMARY KEY (match_id, channel_id)
+);
\ No newline at end of file
@@ -0,0 +1,3 @@
+ALTER TABLE matchlive_tracks
+    DROP COLUMN warmups,
+    DROP COLUMN scoreboard_msg_id;
\ No newline at end of file
@@ -0,0 +1,3 @@
+ALTER TABLE matchlive_tracks
+    ADD COLUMN warmups INT2 NOT NULL DEFAULT 2,
+    ADD COLUMN scoreboard_msg_id INT8;
\ No newline at end of file
@@ -0,0 +1 @@
+DROP TABLE match_stages;
\ No newline at end of file
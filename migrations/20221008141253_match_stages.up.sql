@@ -0,0 +1,7 @@
+CREATE TABLE match_stages (
+    guild_id  INT8 NOT NULL,
+    name      VARCHAR(32) NOT NULL,
+    match_ids INT4[] NOT NULL,
+
+    PRIMARY KEY (guild_id, name)
+);
\ No newline at end of file
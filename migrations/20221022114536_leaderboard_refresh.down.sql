@@ -0,0 +1 @@
+ALTER TABLE guild_configs DROP COLUMN leaderboard_refresh;
\ No newline at end of file
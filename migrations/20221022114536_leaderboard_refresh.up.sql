@@ -0,0 +1 @@
+ALTER TABLE guild_configs ADD COLUMN leaderboard_refresh BOOL;
\ No newline at end of file
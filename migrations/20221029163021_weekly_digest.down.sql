@@ -0,0 +1,6 @@
+DROP TABLE guild_digest_values;
+
+ALTER TABLE guild_configs
+    DROP COLUMN digest_channel_id,
+    DROP COLUMN digest_day,
+    DROP COLUMN digest_hour;
\ No newline at end of file
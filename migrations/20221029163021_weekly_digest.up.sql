@@ -0,0 +1,15 @@
+ALTER TABLE guild_configs
+    ADD COLUMN digest_channel_id INT8,
+    ADD COLUMN digest_day INT2,
+    ADD COLUMN digest_hour INT2;
+
+CREATE TABLE guild_digest_values (
+    guild_id    INT8 NOT NULL,
+    user_id     INT4 NOT NULL,
+    pp          FLOAT4 NOT NULL,
+    global_rank INT4 NOT NULL,
+    playtime    INT4 NOT NULL,
+    taken_at    TIMESTAMPTZ NOT NULL,
+
+    PRIMARY KEY (guild_id, user_id)
+);
\ No newline at end of file
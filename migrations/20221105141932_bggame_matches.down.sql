@@ -0,0 +1,2 @@
+DROP TABLE bggame_match_scores;
+DROP TABLE bggame_matches;
\ No newline at end of file
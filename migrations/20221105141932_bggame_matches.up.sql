@@ -0,0 +1,18 @@
+CREATE TABLE bggame_matches (
+    match_id    SERIAL NOT NULL,
+    channel_id  INT8 NOT NULL,
+    rounds      INT2 NOT NULL,
+    teams       BOOL NOT NULL,
+    finished_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (match_id)
+);
+
+CREATE TABLE bggame_match_scores (
+    match_id   INT4 NOT NULL REFERENCES bggame_matches (match_id) ON DELETE CASCADE,
+    discord_id INT8 NOT NULL,
+    team       INT2,
+    score      INT4 NOT NULL,
+
+    PRIMARY KEY (match_id, discord_id)
+);
\ No newline at end of file
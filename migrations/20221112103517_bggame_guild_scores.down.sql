@@ -0,0 +1 @@
+DROP TABLE bggame_guild_scores;
\ No newline at end of file
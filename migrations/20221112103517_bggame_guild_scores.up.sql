@@ -0,0 +1,9 @@
+CREATE TABLE bggame_guild_scores (
+    guild_id      INT8 NOT NULL,
+    discord_id    INT8 NOT NULL,
+    score         INT4 NOT NULL DEFAULT 0,
+    guess_time_ms INT8 NOT NULL DEFAULT 0,
+    hints         INT4 NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (guild_id, discord_id)
+);
\ No newline at end of file
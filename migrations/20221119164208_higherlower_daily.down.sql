@@ -0,0 +1 @@
+DROP TABLE higherlower_daily_scores;
\ No newline at end of file
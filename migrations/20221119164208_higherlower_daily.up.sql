@@ -0,0 +1,8 @@
+CREATE TABLE higherlower_daily_scores (
+    day        DATE NOT NULL,
+    version    INT2 NOT NULL,
+    discord_id INT8 NOT NULL,
+    score      INT4 NOT NULL,
+
+    PRIMARY KEY (day, version, discord_id)
+);
\ No newline at end of file
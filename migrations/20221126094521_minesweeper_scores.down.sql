@@ -0,0 +1 @@
+DROP TABLE minesweeper_scores;
\ No newline at end of file
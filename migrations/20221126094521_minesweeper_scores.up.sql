@@ -0,0 +1,7 @@
+CREATE TABLE minesweeper_scores (
+    discord_id INT8 NOT NULL,
+    difficulty INT2 NOT NULL,
+    time_ms    INT4 NOT NULL,
+
+    PRIMARY KEY (discord_id, difficulty)
+);
\ No newline at end of file
@@ -0,0 +1,2 @@
+DROP INDEX osu_tracking_notifications_notified_at;
+CREATE INDEX osu_tracking_notifications_ended_at ON osu_tracking_notifications (ended_at);
\ No newline at end of file
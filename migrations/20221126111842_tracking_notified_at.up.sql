@@ -0,0 +1,2 @@
+DROP INDEX osu_tracking_notifications_ended_at;
+CREATE INDEX osu_tracking_notifications_notified_at ON osu_tracking_notifications (notified_at);
\ No newline at end of file
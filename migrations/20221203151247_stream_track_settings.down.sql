@@ -0,0 +1,5 @@
+ALTER TABLE stream_tracks
+    DROP COLUMN osu_only,
+    DROP COLUMN title_filter,
+    DROP COLUMN vod_edit,
+    DROP COLUMN role_id;
\ No newline at end of file
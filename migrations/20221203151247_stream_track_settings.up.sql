@@ -0,0 +1,5 @@
+ALTER TABLE stream_tracks
+    ADD COLUMN osu_only     BOOL NOT NULL DEFAULT FALSE,
+    ADD COLUMN title_filter VARCHAR(64),
+    ADD COLUMN vod_edit     BOOL NOT NULL DEFAULT TRUE,
+    ADD COLUMN role_id      INT8;
\ No newline at end of file
@@ -0,0 +1,2 @@
+DROP INDEX osu_user_name_lower;
+DROP TABLE osu_name_usage;
\ No newline at end of file
@@ -0,0 +1,9 @@
+CREATE TABLE osu_name_usage (
+    guild_id  INT8 NOT NULL,
+    username  VARCHAR(15) NOT NULL,
+    last_used TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (guild_id, username)
+);
+
+CREATE INDEX osu_user_name_lower ON osu_user_names (LOWER(username) text_pattern_ops);
\ No newline at end of file
@@ -0,0 +1 @@
+DROP INDEX map_checksum;
\ No newline at end of file
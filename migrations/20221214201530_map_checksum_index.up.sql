@@ -0,0 +1 @@
+CREATE INDEX map_checksum ON maps (checksum);
\ No newline at end of file
@@ -0,0 +1 @@
+DROP TABLE higherlower_daily_entries;
\ No newline at end of file
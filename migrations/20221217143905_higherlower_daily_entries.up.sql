@@ -0,0 +1,11 @@
+CREATE TABLE higherlower_daily_entries (
+    day     DATE NOT NULL,
+    version INT2 NOT NULL,
+    idx     INT4 NOT NULL,
+    map_id  INT4 NOT NULL,
+    user_id INT4,
+    mods    INT4,
+    farm    INT4,
+
+    PRIMARY KEY (day, version, idx)
+);
\ No newline at end of file
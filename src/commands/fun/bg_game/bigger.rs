@@ -4,6 +4,7 @@ use eyre::Result;
 use twilight_model::channel::Message;
 
 use crate::{
+    commands::fun::BgGameKind,
     core::{buckets::BucketName, commands::checks::check_ratelimit},
     games::bg::GameState,
     util::{builder::MessageBuilder, constants::GENERAL_ISSUE, ChannelExt},
@@ -26,6 +27,10 @@ pub async fn bigger(ctx: Arc<Context>, msg: &Message) -> Result<()> {
     let _ = ctx.http.create_typing_trigger(msg.channel_id).exec().await;
 
     match ctx.bg_games().read(&msg.channel_id).await.get() {
+        Some(GameState::Running { game }) if matches!(game.kind(), BgGameKind::Audio) => {
+            let content = "Audio previews can't be made bigger, try `<bg hint` instead";
+            msg.error(&ctx, content).await?;
+        }
         Some(GameState::Running { game }) => match game.sub_image().await {
             Ok(bytes) => {
                 let builder = MessageBuilder::new().attachment("bg_img.png", bytes);
@@ -46,7 +51,7 @@ pub async fn bigger(ctx: Arc<Context>, msg: &Message) -> Result<()> {
             msg.error(&ctx, content).await?;
         }
         None => {
-            let content = "No running game in this channel. Start one with `/bg`.";
+            let content = "No running game in this channel. Start one with `/bg start`.";
             msg.error(&ctx, content).await?;
         }
     }
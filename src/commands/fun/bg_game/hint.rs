@@ -23,7 +23,7 @@ pub async fn hint(ctx: Arc<Context>, msg: &Message) -> Result<()> {
     }
 
     match ctx.bg_games().read(&msg.channel_id).await.get() {
-        Some(GameState::Running { game }) => match game.hint().await {
+        Some(GameState::Running { game }) => match game.hint(msg.author.id.get()).await {
             Ok(hint) => {
                 let builder = MessageBuilder::new().content(hint);
                 msg.create_message(&ctx, &builder).await?;
@@ -43,7 +43,7 @@ pub async fn hint(ctx: Arc<Context>, msg: &Message) -> Result<()> {
             msg.error(&ctx, content).await?;
         }
         None => {
-            let content = "No running game in this channel. Start one with `/bg`.";
+            let content = "No running game in this channel. Start one with `/bg start`.";
             msg.error(&ctx, content).await?;
         }
     }
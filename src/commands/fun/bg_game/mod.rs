@@ -1,4 +1,8 @@
-use std::sync::Arc;
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    sync::Arc,
+    time::Duration,
+};
 
 use command_macros::{command, SlashCommand};
 use eyre::{Report, Result};
@@ -11,11 +15,15 @@ use twilight_model::{
         SelectMenu,
     },
     channel::{thread::AutoArchiveDuration, ChannelType},
+    id::{marker::UserMarker, Id},
 };
 
 use crate::{
     commands::ThreadChannel,
-    games::bg::{Effects, GameState, GameWrapper, MapsetTags},
+    core::BotConfig,
+    games::bg::{
+        retain_previews, BgPreset, Effects, GameState, GameWrapper, MapsetTags, MatchSettings,
+    },
     util::{
         builder::MessageBuilder,
         constants::{GENERAL_ISSUE, INVALID_ACTION_FOR_CHANNEL_TYPE, THREADS_UNAVAILABLE},
@@ -25,17 +33,18 @@ use crate::{
     Context,
 };
 
-use self::{bigger::*, hint::*, rankings::*, skip::*, stop::*};
+use self::{bigger::*, hint::*, rankings::*, skip::*, stats::*, stop::*};
 
 mod bigger;
 mod hint;
 mod rankings;
 mod skip;
+mod stats;
 mod stop;
 // mod tags; // TODO
 
 #[command]
-#[desc("Play the background guessing game, use `/bg` to start")]
+#[desc("Play the background guessing game, use `/bg start` to start")]
 #[alias("bg")]
 #[flags(SKIP_DEFER)] // defer manually on specific subcommands
 #[group(Games)]
@@ -45,7 +54,7 @@ pub async fn prefix_backgroundgame(ctx: Arc<Context>, msg: &Message, args: Args<
 
     match arg.as_ref().map(|arg| arg.as_ref()) {
         None | Some("help") => {
-            let content = "Use `/bg` to start a new background guessing game.\n\
+            let content = "Use `/bg start` to start a new background guessing game.\n\
                 Given part of a map's background, try to guess the **title** of the map's song.\n\
                 You don't need to guess content in parentheses `(...)` or content after `ft.` or `feat.`.\n\n\
                 Use these prefix commands to initiate with the game:\n\
@@ -56,7 +65,8 @@ pub async fn prefix_backgroundgame(ctx: Arc<Context>, msg: &Message, args: Args<
                 • `<bg stop`: Resolve the current background and stop the game.
                 • `<bg l[eaderboard] s[erver]`: Check out the global leaderboard for \
                 amount of correct guesses. If `server` or `s` is added at the end, \
-                I will only show members of this server.";
+                I will only show guesses within this server.\n\
+                • `<bg stats`: Check out your background game stats in this server.";
 
             let builder = MessageBuilder::new().embed(content);
             msg.create_message(&ctx, &builder).await?;
@@ -67,12 +77,13 @@ pub async fn prefix_backgroundgame(ctx: Arc<Context>, msg: &Message, args: Args<
         Some("h" | "hint") => hint(ctx, msg).await,
         Some("b" | "bigger" | "enhance") => bigger(ctx, msg).await,
         Some("stop" | "end" | "quit") => stop(ctx, msg).await,
+        Some("stats") => stats(ctx, msg.into(), None).await,
         Some("l" | "lb" | "leaderboard") => {
             let arg = args.next();
 
             match arg.as_ref().map(|arg| arg.as_ref()) {
-                Some("s" | "server") => leaderboard(ctx, msg, false).await,
-                _ => leaderboard(ctx, msg, true).await,
+                Some("s" | "server") => leaderboard(ctx, msg.into(), false).await,
+                _ => leaderboard(ctx, msg.into(), true).await,
             }
         }
         _ => {
@@ -91,6 +102,22 @@ pub async fn prefix_backgroundgame(ctx: Arc<Context>, msg: &Message, args: Args<
 #[derive(CommandModel, CreateCommand, SlashCommand)]
 #[command(
     name = "bg",
+    help = "Play the background guessing game or check out its leaderboards and stats."
+)]
+#[flags(SKIP_DEFER)]
+/// Play the background guessing game
+pub enum Bg {
+    #[command(name = "start")]
+    Start(BgStart),
+    #[command(name = "stats")]
+    Stats(BgStats),
+    #[command(name = "leaderboard")]
+    Leaderboard(BgLeaderboard),
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "start",
     help = "Start a new background guessing game.\n\
     Given part of a map's background, try to guess the **title** of the map's song.\n\
     You don't need to guess content in parentheses `(...)` or content after `ft.` or `feat.`.\n\n\
@@ -102,19 +129,68 @@ pub async fn prefix_backgroundgame(ctx: Arc<Context>, msg: &Message, args: Args<
     • `<bg stop`: Resolve the current background and stop the game.
     • `<bg l[eaderboard] s[erver]`: Check out the global leaderboard for \
     amount of correct guesses. If `server` or `s` is added at the end, \
-    I will only show members of this server."
+    I will only show guesses within this server.\n\n\
+    When specifying the `rounds` option, the game ends after that many backgrounds \
+    and each background has its own time limit. \
+    Skipping a background also counts as a round. \
+    The scores are shown on a live scoreboard and the final results are stored \
+    separately from the all-time leaderboard."
 )]
-#[flags(SKIP_DEFER)]
 /// Start a new background guessing game
-pub struct Bg {
+pub struct BgStart {
     /// Specify a gamemode
     mode: Option<BgGameMode>,
+    #[command(help = "Choose what should be guessed.\n\
+    With `Audio preview`, instead of parts of the background, \
+    you'll get to hear the audio preview of the mapset. \
+    Image effects don't apply and `<bg bigger` is not available in that case.")]
+    /// Guess backgrounds or audio previews, defaults to backgrounds
+    kind: Option<BgGameKind>,
     #[command(help = "Increase the difficulty.\n\
     The higher the difficulty, the more accurate guesses have to be in order to be accepted.")]
     /// Increase difficulty by requiring better guessing
     difficulty: Option<GameDifficulty>,
     /// Choose if a new thread should be started, defaults to staying in the channel
     thread: Option<ThreadChannel>,
+    #[command(min_value = 1, max_value = 50)]
+    /// Play a fixed amount of rounds with a final scoreboard
+    rounds: Option<i64>,
+    #[command(min_value = 10, max_value = 180)]
+    /// Time limit per round in seconds (defaults to 60), requires `rounds`
+    round_time: Option<i64>,
+    #[command(
+        help = "Let players split into two teams via buttons on the scoreboard.\n\
+    Players that guess correctly without having joined a team are assigned to the smaller one.\n\
+    Requires the `rounds` option."
+    )]
+    /// Play in two teams, requires `rounds`
+    teams: Option<bool>,
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "stats",
+    help = "Show the amount of guessed mapsets, the average guess time, \
+    and the amount of used hints of a user in this server.\n\
+    Only games with at least 20 available mapsets are taken into account."
+)]
+/// Show a user's background game stats in this server
+pub struct BgStats {
+    /// Specify a user, defaults to yourself
+    user: Option<Id<UserMarker>>,
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "leaderboard",
+    help = "Show the leaderboard for correct guesses.\n\
+    The server leaderboard only contains guesses that were made within this server \
+    while the global leaderboard contains all-time guesses across all servers."
+)]
+/// Show the leaderboard for correct guesses
+pub struct BgLeaderboard {
+    /// Show the global leaderboard instead of this server's one
+    global: Option<bool>,
 }
 
 #[derive(CommandOption, CreateOption)]
@@ -125,6 +201,29 @@ pub enum BgGameMode {
     Mania,
 }
 
+#[derive(Copy, Clone, Debug, CommandOption, CreateOption)]
+pub enum BgGameKind {
+    #[option(name = "Background", value = "bg")]
+    Background,
+    #[option(name = "Audio preview", value = "audio")]
+    Audio,
+}
+
+impl Default for BgGameKind {
+    fn default() -> Self {
+        Self::Background
+    }
+}
+
+impl BgGameKind {
+    pub fn attachment_name(self) -> &'static str {
+        match self {
+            Self::Background => "bg_img.png",
+            Self::Audio => "preview.mp3",
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, CommandOption, CreateOption)]
 pub enum GameDifficulty {
     #[option(name = "Normal", value = "normal")]
@@ -145,6 +244,19 @@ impl GameDifficulty {
     }
 }
 
+impl Display for GameDifficulty {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let name = match self {
+            GameDifficulty::Normal => "Normal",
+            GameDifficulty::Hard => "Hard",
+            GameDifficulty::Impossible => "Impossible",
+        };
+
+        f.write_str(name)
+    }
+}
+
 impl Default for GameDifficulty {
     fn default() -> Self {
         Self::Normal
@@ -152,11 +264,54 @@ impl Default for GameDifficulty {
 }
 
 async fn slash_bg(ctx: Arc<Context>, mut command: InteractionCommand) -> Result<()> {
-    let Bg {
+    match Bg::from_interaction(command.input_data())? {
+        Bg::Start(args) => start(ctx, command, args).await,
+        Bg::Stats(args) => {
+            command.defer(&ctx, false).await?;
+
+            stats(ctx, (&mut command).into(), args.user).await
+        }
+        Bg::Leaderboard(args) => {
+            command.defer(&ctx, false).await?;
+            let global = args.global.unwrap_or(false);
+
+            leaderboard(ctx, (&mut command).into(), global).await
+        }
+    }
+}
+
+async fn start(ctx: Arc<Context>, mut command: InteractionCommand, args: BgStart) -> Result<()> {
+    let BgStart {
         difficulty,
         mode,
+        kind,
         thread,
-    } = Bg::from_interaction(command.input_data())?;
+        rounds,
+        round_time,
+        teams,
+    } = args;
+
+    let settings = match rounds {
+        Some(rounds) => Some(MatchSettings {
+            rounds: rounds as usize,
+            round_time: Duration::from_secs(round_time.unwrap_or(60) as u64),
+            teams: teams.unwrap_or(false),
+        }),
+        None if round_time.is_some() || teams.is_some() => {
+            let content = "The `round_time` and `teams` options require the `rounds` option";
+            command.error_callback(&ctx, content).await?;
+
+            return Ok(());
+        }
+        None => None,
+    };
+
+    if matches!(kind, Some(BgGameKind::Audio)) && BotConfig::get().paths.previews.is_none() {
+        let content = "Audio previews are not available, try guessing backgrounds instead";
+        command.error_callback(&ctx, content).await?;
+
+        return Ok(());
+    }
 
     let mut channel = command.channel_id;
     let author_user = command.user()?;
@@ -217,10 +372,11 @@ async fn slash_bg(ctx: Arc<Context>, mut command: InteractionCommand) -> Result<
     }
 
     let difficulty = difficulty.unwrap_or_default();
+    let kind = kind.unwrap_or_default();
 
     let state = match mode {
         Some(BgGameMode::Osu) | None => {
-            let components = bg_components();
+            let components = bg_components(kind);
 
             let content = format!(
                 "<@{author}> select which tags should be included \
@@ -245,10 +401,12 @@ async fn slash_bg(ctx: Arc<Context>, mut command: InteractionCommand) -> Result<
                 effects: Effects::empty(),
                 excluded: MapsetTags::empty(),
                 included: MapsetTags::empty(),
+                kind,
+                settings,
             }
         }
         Some(BgGameMode::Mania) => {
-            let mapsets = match ctx.psql().get_all_tags_mapset(GameMode::Mania).await {
+            let mut mapsets = match ctx.psql().get_all_tags_mapset(GameMode::Mania).await {
                 Ok(mapsets) => mapsets,
                 Err(err) => {
                     let _ = command.error(&ctx, GENERAL_ISSUE).await;
@@ -257,10 +415,27 @@ async fn slash_bg(ctx: Arc<Context>, mut command: InteractionCommand) -> Result<
                 }
             };
 
-            let content = format!(
-                "Starting mania background guessing game with {} different backgrounds",
-                mapsets.len()
-            );
+            if matches!(kind, BgGameKind::Audio) {
+                retain_previews(&mut mapsets).await;
+
+                if mapsets.is_empty() {
+                    let content = "There are no audio previews for mania mapsets";
+                    command.error_callback(&ctx, content).await?;
+
+                    return Ok(());
+                }
+            }
+
+            let content = match kind {
+                BgGameKind::Background => format!(
+                    "Starting mania background guessing game with {} different backgrounds",
+                    mapsets.len()
+                ),
+                BgGameKind::Audio => format!(
+                    "Starting mania audio guessing game with {} different mapsets",
+                    mapsets.len()
+                ),
+            };
 
             let builder = MessageBuilder::new().embed(content);
 
@@ -279,6 +454,8 @@ async fn slash_bg(ctx: Arc<Context>, mut command: InteractionCommand) -> Result<
                 mapsets,
                 Effects::empty(),
                 difficulty,
+                kind,
+                settings,
             );
 
             GameState::Running {
@@ -292,7 +469,7 @@ async fn slash_bg(ctx: Arc<Context>, mut command: InteractionCommand) -> Result<
     Ok(())
 }
 
-fn bg_components() -> Vec<Component> {
+fn bg_components(kind: BgGameKind) -> Vec<Component> {
     let options = vec![
         SelectMenuOption {
             default: false,
@@ -481,6 +658,41 @@ fn bg_components() -> Vec<Component> {
             label: "Invert".to_owned(),
             value: "invert".to_owned(),
         },
+        SelectMenuOption {
+            default: false,
+            description: Some("Pixelate the image".to_owned()),
+            emoji: None,
+            label: "Pixelate".to_owned(),
+            value: "pixelate".to_owned(),
+        },
+        SelectMenuOption {
+            default: false,
+            description: Some("Rotate the image by 90, 180, or 270 degrees".to_owned()),
+            emoji: None,
+            label: "Rotate".to_owned(),
+            value: "rotate".to_owned(),
+        },
+        SelectMenuOption {
+            default: false,
+            description: Some("Shuffle the color channels".to_owned()),
+            emoji: None,
+            label: "Channel shuffle".to_owned(),
+            value: "channel_shuffle".to_owned(),
+        },
+        SelectMenuOption {
+            default: false,
+            description: Some("Split the image into tiles and shuffle them".to_owned()),
+            emoji: None,
+            label: "Scramble".to_owned(),
+            value: "scramble".to_owned(),
+        },
+        SelectMenuOption {
+            default: false,
+            description: Some("Only show the edges of the image".to_owned()),
+            emoji: None,
+            label: "Edge detection".to_owned(),
+            value: "edge_detect".to_owned(),
+        },
     ];
 
     let effects_menu = SelectMenu {
@@ -496,10 +708,43 @@ fn bg_components() -> Vec<Component> {
         components: vec![Component::SelectMenu(effects_menu)],
     };
 
-    vec![
-        Component::ActionRow(include_row),
-        Component::ActionRow(exclude_row),
-        Component::ActionRow(effects_row),
-        Component::ActionRow(button_row),
-    ]
+    let presets = BgPreset::ALL
+        .into_iter()
+        .map(|preset| SelectMenuOption {
+            default: false,
+            description: Some(preset.description().to_owned()),
+            emoji: None,
+            label: preset.name().to_owned(),
+            value: preset.value().to_owned(),
+        })
+        .collect();
+
+    let preset_menu = SelectMenu {
+        custom_id: "bg_start_preset".to_owned(),
+        disabled: false,
+        max_values: Some(1),
+        min_values: Some(1),
+        options: presets,
+        placeholder: Some("Choose a preset of effects and difficulty".to_owned()),
+    };
+
+    let preset_row = ActionRow {
+        components: vec![Component::SelectMenu(preset_menu)],
+    };
+
+    // Effects and presets only apply to images
+    match kind {
+        BgGameKind::Background => vec![
+            Component::ActionRow(include_row),
+            Component::ActionRow(exclude_row),
+            Component::ActionRow(effects_row),
+            Component::ActionRow(preset_row),
+            Component::ActionRow(button_row),
+        ],
+        BgGameKind::Audio => vec![
+            Component::ActionRow(include_row),
+            Component::ActionRow(exclude_row),
+            Component::ActionRow(button_row),
+        ],
+    }
 }
@@ -2,34 +2,48 @@ use std::{collections::BTreeMap, sync::Arc};
 
 use eyre::Result;
 use hashbrown::HashSet;
-use twilight_model::{channel::Message, id::Id};
+use twilight_model::id::Id;
 
 use crate::{
     commands::osu::UserValue,
+    core::commands::CommandOrigin,
     embeds::{RankingEntry, RankingKindData},
     pagination::RankingPagination,
-    util::{constants::GENERAL_ISSUE, ChannelExt},
+    util::constants::GENERAL_ISSUE,
     Context,
 };
 
-pub async fn leaderboard(ctx: Arc<Context>, msg: &Message, global: bool) -> Result<()> {
-    let mut scores = match ctx.psql().all_bggame_scores().await {
+/// Global scores are the all-time scores across all servers,
+/// server scores only contain points that were gained within the server
+pub async fn leaderboard(ctx: Arc<Context>, orig: CommandOrigin<'_>, global: bool) -> Result<()> {
+    let guild = orig.guild_id();
+
+    let scores_res = match guild.filter(|_| !global) {
+        Some(guild) => match ctx.psql().guild_bggame_scores(guild).await {
+            // Servers that have no own scores yet show the all-time scores of their members
+            Ok(scores) if scores.is_empty() => {
+                ctx.psql().all_bggame_scores().await.map(|mut scores| {
+                    let members: HashSet<_> = ctx.cache.members(guild, |id| id.get());
+                    scores.retain(|(id, _)| members.contains(id));
+
+                    scores
+                })
+            }
+            res => res,
+        },
+        None => ctx.psql().all_bggame_scores().await,
+    };
+
+    let mut scores = match scores_res {
         Ok(scores) => scores,
         Err(err) => {
-            let _ = msg.error(&ctx, GENERAL_ISSUE).await;
+            let _ = orig.error(&ctx, GENERAL_ISSUE).await;
 
             return Err(err.wrap_err("failed to get bggame scores"));
         }
     };
 
-    let guild = msg.guild_id;
-
-    if let Some(guild) = guild.filter(|_| !global) {
-        let members: HashSet<_> = ctx.cache.members(guild, |id| id.get());
-        scores.retain(|(id, _)| members.contains(id));
-    }
-
-    let author = msg.author.id.get();
+    let author = orig.user_id()?.get();
 
     scores.sort_unstable_by(|(_, a), (_, b)| b.cmp(a));
     let author_idx = scores.iter().position(|(user, _)| *user == author);
@@ -72,6 +86,6 @@ pub async fn leaderboard(ctx: Arc<Context>, msg: &Message, global: bool) -> Resu
     let data = RankingKindData::BgScores { global, scores };
 
     RankingPagination::builder(users, total, author_idx, data)
-        .start(ctx, msg.into())
+        .start(ctx, orig)
         .await
 }
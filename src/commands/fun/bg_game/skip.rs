@@ -47,7 +47,7 @@ pub async fn skip(ctx: Arc<Context>, msg: &Message) -> Result<()> {
         }
         None => {
             let content = format!(
-                "The background guessing game must be started with `/bg`.\n\
+                "The background guessing game must be started with `/bg start`.\n\
                 If slash commands are not available in your server, \
                 try [re-inviting the bot]({INVITE_LINK})."
             );
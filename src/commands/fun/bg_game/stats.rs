@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::id::{marker::UserMarker, Id};
+
+use crate::{
+    core::commands::CommandOrigin,
+    embeds::{BgStatsEmbed, EmbedData},
+    util::{builder::MessageBuilder, constants::GENERAL_ISSUE},
+    Context,
+};
+
+pub async fn stats(
+    ctx: Arc<Context>,
+    orig: CommandOrigin<'_>,
+    user: Option<Id<UserMarker>>,
+) -> Result<()> {
+    let guild = match orig.guild_id() {
+        Some(guild) => guild,
+        None => {
+            let content = "Background game stats are only available in servers";
+
+            return orig.error(&ctx, content).await;
+        }
+    };
+
+    let user = match user {
+        Some(user) => user,
+        None => orig.user_id()?,
+    };
+
+    let stats_fut = ctx.psql().get_bggame_guild_stats(guild, user);
+    let guild_scores_fut = ctx.psql().guild_bggame_scores(guild);
+    let global_fut = ctx.psql().get_bggame_global_rank(user);
+
+    let (stats, mut guild_scores, global) =
+        match tokio::try_join!(stats_fut, guild_scores_fut, global_fut) {
+            Ok(tuple) => tuple,
+            Err(err) => {
+                let _ = orig.error(&ctx, GENERAL_ISSUE).await;
+
+                return Err(err.wrap_err("failed to get bggame stats"));
+            }
+        };
+
+    let stats = match stats {
+        Some(stats) => stats,
+        None => {
+            let content =
+                format!("<@{user}> has not played the background game in this server yet");
+
+            return orig.error(&ctx, content).await;
+        }
+    };
+
+    let user_id = user.get();
+
+    guild_scores.sort_unstable_by(|(_, a), (_, b)| b.cmp(a));
+
+    let guild_rank = guild_scores
+        .iter()
+        .position(|(id, _)| *id == user_id)
+        .map(|idx| (idx + 1, guild_scores.len()));
+
+    let guild_name = ctx
+        .cache
+        .guild(guild, |g| g.name().to_owned())
+        .unwrap_or_else(|_| "this server".to_owned());
+
+    let embed = BgStatsEmbed::new(user, &guild_name, stats, guild_rank, global).build();
+    let builder = MessageBuilder::new().embed(embed);
+    orig.create_message(&ctx, &builder).await?;
+
+    Ok(())
+}
@@ -26,7 +26,7 @@ pub async fn stop(ctx: Arc<Context>, msg: &Message) -> Result<()> {
             msg.error(&ctx, content).await?;
         }
         None => {
-            let content = "No running game in this channel. Start one with `/bg`.";
+            let content = "No running game in this channel. Start one with `/bg start`.";
             msg.error(&ctx, content).await?;
         }
     }
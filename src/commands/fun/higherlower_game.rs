@@ -4,7 +4,7 @@ use command_macros::SlashCommand;
 use eyre::{Result, WrapErr};
 use hashbrown::HashSet;
 use rosu_v2::prelude::GameMode;
-use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_interactions::command::{CommandModel, CommandOption, CreateCommand, CreateOption};
 use twilight_model::id::Id;
 
 use crate::{
@@ -27,6 +27,14 @@ pub enum HigherLower {
     ScorePp(HigherLowerScorePp),
     #[command(name = "farm")]
     FarmMaps(HigherLowerFarmMaps),
+    #[command(name = "rank")]
+    RankPlayers(HigherLowerRankPlayers),
+    #[command(name = "playcount")]
+    MapPlaycount(HigherLowerMapPlaycount),
+    #[command(name = "stars")]
+    MapStars(HigherLowerMapStars),
+    #[command(name = "daily")]
+    Daily(HigherLowerDaily),
     #[command(name = "leaderboard")]
     Leaderboard(HigherLowerLeaderboard),
 }
@@ -56,23 +64,130 @@ pub struct HigherLowerScorePp {
 /// Is the amount of times the map appears in top scores higher or lower?
 pub struct HigherLowerFarmMaps;
 
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "rank",
+    help = "Is the player's global rank number higher or lower?\n\
+    The players are chosen randomly among those whose stats are cached by the bot \
+    so their rank might be slightly outdated. The higher the current score is, \
+    the closer the next rank will be to the previous one."
+)]
+/// Is the player's global rank number higher or lower?
+pub struct HigherLowerRankPlayers {
+    /// Specify a gamemode
+    mode: Option<GameModeOption>,
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "playcount",
+    help = "Does the map have a higher or lower playcount?\n\
+    The maps are chosen randomly among those provided by [osutracker](https://osutracker.com)."
+)]
+/// Does the map have a higher or lower playcount?
+pub struct HigherLowerMapPlaycount;
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "stars",
+    help = "Is the map's star rating with the given mods higher or lower?\n\
+    The maps are chosen randomly among those provided by [osutracker](https://osutracker.com) \
+    and the mods are chosen randomly among NM, HD, HR, DT, EZ, HT, HDHR, and HDDT."
+)]
+/// Is the map's star rating with the given mods higher or lower?
+pub struct HigherLowerMapStars;
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "daily",
+    help = "Play today's daily challenge.\n\
+    Everyone gets the same sequence of entries for the day so scores can be compared \
+    in the daily leaderboard. You only get a single attempt per day and version.\n\
+    The Score PP version always uses osu!standard.\n\
+    A new challenge starts every day at midnight UTC."
+)]
+/// Play today's daily challenge
+pub struct HigherLowerDaily {
+    /// Specify the version of the challenge, defaults to score pp
+    version: Option<HlDailyVersion>,
+}
+
+#[derive(Copy, Clone, CommandOption, CreateOption)]
+pub enum HlDailyVersion {
+    #[option(name = "Score PP", value = "score_pp")]
+    ScorePp,
+    #[option(name = "Farm", value = "farm")]
+    FarmMaps,
+}
+
+impl From<HlDailyVersion> for HlVersion {
+    fn from(version: HlDailyVersion) -> Self {
+        match version {
+            HlDailyVersion::ScorePp => Self::ScorePp,
+            HlDailyVersion::FarmMaps => Self::FarmMaps,
+        }
+    }
+}
+
 #[derive(CommandModel, CreateCommand)]
 #[command(name = "leaderboard")]
 /// Get the server leaderboard for higherlower highscores
 pub struct HigherLowerLeaderboard {
     /// Specify the version to get the highscores of
     version: HlVersion,
+    /// Show the global leaderboard of today's daily challenge instead
+    daily: Option<bool>,
 }
 
 async fn slash_higherlower(ctx: Arc<Context>, mut command: InteractionCommand) -> Result<()> {
     let args = HigherLower::from_interaction(command.input_data())?;
 
     if let HigherLower::Leaderboard(ref args) = args {
-        return higherlower_leaderboard(ctx, command, args.version).await;
+        let daily = args.daily.unwrap_or(false);
+
+        return higherlower_leaderboard(ctx, command, args.version, daily).await;
     }
 
     let user = command.user_id()?;
 
+    let daily_version = match args {
+        HigherLower::Daily(ref args) => {
+            let version = args.version.map_or(HlVersion::ScorePp, HlVersion::from);
+            let day = GameState::today();
+
+            let attempted = match ctx
+                .psql()
+                .has_higherlower_daily_attempt(user.get(), version, day)
+                .await
+            {
+                Ok(attempted) => attempted,
+                Err(err) => {
+                    let _ = command.error(&ctx, GENERAL_ISSUE).await;
+
+                    return Err(err.wrap_err("failed to check daily attempt"));
+                }
+            };
+
+            if attempted {
+                let tomorrow = day
+                    .next_day()
+                    .map_or(0, |day| day.midnight().assume_utc().unix_timestamp());
+
+                let content = format!(
+                    "You already played today's daily challenge, \
+                    the next one starts <t:{tomorrow}:R>"
+                );
+
+                command.error(&ctx, content).await?;
+
+                return Ok(());
+            }
+
+            Some((version, day))
+        }
+        _ => None,
+    };
+
     if let Some(game) = ctx.hl_games().lock(&user).await.remove() {
         let components = HlComponents::disabled();
         let builder = MessageBuilder::new().components(components);
@@ -93,6 +208,20 @@ async fn slash_higherlower(ctx: Arc<Context>, mut command: InteractionCommand) -
             GameState::score_pp(&ctx, &command, mode).await
         }
         HigherLower::FarmMaps(_) => GameState::farm_maps(&ctx, &command).await,
+        HigherLower::RankPlayers(args) => {
+            let mode = match args.mode.map(GameMode::from) {
+                Some(mode) => mode,
+                None => ctx.user_config(user).await?.mode.unwrap_or(GameMode::Osu),
+            };
+
+            GameState::rank_players(&ctx, &command, mode).await
+        }
+        HigherLower::MapPlaycount(_) => GameState::map_playcount(&ctx, &command).await,
+        HigherLower::MapStars(_) => GameState::map_stars(&ctx, &command).await,
+        HigherLower::Daily(_) => match daily_version {
+            Some((version, _)) => GameState::daily(&ctx, &command, version).await,
+            None => unreachable!(),
+        },
         HigherLower::Leaderboard(_) => unreachable!(),
     };
 
@@ -105,6 +234,22 @@ async fn slash_higherlower(ctx: Arc<Context>, mut command: InteractionCommand) -
         }
     };
 
+    if let Some((version, day)) = daily_version {
+        let inserted = ctx
+            .psql()
+            .insert_higherlower_daily_attempt(user.get(), version, day)
+            .await
+            .wrap_err("failed to insert daily attempt")?;
+
+        // Another game was started in the meanwhile
+        if !inserted {
+            let content = "You already played today's daily challenge";
+            command.error(&ctx, content).await?;
+
+            return Ok(());
+        }
+    }
+
     let embed = game.make_embed().await;
     let components = HlComponents::higherlower();
     let builder = MessageBuilder::new().embed(embed).components(components);
@@ -121,18 +266,43 @@ async fn higherlower_leaderboard(
     ctx: Arc<Context>,
     mut command: InteractionCommand,
     version: HlVersion,
+    daily: bool,
 ) -> Result<()> {
-    let guild = match command.guild_id {
-        Some(guild) => guild,
-        None => {
-            let content = "That command is only available in servers";
+    let scores_res = if daily {
+        if !matches!(version, HlVersion::ScorePp | HlVersion::FarmMaps) {
+            let content = "There is only a daily challenge for the Score PP and Farm versions";
             command.error(&ctx, content).await?;
 
             return Ok(());
         }
+
+        // Daily challenges are the same for everyone so the leaderboard is global
+        ctx.psql()
+            .get_higherlower_daily_scores(version, GameState::today())
+            .await
+    } else {
+        let guild = match command.guild_id {
+            Some(guild) => guild,
+            None => {
+                let content = "That command is only available in servers";
+                command.error(&ctx, content).await?;
+
+                return Ok(());
+            }
+        };
+
+        ctx.psql()
+            .get_higherlower_scores(version)
+            .await
+            .map(|mut scores| {
+                let members: HashSet<_> = ctx.cache.members(guild, |id| id.get());
+                scores.retain(|(id, _)| members.contains(id));
+
+                scores
+            })
     };
 
-    let mut scores = match ctx.psql().get_higherlower_scores(version).await {
+    let mut scores = match scores_res {
         Ok(scores) => scores,
         Err(err) => {
             let _ = command.error(&ctx, GENERAL_ISSUE).await;
@@ -141,9 +311,6 @@ async fn higherlower_leaderboard(
         }
     };
 
-    let members: HashSet<_> = ctx.cache.members(guild, |id| id.get());
-    scores.retain(|(id, _)| members.contains(id));
-
     let author = command.user_id()?;
 
     scores.sort_unstable_by(|(_, a), (_, b)| b.cmp(a));
@@ -182,7 +349,11 @@ async fn higherlower_leaderboard(
     }
 
     let total = scores.len();
-    let data = RankingKindData::HlScores { scores, version };
+    let data = RankingKindData::HlScores {
+        scores,
+        version,
+        daily,
+    };
 
     RankingPagination::builder(users, total, author_idx, data)
         .start_by_update()
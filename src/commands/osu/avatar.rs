@@ -25,6 +25,7 @@ use super::{get_user, require_link, UserArgs};
 #[command(name = "avatar")]
 /// Display someone's osu! profile picture
 pub struct Avatar<'a> {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
@@ -60,6 +60,7 @@ struct BadgesQuery_ {
 #[command(name = "user")]
 /// Display all badges of a user
 pub struct BadgesUser {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<String>,
     /// Choose how the badges should be ordered
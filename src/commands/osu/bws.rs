@@ -33,6 +33,7 @@ There are various formulas around but this command uses `rank^(0.9937^(badges^2)
 )]
 /// Show the badge weighted seeding for an osu!standard player
 pub struct Bws<'a> {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
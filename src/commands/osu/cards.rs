@@ -95,6 +95,7 @@ static HTML_TEMPLATE: Lazy<Handlebars<'static>> = Lazy::new(|| {
 pub struct Card {
     /// Specify a gamemode
     mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<String>,
     #[command(
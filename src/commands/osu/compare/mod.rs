@@ -49,11 +49,15 @@ pub enum Compare<'a> {
 )]
 /// Compare a score (same as `/cs`)
 pub struct CompareScore<'a> {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
-    #[command(help = "Specify a map either by map url or map id.\n\
-    If none is specified, it will search in the recent channel history \
-    and pick the first map it can find.")]
+    #[command(
+        autocomplete = true,
+        help = "Specify a map either by map url or map id.\n\
+        If none is specified, it will search in the recent channel history \
+        and pick the first map it can find."
+    )]
     /// Specify a map url or map id
     map: Option<Cow<'a, str>>,
     /// Choose how the scores should be ordered
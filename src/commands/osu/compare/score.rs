@@ -40,11 +40,15 @@ use super::{CompareScore, CompareScoreOrder};
 )]
 /// Compare a score
 pub struct Cs<'a> {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
-    #[command(help = "Specify a map either by map url or map id.\n\
+    #[command(
+        autocomplete = true,
+        help = "Specify a map either by map url or map id.\n\
         If none is specified, it will search in the recent channel history \
-        and pick the first map it can find.")]
+        and pick the first map it can find."
+    )]
     /// Specify a map url or map id
     map: Option<Cow<'a, str>>,
     /// Choose how the scores should be ordered
@@ -372,16 +376,16 @@ pub(super) async fn score(
                 None => 0,
             };
 
-            let msgs = match ctx.retrieve_channel_history(orig.channel_id()).await {
-                Ok(msgs) => msgs,
+            let map_id = match ctx.recent_map_id(orig.channel_id(), idx).await {
+                Ok(map_id) => map_id,
                 Err(err) => {
                     let _ = orig.error(&ctx, GENERAL_ISSUE).await;
 
-                    return Err(err.wrap_err("failed to retrieve channel history"));
+                    return Err(err.wrap_err("failed to get recent map"));
                 }
             };
 
-            match MapIdType::map_from_msgs(&msgs, idx) {
+            match map_id {
                 Some(id) => id,
                 None if idx == 0 => {
                     let content =
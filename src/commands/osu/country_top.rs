@@ -44,6 +44,7 @@ pub struct CountryTop {
     reverse: Option<bool>,
     /// Search for a specific artist, title, difficulty, or mapper
     query: Option<String>,
+    #[command(autocomplete = true)]
     /// Only keep scores from this username
     name: Option<String>,
     #[command(
@@ -31,12 +31,16 @@ use super::{get_beatmap_user_score, get_user, require_link, HasMods, ModsResult,
 #[command(name = "fix")]
 /// Display a user's pp after unchoking their score on a map
 pub struct Fix<'a> {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
-    #[command(help = "Specify a map either by map url or map id.\n\
+    #[command(
+        autocomplete = true,
+        help = "Specify a map either by map url or map id.\n\
         If none is specified, it will search in the recent channel history \
         and pick the first map it can find.\
-        Alternatively, you can also provide a score url.")]
+        Alternatively, you can also provide a score url."
+    )]
     /// Specify a map url or map id
     map: Option<String>,
     #[command(
@@ -222,8 +226,8 @@ async fn fix(ctx: Arc<Context>, orig: CommandOrigin<'_>, args: FixArgs<'_>) -> R
             return orig.error(&ctx, content).await;
         }
         None => {
-            let msgs = match ctx.retrieve_channel_history(orig.channel_id()).await {
-                Ok(msgs) => msgs,
+            let map_id = match ctx.recent_map_id(orig.channel_id(), 0).await {
+                Ok(map_id) => map_id,
                 Err(err) => {
                     let _ = orig.error(&ctx, GENERAL_ISSUE).await;
 
@@ -231,7 +235,7 @@ async fn fix(ctx: Arc<Context>, orig: CommandOrigin<'_>, args: FixArgs<'_>) -> R
                 }
             };
 
-            match MapIdType::map_from_msgs(&msgs, 0) {
+            match map_id {
                 Some(id) => request_by_map(&ctx, &orig, id, name.as_str(), mods).await,
                 None => {
                     let content = "No beatmap specified and none found in recent channel history. \
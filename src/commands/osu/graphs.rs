@@ -56,6 +56,7 @@ pub enum Graph {
 #[command(name = "medals")]
 /// Display a user's medal progress over time
 pub struct GraphMedals {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<String>,
     #[command(
@@ -71,6 +72,7 @@ pub struct GraphMedals {
 #[command(name = "playcount_replays")]
 /// Display a user's playcount and replays watched over time
 pub struct GraphPlaycountReplays {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<String>,
     #[command(
@@ -94,6 +96,7 @@ pub struct GraphPlaycountReplays {
 pub struct GraphRank {
     /// Specify a gamemode
     mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<String>,
     #[command(
@@ -109,6 +112,7 @@ pub struct GraphRank {
 #[command(name = "sniped")]
 /// Display sniped users of the past 8 weeks
 pub struct GraphSniped {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<String>,
     #[command(
@@ -124,6 +128,7 @@ pub struct GraphSniped {
 #[command(name = "snipe_count")]
 /// Display how a user's national #1 count progressed
 pub struct GraphSnipeCount {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<String>,
     #[command(
@@ -147,6 +152,7 @@ pub struct GraphTop {
     order: GraphTopOrder,
     /// Specify a gamemode
     mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<String>,
     /// Specify a timezone (only relevant when ordered by `Time`)
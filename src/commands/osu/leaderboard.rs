@@ -27,9 +27,12 @@ use super::{HasMods, ModsResult};
 #[command(name = "leaderboard")]
 /// Display the global leaderboard of a map
 pub struct Leaderboard<'a> {
-    #[command(help = "Specify a map either by map url or map id.\n\
+    #[command(
+        autocomplete = true,
+        help = "Specify a map either by map url or map id.\n\
         If none is specified, it will search in the recent channel history \
-        and pick the first map it can find.")]
+        and pick the first map it can find."
+    )]
     /// Specify a map url or map id
     map: Option<Cow<'a, str>>,
     #[command(
@@ -173,8 +176,8 @@ async fn leaderboard(
             return orig.error(&ctx, content).await;
         }
         None => {
-            let msgs = match ctx.retrieve_channel_history(orig.channel_id()).await {
-                Ok(msgs) => msgs,
+            let map_id = match ctx.recent_map_id(orig.channel_id(), 0).await {
+                Ok(map_id) => map_id,
                 Err(err) => {
                     let _ = orig.error(&ctx, GENERAL_ISSUE).await;
 
@@ -182,7 +185,7 @@ async fn leaderboard(
                 }
             };
 
-            match MapIdType::map_from_msgs(&msgs, 0) {
+            match map_id {
                 Some(id) => id,
                 None => {
                     let content = "No beatmap specified and none found in recent channel history. \
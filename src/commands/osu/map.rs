@@ -42,9 +42,12 @@ use super::{HasMods, ModsResult};
 )]
 /// Display a bunch of stats about a map(set)
 pub struct Map<'a> {
-    #[command(help = "Specify a map either by map url or map id.\n\
-    If none is specified, it will search in the recent channel history \
-    and pick the first map it can find.")]
+    #[command(
+        autocomplete = true,
+        help = "Specify a map either by map url or map id.\n\
+        If none is specified, it will search in the recent channel history \
+        and pick the first map it can find."
+    )]
     /// Specify a map url or map id
     map: Option<Cow<'a, str>>,
     #[command(
@@ -254,16 +257,16 @@ async fn map(ctx: Arc<Context>, orig: CommandOrigin<'_>, args: MapArgs<'_>) -> R
     let map_id = if let Some(id) = map {
         id
     } else {
-        let msgs = match ctx.retrieve_channel_history(orig.channel_id()).await {
-            Ok(msgs) => msgs,
+        let map_id = match ctx.recent_map(orig.channel_id(), 0).await {
+            Ok(map_id) => map_id,
             Err(err) => {
                 let _ = orig.error(&ctx, GENERAL_ISSUE).await;
 
-                return Err(err.wrap_err("failed to retrieve channel history"));
+                return Err(err.wrap_err("failed to get recent map"));
             }
         };
 
-        match MapIdType::from_msgs(&msgs, 0) {
+        match map_id {
             Some(id) => id,
             None => {
                 let content = "No beatmap specified and none found in recent channel history. \
@@ -42,6 +42,7 @@ pub struct Mapper<'a> {
     mapper: Cow<'a, str>,
     /// Specify a gamemode
     mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
@@ -0,0 +1,482 @@
+use hashbrown::{HashMap, HashSet};
+use rosu_v2::prelude::{GameMods, MatchGame, MatchScore};
+use twilight_interactions::command::{CommandOption, CreateOption};
+
+use crate::util::hasher::IntHasher;
+
+pub type Ratings = HashMap<u32, f32, IntHasher>;
+
+/// A way of rating the performance of each player in a match
+pub trait CostFormula {
+    /// Calculate the rating of each user that set a non-zero score.
+    ///
+    /// `tiebreaker` is true if the last game decided the match.
+    fn ratings(&self, games: &[MatchGame], tiebreaker: bool) -> Ratings;
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, CommandOption, CreateOption)]
+pub enum MatchCostFormula {
+    #[option(name = "Bathbot", value = "bathbot")]
+    Bathbot,
+    #[option(name = "Z-score", value = "zscore")]
+    ZScore,
+    #[option(name = "Median", value = "median")]
+    Median,
+    #[option(name = "Average score ratio", value = "average")]
+    Average,
+}
+
+impl MatchCostFormula {
+    pub fn formula(self) -> &'static dyn CostFormula {
+        match self {
+            Self::Bathbot => &BathbotFormula,
+            Self::ZScore => &ZScoreFormula,
+            Self::Median => &MedianFormula,
+            Self::Average => &AverageFormula,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Bathbot => "Bathbot",
+            Self::ZScore => "Z-score",
+            Self::Median => "Median",
+            Self::Average => "Average score ratio",
+        }
+    }
+}
+
+impl Default for MatchCostFormula {
+    fn default() -> Self {
+        Self::Bathbot
+    }
+}
+
+fn valid_scores(game: &MatchGame) -> impl Iterator<Item = &MatchScore> {
+    game.scores.iter().filter(|score| score.score > 0)
+}
+
+fn average(values: &[f32]) -> f32 {
+    values.iter().sum::<f32>() / values.len() as f32
+}
+
+/// Sorts the values in-place
+fn median(values: &mut [f32]) -> f32 {
+    values.sort_unstable_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+
+    if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Collect each user's value of each game and combine them into a rating
+fn collect_ratings(
+    games: &[MatchGame],
+    mut game_values: impl FnMut(&MatchGame) -> Vec<(u32, f32)>,
+    mut combine: impl FnMut(&mut [f32]) -> f32,
+) -> Ratings {
+    let mut values = HashMap::with_hasher(IntHasher);
+
+    for game in games {
+        for (user_id, value) in game_values(game) {
+            values.entry(user_id).or_insert_with(Vec::new).push(value);
+        }
+    }
+
+    values
+        .into_iter()
+        .map(|(user_id, mut values)| (user_id, combine(&mut values)))
+        .collect()
+}
+
+// flat additive bonus for each participated game
+const FLAT_PARTICIPATION_BONUS: f32 = 0.5;
+
+// exponent base, the higher - the higher is the difference
+// between players who played a lot and players who played fewer
+const BASE_PARTICIPATION_BONUS: f32 = 1.4;
+
+// exponent, low: logithmically ~ high: linear
+const EXP_PARTICIPATION_BONUS: f32 = 0.6;
+
+// instead of considering tb score once, consider it this many times
+const TIEBREAKER_BONUS: f32 = 2.0;
+
+// global multiplier per combination (if at least 3)
+const MOD_BONUS: f32 = 0.02;
+
+/// Score relative to the game's average with bonuses for
+/// participation, tiebreakers, and mod combinations.
+///
+/// Here's the [formula](https://i.imgur.com/7KFwcUS.png).
+pub struct BathbotFormula;
+
+impl CostFormula for BathbotFormula {
+    fn ratings(&self, games: &[MatchGame], tiebreaker: bool) -> Ratings {
+        let mut point_costs = HashMap::with_hasher(IntHasher);
+        let mut mods = HashMap::with_hasher(IntHasher);
+
+        // Calculate point scores for each score in each game
+        for game in games.iter() {
+            let score_sum: f32 = game.scores.iter().map(|s| s.score as f32).sum();
+            let avg = score_sum / valid_scores(game).count() as f32;
+
+            for score in valid_scores(game) {
+                mods.entry(score.user_id)
+                    .or_insert_with(HashSet::new)
+                    .insert(score.mods - GameMods::NoFail);
+
+                let point_cost = score.score as f32 / avg + FLAT_PARTICIPATION_BONUS;
+
+                point_costs
+                    .entry(score.user_id)
+                    .or_insert_with(Vec::new)
+                    .push(point_cost);
+            }
+        }
+
+        // Tiebreaker bonus
+        if let Some(game) = games.last().filter(|_| tiebreaker) {
+            point_costs
+                .iter_mut()
+                .filter(|(&user_id, _)| game.scores.iter().any(|score| score.user_id == user_id))
+                .filter_map(|(_, costs)| costs.last_mut())
+                .for_each(|value| {
+                    *value -= FLAT_PARTICIPATION_BONUS;
+                    *value *= TIEBREAKER_BONUS;
+                    *value += FLAT_PARTICIPATION_BONUS;
+                });
+        }
+
+        // Mod combinations bonus
+        let mods_count = mods
+            .into_iter()
+            .filter(|(_, mods)| mods.len() > 2)
+            .map(|(id, mods)| (id, mods.len() - 2));
+
+        for (user_id, count) in mods_count {
+            let multiplier = 1.0 + count as f32 * MOD_BONUS;
+
+            point_costs.entry(user_id).and_modify(|point_scores| {
+                point_scores
+                    .iter_mut()
+                    .for_each(|point_score| *point_score *= multiplier);
+            });
+        }
+
+        // Calculate match costs by combining point costs
+        point_costs
+            .into_iter()
+            .map(|(user_id, point_costs)| {
+                let costs_len = point_costs.len() as f32;
+                let mut match_cost = average(&point_costs);
+
+                let exp = match games.len() {
+                    1 => 0.0,
+                    len => (costs_len - 1.0) / (len as f32 - 1.0),
+                };
+
+                match_cost *= BASE_PARTICIPATION_BONUS.powf(exp.powf(EXP_PARTICIPATION_BONUS));
+
+                (user_id, match_cost)
+            })
+            .collect()
+    }
+}
+
+/// Average of the player's z-scores i.e. how many standard deviations
+/// their score was above or below the game's average.
+///
+/// A rating of 0 means the player performed exactly average.
+pub struct ZScoreFormula;
+
+impl CostFormula for ZScoreFormula {
+    fn ratings(&self, games: &[MatchGame], _: bool) -> Ratings {
+        let game_values = |game: &MatchGame| {
+            let scores: Vec<_> = valid_scores(game)
+                .map(|score| (score.user_id, score.score as f32))
+                .collect();
+
+            let values: Vec<_> = scores.iter().map(|(_, score)| *score).collect();
+            let avg = average(&values);
+
+            let variance = values
+                .iter()
+                .map(|score| (score - avg) * (score - avg))
+                .sum::<f32>()
+                / values.len() as f32;
+
+            let std_dev = variance.sqrt();
+
+            scores
+                .into_iter()
+                .map(|(user_id, score)| {
+                    let z = if std_dev > 0.0 {
+                        (score - avg) / std_dev
+                    } else {
+                        0.0
+                    };
+
+                    (user_id, z)
+                })
+                .collect()
+        };
+
+        collect_ratings(games, game_values, |values| average(values))
+    }
+}
+
+/// Median of the player's scores relative to each game's median score
+pub struct MedianFormula;
+
+impl CostFormula for MedianFormula {
+    fn ratings(&self, games: &[MatchGame], _: bool) -> Ratings {
+        let game_values = |game: &MatchGame| {
+            let mut values: Vec<_> = valid_scores(game).map(|score| score.score as f32).collect();
+
+            if values.is_empty() {
+                return Vec::new();
+            }
+
+            let median = median(&mut values);
+
+            valid_scores(game)
+                .map(|score| (score.user_id, score.score as f32 / median))
+                .collect()
+        };
+
+        collect_ratings(games, game_values, median)
+    }
+}
+
+/// Average of the player's scores relative to each game's average score
+pub struct AverageFormula;
+
+impl CostFormula for AverageFormula {
+    fn ratings(&self, games: &[MatchGame], _: bool) -> Ratings {
+        let game_values = |game: &MatchGame| {
+            let values: Vec<_> = valid_scores(game).map(|score| score.score as f32).collect();
+
+            let avg = average(&values);
+
+            valid_scores(game)
+                .map(|score| (score.user_id, score.score as f32 / avg))
+                .collect()
+        };
+
+        collect_ratings(games, game_values, |values| average(values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rosu_v2::prelude::OsuMatch;
+
+    use super::*;
+
+    // Two games of a 2v2 where blue wins the first and red the second:
+    //   game 1: 400k (blue), 200k (blue), 300k (red), 100k (red)
+    //   game 2: 300k (blue), 300k (blue), 300k (red), 500k (red)
+    const FIXTURE: &str = r#"{
+        "match": {
+            "id": 100,
+            "start_time": "2022-10-01T12:00:00+00:00",
+            "end_time": "2022-10-01T12:30:00+00:00",
+            "name": "TEST: (Blue) vs (Red)"
+        },
+        "events": [
+            {
+                "id": 1,
+                "detail": { "type": "match-created" },
+                "timestamp": "2022-10-01T12:00:00+00:00",
+                "user_id": 1
+            },
+            {
+                "id": 2,
+                "detail": { "type": "other" },
+                "timestamp": "2022-10-01T12:05:00+00:00",
+                "user_id": null,
+                "game": {
+                    "beatmap_id": 1000,
+                    "id": 10,
+                    "start_time": "2022-10-01T12:05:00+00:00",
+                    "end_time": "2022-10-01T12:10:00+00:00",
+                    "mode": "osu",
+                    "mode_int": 0,
+                    "scoring_type": "scorev2",
+                    "team_type": "team-vs",
+                    "mods": [],
+                    "scores": [
+                        SCORE_1_400000_BLUE,
+                        SCORE_2_200000_BLUE,
+                        SCORE_3_300000_RED,
+                        SCORE_4_100000_RED
+                    ]
+                }
+            },
+            {
+                "id": 3,
+                "detail": { "type": "other" },
+                "timestamp": "2022-10-01T12:15:00+00:00",
+                "user_id": null,
+                "game": {
+                    "beatmap_id": 1001,
+                    "id": 11,
+                    "start_time": "2022-10-01T12:15:00+00:00",
+                    "end_time": "2022-10-01T12:20:00+00:00",
+                    "mode": "osu",
+                    "mode_int": 0,
+                    "scoring_type": "scorev2",
+                    "team_type": "team-vs",
+                    "mods": [],
+                    "scores": [
+                        SCORE_1_300000_BLUE,
+                        SCORE_2_300000_BLUE,
+                        SCORE_3_300000_RED,
+                        SCORE_4_500000_RED
+                    ]
+                }
+            },
+            {
+                "id": 4,
+                "detail": { "type": "match-disbanded" },
+                "timestamp": "2022-10-01T12:30:00+00:00",
+                "user_id": null
+            }
+        ],
+        "users": [],
+        "first_event_id": 1,
+        "latest_event_id": 4,
+        "current_game_id": null
+    }"#;
+
+    fn score_json(user_id: u32, score: u32, team: &str) -> String {
+        format!(
+            r#"{{
+                "accuracy": 0.95,
+                "best_id": null,
+                "created_at": "2022-10-01T12:10:00+00:00",
+                "id": null,
+                "max_combo": 500,
+                "mode": "osu",
+                "mode_int": 0,
+                "mods": [],
+                "passed": true,
+                "perfect": 0,
+                "pp": null,
+                "rank": "A",
+                "replay": false,
+                "score": {score},
+                "statistics": {{
+                    "count_100": 20,
+                    "count_300": 480,
+                    "count_50": 0,
+                    "count_geki": 0,
+                    "count_katu": 0,
+                    "count_miss": 0
+                }},
+                "type": "legacy_match_score",
+                "user_id": {user_id},
+                "match": {{ "slot": 0, "team": "{team}", "pass": true }}
+            }}"#
+        )
+    }
+
+    fn fixture_games() -> Vec<MatchGame> {
+        let mut json = FIXTURE.to_owned();
+
+        for (user_id, score, team) in [
+            (1, 400_000, "blue"),
+            (2, 200_000, "blue"),
+            (3, 300_000, "red"),
+            (4, 100_000, "red"),
+            (1, 300_000, "blue"),
+            (2, 300_000, "blue"),
+            (3, 300_000, "red"),
+            (4, 500_000, "red"),
+        ] {
+            let placeholder = format!("SCORE_{user_id}_{score}_{}", team.to_uppercase());
+            json = json.replace(&placeholder, &score_json(user_id, score, team));
+        }
+
+        let mut osu_match: OsuMatch = serde_json::from_str(&json).expect("invalid fixture");
+
+        osu_match.drain_games().collect()
+    }
+
+    fn assert_ratings(formula: MatchCostFormula, expected: [f32; 4]) {
+        let ratings = formula.formula().ratings(&fixture_games(), false);
+        assert_eq!(ratings.len(), 4, "{}", formula.name());
+
+        for (user_id, expected) in (1..).zip(expected) {
+            let rating = ratings[&user_id];
+
+            assert!(
+                (rating - expected).abs() < 0.001,
+                "{}: user {user_id} has rating {rating}, expected {expected}",
+                formula.name(),
+            );
+        }
+    }
+
+    #[test]
+    fn average_formula() {
+        assert_ratings(
+            MatchCostFormula::Average,
+            [1.228_571, 0.828_571, 1.028_571, 0.914_286],
+        );
+    }
+
+    #[test]
+    fn median_formula() {
+        assert_ratings(MatchCostFormula::Median, [1.3, 0.9, 1.1, 1.033_333]);
+    }
+
+    #[test]
+    fn zscore_formula() {
+        assert_ratings(
+            MatchCostFormula::ZScore,
+            [0.382_14, -0.512_28, -0.065_07, 0.195_22],
+        );
+    }
+
+    #[test]
+    fn bathbot_formula() {
+        // Both games played so the participation bonus is 1.4 for everyone
+        assert_ratings(MatchCostFormula::Bathbot, [2.42, 1.86, 2.14, 1.98]);
+    }
+
+    #[test]
+    fn bathbot_tiebreaker() {
+        let games = fixture_games();
+        let formula = MatchCostFormula::Bathbot.formula();
+
+        let regular = formula.ratings(&games, false);
+        let tiebreaker = formula.ratings(&games, true);
+
+        // Player 4 had the best score on the last map so they benefit the most
+        let gain = |user_id| tiebreaker[&user_id] - regular[&user_id];
+        assert!((1..=3).all(|user_id| gain(4) > gain(user_id)));
+    }
+
+    #[test]
+    fn ignores_zero_scores() {
+        let mut games = fixture_games();
+        games[0].scores[3].score = 0;
+        games[1].scores[3].score = 0;
+
+        for formula in [
+            MatchCostFormula::Bathbot,
+            MatchCostFormula::ZScore,
+            MatchCostFormula::Median,
+            MatchCostFormula::Average,
+        ] {
+            let ratings = formula.formula().ratings(&games, false);
+            assert_eq!(ratings.len(), 3, "{}", formula.name());
+            assert!(!ratings.contains_key(&4), "{}", formula.name());
+        }
+    }
+}
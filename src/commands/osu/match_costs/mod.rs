@@ -3,28 +3,36 @@ use std::{
 };
 
 use command_macros::{command, SlashCommand};
-use eyre::{Report, Result};
+use eyre::{Report, Result, WrapErr};
 use hashbrown::{HashMap, HashSet};
 use rosu_v2::prelude::{
     GameMods, MatchGame, Osu, OsuError, OsuMatch, OsuResult, Team, TeamType, UserCompact,
 };
-use twilight_interactions::command::{CommandModel, CreateCommand};
+use serde::Serialize;
+use twilight_interactions::command::{CommandModel, CommandOption, CreateCommand, CreateOption};
 
 use crate::{
     core::commands::{prefix::Args, CommandOrigin},
     embeds::{EmbedData, MatchCostEmbed},
     util::{
-        builder::MessageBuilder, constants::OSU_API_ISSUE, hasher::IntHasher,
-        interaction::InteractionCommand, matcher, ChannelExt, InteractionCommandExt,
+        builder::MessageBuilder,
+        constants::{GENERAL_ISSUE, OSU_API_ISSUE},
+        hasher::IntHasher,
+        interaction::InteractionCommand,
+        matcher, ChannelExt, InteractionCommandExt,
     },
     Context,
 };
 
+pub use self::formula::{CostFormula, MatchCostFormula};
+
+mod formula;
+
 #[derive(CommandModel, CreateCommand, SlashCommand)]
 #[command(
     name = "matchcost",
     help = "Calculate a performance rating for each player in the given multiplayer match.\n\
-    Here's the current [formula](https://i.imgur.com/7KFwcUS.png).\n\
+    Here's the default [formula](https://i.imgur.com/7KFwcUS.png).\n\
     Additionally, scores with the EZ mod are multiplied by 1.7 beforehand.\n\n\
     Keep in mind that all bots use different formulas \
     so comparing with values from other bots makes no sense."
@@ -58,6 +66,29 @@ pub struct MatchCost<'a> {
     )]
     /// Specify the amount of maps to ignore at the end (defaults to 0)
     skip_last: Option<usize>,
+    #[command(help = "Additionally to the embed, attach a file that contains \
+    each player's score, accuracy, and mods on each map as well as their rating.\n\
+    The file also includes the applied `warmups`, `skip_last`, and `ez_mult` values \
+    so it can be pasted into spreadsheets as is.")]
+    /// Attach a file containing all scores and ratings
+    export: Option<MatchCostExport>,
+    #[command(help = "Choose how the performance rating should be calculated.\n\
+    - `Bathbot`: Score relative to the average score of each map with bonuses for \
+    participation, tiebreakers, and mod combinations (default)\n\
+    - `Z-score`: Average amount of standard deviations above or below the average score, \
+    0 means perfectly average\n\
+    - `Median`: Median of the scores relative to the median score of each map\n\
+    - `Average score ratio`: Average of the scores relative to the average score of each map")]
+    /// Specify the formula for the performance rating
+    formula: Option<MatchCostFormula>,
+}
+
+#[derive(Copy, Clone, CommandOption, CreateOption)]
+pub enum MatchCostExport {
+    #[option(name = "CSV", value = "csv")]
+    Csv,
+    #[option(name = "JSON", value = "json")]
+    Json,
 }
 
 impl<'m> MatchCost<'m> {
@@ -82,6 +113,8 @@ impl<'m> MatchCost<'m> {
             warmups,
             skip_last: None,
             ez_mult: None,
+            export: None,
+            formula: None,
         })
     }
 }
@@ -126,6 +159,8 @@ async fn matchcosts(ctx: Arc<Context>, orig: CommandOrigin<'_>, args: MatchCost<
         warmups,
         skip_last,
         ez_mult,
+        export,
+        formula,
     } = args;
 
     let match_id = match matcher::get_osu_match_id(&match_url) {
@@ -141,9 +176,10 @@ async fn matchcosts(ctx: Arc<Context>, orig: CommandOrigin<'_>, args: MatchCost<
     let warmups = warmups.unwrap_or(2);
     let ez_mult = ez_mult.unwrap_or(1.0);
     let skip_last = skip_last.unwrap_or(0);
+    let formula = formula.unwrap_or_default();
 
     // Retrieve the match
-    let (mut osu_match, games) = match ctx.osu().osu_match(match_id).await {
+    let (osu_match, games) = match ctx.osu().osu_match(match_id).await {
         Ok(mut osu_match) => {
             retrieve_previous(&mut osu_match, ctx.osu()).await?;
             let games_iter = osu_match.drain_games().skip(warmups);
@@ -211,14 +247,36 @@ async fn matchcosts(ctx: Arc<Context>, orig: CommandOrigin<'_>, args: MatchCost<
 
         (Some(description), None)
     } else {
-        let result = process_match(&games, osu_match.end_time.is_some(), &osu_match.users);
+        let finished = osu_match.end_time.is_some();
+        let result = process_match(&games, finished, &osu_match.users, formula);
 
         (None, Some(result))
     };
 
+    let attachment = match export.zip(match_result.as_ref()) {
+        Some((export, result)) => {
+            let settings = ExportSettings {
+                warmups,
+                skip_last,
+                ez_mult,
+                formula: formula.name(),
+            };
+
+            match export.attachment(&osu_match, &games, result, settings) {
+                Ok(attachment) => Some(attachment),
+                Err(err) => {
+                    let _ = orig.error(&ctx, GENERAL_ISSUE).await;
+
+                    return Err(err);
+                }
+            }
+        }
+        None => None,
+    };
+
     // Accumulate all necessary data
     // TODO: pagination(?)
-    let embed_data = match MatchCostEmbed::new(&mut osu_match, description, match_result) {
+    let embed_data = match MatchCostEmbed::new(&osu_match, description, match_result) {
         Some(data) => data,
         None => return orig.error(&ctx, TOO_MANY_PLAYERS_TEXT).await,
     };
@@ -249,6 +307,10 @@ async fn matchcosts(ctx: Arc<Context>, orig: CommandOrigin<'_>, args: MatchCost<
         content.push(':');
     }
 
+    if formula != MatchCostFormula::Bathbot {
+        content.insert_str(0, &format!("Formula: `{}`\n", formula.name()));
+    }
+
     // Creating the embed
     let mut builder = MessageBuilder::new().embed(embed);
 
@@ -256,6 +318,10 @@ async fn matchcosts(ctx: Arc<Context>, orig: CommandOrigin<'_>, args: MatchCost<
         builder = builder.content(content);
     }
 
+    if let Some((name, bytes)) = attachment {
+        builder = builder.attachment(name, bytes);
+    }
+
     orig.create_message(&ctx, &builder).await?;
 
     Ok(())
@@ -298,54 +364,21 @@ macro_rules! sort {
     };
 }
 
-// flat additive bonus for each participated game
-const FLAT_PARTICIPATION_BONUS: f32 = 0.5;
-
-// exponent base, the higher - the higher is the difference
-// between players who played a lot and players who played fewer
-const BASE_PARTICIPATION_BONUS: f32 = 1.4;
-
-// exponent, low: logithmically ~ high: linear
-const EXP_PARTICIPATION_BONUS: f32 = 0.6;
-
-// instead of considering tb score once, consider it this many times
-const TIEBREAKER_BONUS: f32 = 2.0;
-
-// global multiplier per combination (if at least 3)
-const MOD_BONUS: f32 = 0.02;
-
 pub fn process_match(
     games: &[MatchGame],
     finished: bool,
     users: &StdHashMap<u32, UserCompact>,
+    formula: MatchCostFormula,
 ) -> MatchResult {
     let mut teams = HashMap::with_hasher(IntHasher);
-    let mut point_costs = HashMap::with_hasher(IntHasher);
-    let mut mods = HashMap::with_hasher(IntHasher);
     let team_vs = games[0].team_type == TeamType::TeamVS;
     let mut match_scores = MatchScores(0, 0);
 
-    // Calculate point scores for each score in each game
+    // Count the won games of each team
     for game in games.iter() {
-        let score_sum: f32 = game.scores.iter().map(|s| s.score as f32).sum();
-
-        let avg = score_sum / game.scores.iter().filter(|s| s.score > 0).count() as f32;
         let mut team_scores = HashMap::with_capacity(team_vs as usize + 1);
 
         for score in game.scores.iter().filter(|s| s.score > 0) {
-            mods.entry(score.user_id)
-                .or_insert_with(HashSet::new)
-                .insert(score.mods - GameMods::NoFail);
-
-            let mut point_cost = score.score as f32 / avg;
-
-            point_cost += FLAT_PARTICIPATION_BONUS;
-
-            point_costs
-                .entry(score.user_id)
-                .or_insert_with(Vec::new)
-                .push(point_cost);
-
             teams.entry(score.user_id).or_insert(score.team);
 
             team_scores
@@ -362,55 +395,15 @@ pub fn process_match(
         match_scores.incr(winner_team);
     }
 
-    // Tiebreaker bonus
-    if let Some(game) = games
-        .last()
-        .filter(|_| finished && games.len() > 4 && match_scores.difference() == 1)
-    {
-        point_costs
-            .iter_mut()
-            .filter(|(&user_id, _)| game.scores.iter().any(|score| score.user_id == user_id))
-            .filter_map(|(_, costs)| costs.last_mut())
-            .for_each(|value| {
-                *value -= FLAT_PARTICIPATION_BONUS;
-                *value *= TIEBREAKER_BONUS;
-                *value += FLAT_PARTICIPATION_BONUS;
-            });
-    }
-
-    // Mod combinations bonus
-    let mods_count = mods
-        .into_iter()
-        .filter(|(_, mods)| mods.len() > 2)
-        .map(|(id, mods)| (id, mods.len() - 2));
-
-    for (user_id, count) in mods_count {
-        let multiplier = 1.0 + count as f32 * MOD_BONUS;
+    let tiebreaker = finished && games.len() > 4 && match_scores.difference() == 1;
+    let ratings = formula.formula().ratings(games, tiebreaker);
 
-        point_costs.entry(user_id).and_modify(|point_scores| {
-            point_scores
-                .iter_mut()
-                .for_each(|point_score| *point_score *= multiplier);
-        });
-    }
-
-    // Calculate match costs by combining point costs
+    // Split ratings into teams
     let mut data = HashMap::with_capacity(team_vs as usize + 1);
-    let mut highest_cost = 0.0;
+    let mut highest_cost = f32::NEG_INFINITY;
     let mut mvp_avatar_url = None;
 
-    for (user_id, point_costs) in point_costs {
-        let sum: f32 = point_costs.iter().sum();
-        let costs_len = point_costs.len() as f32;
-        let mut match_cost = sum / costs_len;
-
-        let exp = match games.len() {
-            1 => 0.0,
-            len => (costs_len - 1.0) / (len as f32 - 1.0),
-        };
-
-        match_cost *= BASE_PARTICIPATION_BONUS.powf(exp.powf(EXP_PARTICIPATION_BONUS));
-
+    for (user_id, match_cost) in ratings {
         data.entry(*teams.get(&user_id).unwrap())
             .or_insert_with(Vec::new)
             .push((user_id, match_cost));
@@ -471,6 +464,16 @@ pub enum MatchResult {
 }
 
 impl MatchResult {
+    /// All players with their rating
+    pub fn players(&self) -> impl Iterator<Item = &PlayerResult> {
+        let (first, second): (&[_], &[_]) = match self {
+            Self::TeamVS { blue, red, .. } => (blue.as_slice(), red.as_slice()),
+            Self::HeadToHead { players, .. } => (players.as_slice(), &[]),
+        };
+
+        first.iter().chain(second)
+    }
+
     fn team(
         mvp_avatar_url: String,
         match_scores: MatchScores,
@@ -520,3 +523,199 @@ impl MatchScores {
         max - min
     }
 }
+
+#[derive(Copy, Clone, Serialize)]
+struct ExportSettings {
+    warmups: usize,
+    skip_last: usize,
+    ez_mult: f32,
+    formula: &'static str,
+}
+
+#[derive(Serialize)]
+struct ExportMatch<'m> {
+    match_id: u32,
+    name: &'m str,
+    #[serde(flatten)]
+    settings: ExportSettings,
+    players: Vec<ExportPlayer<'m>>,
+    games: Vec<ExportGame>,
+}
+
+#[derive(Serialize)]
+struct ExportPlayer<'m> {
+    user_id: u32,
+    username: Option<&'m str>,
+    rating: f32,
+}
+
+#[derive(Serialize)]
+struct ExportGame {
+    /// One-based index of the map in the match, including warmups
+    map_idx: usize,
+    map_id: Option<u32>,
+    map: Option<String>,
+    scores: Vec<ExportScore>,
+}
+
+#[derive(Serialize)]
+struct ExportScore {
+    user_id: u32,
+    team: &'static str,
+    score: u32,
+    accuracy: f32,
+    mods: String,
+}
+
+impl MatchCostExport {
+    /// Returns the file name and content of the attachment
+    fn attachment(
+        self,
+        osu_match: &OsuMatch,
+        games: &[MatchGame],
+        result: &MatchResult,
+        settings: ExportSettings,
+    ) -> Result<(String, Vec<u8>)> {
+        let data = ExportMatch::new(osu_match, games, result, settings);
+        let match_id = osu_match.match_id;
+
+        let attachment = match self {
+            MatchCostExport::Csv => (format!("match_costs_{match_id}.csv"), data.to_csv()),
+            MatchCostExport::Json => {
+                let bytes =
+                    serde_json::to_vec_pretty(&data).wrap_err("failed to serialize match costs")?;
+
+                (format!("match_costs_{match_id}.json"), bytes)
+            }
+        };
+
+        Ok(attachment)
+    }
+}
+
+impl<'m> ExportMatch<'m> {
+    fn new(
+        osu_match: &'m OsuMatch,
+        games: &[MatchGame],
+        result: &MatchResult,
+        settings: ExportSettings,
+    ) -> Self {
+        let players = result
+            .players()
+            .map(|&(user_id, rating)| ExportPlayer {
+                user_id,
+                username: osu_match
+                    .users
+                    .get(&user_id)
+                    .map(|user| user.username.as_str()),
+                rating,
+            })
+            .collect();
+
+        let games = games
+            .iter()
+            .zip(settings.warmups + 1..)
+            .map(|(game, map_idx)| {
+                let map = game.map.as_ref().and_then(|map| {
+                    let mapset = map.mapset.as_ref()?;
+
+                    Some(format!(
+                        "{} - {} [{}]",
+                        mapset.artist, mapset.title, map.version
+                    ))
+                });
+
+                let scores = game
+                    .scores
+                    .iter()
+                    .filter(|score| score.score > 0)
+                    .map(|score| ExportScore {
+                        user_id: score.user_id,
+                        team: match score.team {
+                            Team::None => "none",
+                            Team::Blue => "blue",
+                            Team::Red => "red",
+                        },
+                        score: score.score,
+                        accuracy: score.accuracy,
+                        mods: (score.mods | game.mods).to_string(),
+                    })
+                    .collect();
+
+                ExportGame {
+                    map_idx,
+                    map_id: game.map.as_ref().map(|map| map.map_id),
+                    map,
+                    scores,
+                }
+            })
+            .collect();
+
+        Self {
+            match_id: osu_match.match_id,
+            name: &osu_match.name,
+            settings,
+            players,
+            games,
+        }
+    }
+
+    /// One row per player per map
+    fn to_csv(&self) -> Vec<u8> {
+        let ratings: HashMap<_, _, IntHasher> = self
+            .players
+            .iter()
+            .map(|player| (player.user_id, (player.username, player.rating)))
+            .collect();
+
+        let ExportSettings {
+            warmups,
+            skip_last,
+            ez_mult,
+            formula,
+        } = self.settings;
+
+        let mut csv = String::from(
+            "match_id,map_idx,map_id,map,user_id,username,team,score,accuracy,mods,rating,\
+            warmups,skip_last,ez_mult,formula\n",
+        );
+
+        for game in self.games.iter() {
+            let map_id = game.map_id.map_or_else(String::new, |id| id.to_string());
+            let map = csv_field(game.map.as_deref().unwrap_or_default());
+
+            for score in game.scores.iter() {
+                let (username, rating) = ratings
+                    .get(&score.user_id)
+                    .map_or((None, None), |(username, rating)| {
+                        (*username, Some(*rating))
+                    });
+
+                let _ = writeln!(
+                    csv,
+                    "{match_id},{map_idx},{map_id},{map},{user_id},{username},{team},{score},\
+                    {acc:.2},{mods},{rating},{warmups},{skip_last},{ez_mult},{formula}",
+                    match_id = self.match_id,
+                    map_idx = game.map_idx,
+                    user_id = score.user_id,
+                    username = csv_field(username.unwrap_or_default()),
+                    team = score.team,
+                    score = score.score,
+                    acc = score.accuracy,
+                    mods = score.mods,
+                    rating = rating.map_or_else(String::new, |rating| format!("{rating:.4}")),
+                );
+            }
+        }
+
+        csv.into_bytes()
+    }
+}
+
+fn csv_field(field: &str) -> Cow<'_, str> {
+    if field.contains(&[',', '"', '\n', '\r'][..]) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
@@ -47,6 +47,16 @@ pub struct MatchliveAdd<'a> {
     match_url: Cow<'a, str>,
     /// Choose if a new thread should be started
     thread: ThreadChannel,
+    #[command(
+        min_value = 0,
+        help = "Once games are finished, I'll keep a scoreboard message up to date \
+        that shows the team scores and match costs.\n\
+        Since warmup maps commonly want to be skipped for that, this option allows you \
+        to specify how many maps should be ignored in the beginning.\n\
+        If no value is specified, it defaults to 2."
+    )]
+    /// Specify the amount of warmups to ignore for the scoreboard (defaults to 2)
+    warmups: Option<usize>,
 }
 
 #[derive(CommandModel, CreateCommand)]
@@ -70,10 +80,14 @@ async fn slash_matchlive(ctx: Arc<Context>, mut command: InteractionCommand) ->
     "Live track a multiplayer match in a channel.\n\
     Similar to what an mp link does, I will keep a channel up \
     to date about events in a match.\n\
+    Once games are finished, I'll also keep a scoreboard message up to date \
+    that shows the team scores and match costs.\n\
+    As second argument you can specify how many warmups should be ignored \
+    for the scoreboard, defaults to 2.\n\
     Use the `matchliveremove` command to stop tracking the match."
 )]
-#[usage("[match url / match id]")]
-#[examples("58320988", "https://osu.ppy.sh/community/matches/58320988")]
+#[usage("[match url / match id] [amount of warmups]")]
+#[examples("58320988", "https://osu.ppy.sh/community/matches/58320988 1")]
 #[alias("ml", "mla", "matchliveadd", "mlt", "matchlivetrack")]
 #[bucket(MatchLive)]
 #[flags(AUTHORITY)]
@@ -81,9 +95,21 @@ async fn slash_matchlive(ctx: Arc<Context>, mut command: InteractionCommand) ->
 async fn prefix_matchlive(ctx: Arc<Context>, msg: &Message, mut args: Args<'_>) -> Result<()> {
     match args.next() {
         Some(arg) => {
+            let warmups = match args.next().map(str::parse) {
+                Some(Ok(warmups)) => Some(warmups),
+                Some(Err(_)) => {
+                    let content = "The second argument must be the amount of warmups";
+                    msg.error(&ctx, content).await?;
+
+                    return Ok(());
+                }
+                None => None,
+            };
+
             let args = MatchliveAdd {
                 match_url: arg.into(),
                 thread: ThreadChannel::Channel,
+                warmups,
             };
 
             matchlive(ctx, msg.into(), args).await
@@ -148,7 +174,11 @@ async fn matchlive(
     orig: CommandOrigin<'_>,
     args: MatchliveAdd<'_>,
 ) -> Result<()> {
-    let MatchliveAdd { match_url, thread } = args;
+    let MatchliveAdd {
+        match_url,
+        thread,
+        warmups,
+    } = args;
 
     let match_id = match parse_match_id(&match_url) {
         Ok(id) => id,
@@ -200,7 +230,9 @@ async fn matchlive(
         }
     }
 
-    let content: &str = match ctx.add_match_track(channel, match_id).await {
+    let warmups = warmups.unwrap_or(2);
+
+    let content: &str = match ctx.add_match_track(channel, match_id, warmups).await {
         MatchTrackResult::Added => match orig {
             CommandOrigin::Message { .. } => return Ok(()),
             CommandOrigin::Interaction { command } => {
@@ -0,0 +1,668 @@
+use std::{borrow::Cow, cmp::Ordering, fmt::Write, sync::Arc};
+
+use command_macros::SlashCommand;
+use eyre::{Report, Result};
+use hashbrown::{hash_map::Entry, HashMap};
+use rosu_v2::prelude::{MatchGame, OsuError, OsuMatch, Team, TeamType, Username};
+use twilight_interactions::command::{CommandModel, CommandOption, CreateCommand, CreateOption};
+
+use crate::{
+    core::{commands::checks::check_authority, Context},
+    pagination::MatchStagePagination,
+    util::{
+        builder::MessageBuilder,
+        constants::{GENERAL_ISSUE, OSU_API_ISSUE},
+        hasher::IntHasher,
+        interaction::InteractionCommand,
+        matcher, InteractionCommandExt,
+    },
+};
+
+use super::{process_match, retrieve_previous, MatchCostFormula};
+
+const MATCH_LIMIT: usize = 20;
+
+#[derive(CommandModel, CreateCommand, SlashCommand)]
+#[command(
+    name = "matchstage",
+    help = "Aggregate performance ratings across multiple multiplayer matches, \
+    e.g. all matches of a tournament stage.\n\
+    Lists of matches can be saved as stage for the server so they don't need to be \
+    specified over and over again."
+)]
+#[bucket(MatchCompare)]
+/// Aggregate performance ratings across multiple matches
+pub enum MatchStage<'a> {
+    #[command(name = "stats")]
+    Stats(MatchStageStats<'a>),
+    #[command(name = "save")]
+    Save(MatchStageSave<'a>),
+    #[command(name = "remove")]
+    Remove(MatchStageRemove<'a>),
+    #[command(name = "list")]
+    List(MatchStageList),
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "stats",
+    help = "Aggregate performance ratings, maps played, win rates, \
+    and per-map averages across multiple multiplayer matches.\n\
+    Either specify the matches directly or the name of a stage that was saved on this server."
+)]
+/// Aggregate stats across multiple matches
+pub struct MatchStageStats<'a> {
+    #[command(
+        help = "Specify match urls or match ids, separated by spaces or commas.\n\
+    At most 20 matches can be aggregated."
+    )]
+    /// Specify match urls or ids, separated by spaces or commas
+    matches: Option<Cow<'a, str>>,
+    /// Specify the name of a stage saved on this server
+    stage: Option<Cow<'a, str>>,
+    #[command(min_value = 0)]
+    /// Specify the amount of warmups to ignore in each match (defaults to 2)
+    warmups: Option<usize>,
+    /// Specify the formula for the performance rating
+    formula: Option<MatchCostFormula>,
+    /// Specify whether players or maps should be listed (defaults to players)
+    view: Option<MatchStageView>,
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "save",
+    help = "Save a list of matches as stage for this server.\n\
+    If a stage with the same name already exists, its matches will be replaced.\n\
+    Requires authority status."
+)]
+/// Save a list of matches as stage for this server
+pub struct MatchStageSave<'a> {
+    /// Specify a name for the stage
+    name: Cow<'a, str>,
+    /// Specify match urls or ids, separated by spaces or commas
+    matches: Cow<'a, str>,
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "remove",
+    help = "Remove a saved stage.\nRequires authority status."
+)]
+/// Remove a saved stage of this server
+pub struct MatchStageRemove<'a> {
+    /// Specify the name of the stage
+    name: Cow<'a, str>,
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "list")]
+/// List all saved stages of this server
+pub struct MatchStageList;
+
+#[derive(Copy, Clone, CommandOption, CreateOption)]
+pub enum MatchStageView {
+    #[option(name = "Players", value = "players")]
+    Players,
+    #[option(name = "Maps", value = "maps")]
+    Maps,
+}
+
+impl Default for MatchStageView {
+    fn default() -> Self {
+        Self::Players
+    }
+}
+
+async fn slash_matchstage(ctx: Arc<Context>, mut command: InteractionCommand) -> Result<()> {
+    match MatchStage::from_interaction(command.input_data())? {
+        MatchStage::Stats(args) => stats(ctx, command, args).await,
+        MatchStage::Save(args) => save(ctx, command, args).await,
+        MatchStage::Remove(args) => remove(ctx, command, args).await,
+        MatchStage::List(_) => list(ctx, command).await,
+    }
+}
+
+/// Parse match ids separated by whitespace or commas, skipping duplicates
+fn parse_match_ids(matches: &str) -> Result<Vec<u32>, String> {
+    let mut match_ids = Vec::new();
+
+    for arg in matches.split(|c: char| c == ',' || c.is_whitespace()) {
+        if arg.is_empty() {
+            continue;
+        }
+
+        match matcher::get_osu_match_id(arg) {
+            Some(id) if !match_ids.contains(&id) => match_ids.push(id),
+            Some(_) => {}
+            None => {
+                return Err(format!(
+                    "Failed to parse `{arg}`.\nBe sure it's a valid mp url or a match id."
+                ))
+            }
+        }
+    }
+
+    if match_ids.is_empty() {
+        return Err("You must specify at least one match".to_owned());
+    } else if match_ids.len() > MATCH_LIMIT {
+        return Err(format!("You can specify at most {MATCH_LIMIT} matches"));
+    }
+
+    Ok(match_ids)
+}
+
+/// Check that the name is of valid length and the command was used by an authority
+async fn check_stage_name(
+    ctx: &Context,
+    command: &InteractionCommand,
+    name: &str,
+) -> Result<Option<String>> {
+    if command.guild_id.is_none() {
+        return Ok(Some("Stages can only be managed in servers".to_owned()));
+    }
+
+    if !(1..=32).contains(&name.chars().count()) {
+        return Ok(Some(
+            "The stage name must be between 1 and 32 characters long".to_owned(),
+        ));
+    }
+
+    check_authority(ctx, command.user_id()?, command.guild_id).await
+}
+
+async fn stats(
+    ctx: Arc<Context>,
+    mut command: InteractionCommand,
+    args: MatchStageStats<'_>,
+) -> Result<()> {
+    let MatchStageStats {
+        matches,
+        stage,
+        warmups,
+        formula,
+        view,
+    } = args;
+
+    let (match_ids, title) = match (matches, stage, command.guild_id) {
+        (Some(matches), None, _) => match parse_match_ids(&matches) {
+            Ok(match_ids) => {
+                let title = format!("Stats across {} matches", match_ids.len());
+
+                (match_ids, title)
+            }
+            Err(content) => {
+                command.error(&ctx, content).await?;
+
+                return Ok(());
+            }
+        },
+        (None, Some(stage), Some(guild)) => match ctx.psql().get_match_stage(guild, &stage).await {
+            Ok(Some(match_ids)) => (match_ids, format!("Stats of stage `{stage}`")),
+            Ok(None) => {
+                let content = format!(
+                    "There is no stage `{stage}` on this server.\n\
+                    Use `/matchstage list` to see all saved stages."
+                );
+                command.error(&ctx, content).await?;
+
+                return Ok(());
+            }
+            Err(err) => {
+                let _ = command.error(&ctx, GENERAL_ISSUE).await;
+
+                return Err(err.wrap_err("failed to get match stage"));
+            }
+        },
+        (None, Some(_), None) => {
+            let content = "Saved stages can only be used in servers";
+            command.error(&ctx, content).await?;
+
+            return Ok(());
+        }
+        (Some(_), Some(_), _) | (None, None, _) => {
+            let content = "You must specify either `matches` or `stage`";
+            command.error(&ctx, content).await?;
+
+            return Ok(());
+        }
+    };
+
+    let warmups = warmups.unwrap_or(2);
+    let formula = formula.unwrap_or_default();
+    let view = view.unwrap_or_default();
+
+    let mut osu_matches = Vec::with_capacity(match_ids.len());
+
+    for match_id in match_ids {
+        let mut osu_match = match ctx.osu().osu_match(match_id).await {
+            Ok(osu_match) => osu_match,
+            Err(OsuError::NotFound) => {
+                let content = format!("No match with id `{match_id}` was found");
+                command.error(&ctx, content).await?;
+
+                return Ok(());
+            }
+            Err(OsuError::Response { status, .. }) if status == 401 => {
+                let content =
+                    format!("I can't access the match `{match_id}` because it was set as private");
+                command.error(&ctx, content).await?;
+
+                return Ok(());
+            }
+            Err(err) => {
+                let _ = command.error(&ctx, OSU_API_ISSUE).await;
+                let report = Report::new(err).wrap_err("failed to get match");
+
+                return Err(report);
+            }
+        };
+
+        if let Err(err) = retrieve_previous(&mut osu_match, ctx.osu()).await {
+            let _ = command.error(&ctx, OSU_API_ISSUE).await;
+            let report = Report::new(err).wrap_err("failed to get match history");
+
+            return Err(report);
+        }
+
+        osu_matches.push(osu_match);
+    }
+
+    let stats = StageStats::new(osu_matches, warmups, formula);
+
+    if stats.matches == 0 {
+        let content = "No games were played in any of the matches beyond the warmups";
+        command.error(&ctx, content).await?;
+
+        return Ok(());
+    }
+
+    let total = match view {
+        MatchStageView::Players => stats.players.len(),
+        MatchStageView::Maps => stats.maps.len(),
+    };
+
+    MatchStagePagination::builder(stats, view, title, formula.name(), total)
+        .start_by_update()
+        .start(ctx, (&mut command).into())
+        .await
+}
+
+async fn save(
+    ctx: Arc<Context>,
+    command: InteractionCommand,
+    args: MatchStageSave<'_>,
+) -> Result<()> {
+    let MatchStageSave { name, matches } = args;
+
+    match check_stage_name(&ctx, &command, &name).await {
+        Ok(None) => {}
+        Ok(Some(content)) => {
+            command.error(&ctx, content).await?;
+
+            return Ok(());
+        }
+        Err(err) => {
+            let _ = command.error(&ctx, GENERAL_ISSUE).await;
+
+            return Err(err.wrap_err("failed to check authority status"));
+        }
+    }
+
+    let match_ids = match parse_match_ids(&matches) {
+        Ok(match_ids) => match_ids,
+        Err(content) => {
+            command.error(&ctx, content).await?;
+
+            return Ok(());
+        }
+    };
+
+    let guild = command.guild_id.unwrap();
+
+    if let Err(err) = ctx
+        .psql()
+        .upsert_match_stage(guild, &name, &match_ids)
+        .await
+    {
+        let _ = command.error(&ctx, GENERAL_ISSUE).await;
+
+        return Err(err.wrap_err("failed to save match stage"));
+    }
+
+    let content = format!(
+        "Saved stage `{name}` with {} match{}",
+        match_ids.len(),
+        if match_ids.len() == 1 { "" } else { "es" }
+    );
+
+    let builder = MessageBuilder::new().embed(content);
+    command.update(&ctx, &builder).await?;
+
+    Ok(())
+}
+
+async fn remove(
+    ctx: Arc<Context>,
+    command: InteractionCommand,
+    args: MatchStageRemove<'_>,
+) -> Result<()> {
+    let MatchStageRemove { name } = args;
+
+    match check_stage_name(&ctx, &command, &name).await {
+        Ok(None) => {}
+        Ok(Some(content)) => {
+            command.error(&ctx, content).await?;
+
+            return Ok(());
+        }
+        Err(err) => {
+            let _ = command.error(&ctx, GENERAL_ISSUE).await;
+
+            return Err(err.wrap_err("failed to check authority status"));
+        }
+    }
+
+    let guild = command.guild_id.unwrap();
+
+    let content = match ctx.psql().remove_match_stage(guild, &name).await {
+        Ok(true) => format!("Removed stage `{name}`"),
+        Ok(false) => format!("There is no stage `{name}` on this server"),
+        Err(err) => {
+            let _ = command.error(&ctx, GENERAL_ISSUE).await;
+
+            return Err(err.wrap_err("failed to remove match stage"));
+        }
+    };
+
+    let builder = MessageBuilder::new().embed(content);
+    command.update(&ctx, &builder).await?;
+
+    Ok(())
+}
+
+async fn list(ctx: Arc<Context>, command: InteractionCommand) -> Result<()> {
+    let guild = match command.guild_id {
+        Some(guild) => guild,
+        None => {
+            let content = "Stages can only be saved in servers";
+            command.error(&ctx, content).await?;
+
+            return Ok(());
+        }
+    };
+
+    let stages = match ctx.psql().get_match_stages(guild).await {
+        Ok(stages) => stages,
+        Err(err) => {
+            let _ = command.error(&ctx, GENERAL_ISSUE).await;
+
+            return Err(err.wrap_err("failed to get match stages"));
+        }
+    };
+
+    let content = if stages.is_empty() {
+        "No stages have been saved on this server yet".to_owned()
+    } else {
+        let mut content = String::with_capacity(stages.len() * 64);
+
+        for stage in stages {
+            let _ = write!(content, "`{}`: ", stage.name);
+
+            let mut match_ids = stage.match_ids.into_iter();
+
+            if let Some(match_id) = match_ids.next() {
+                let _ = write!(content, "{match_id}");
+
+                for match_id in match_ids {
+                    let _ = write!(content, ", {match_id}");
+                }
+            }
+
+            content.push('\n');
+        }
+
+        content
+    };
+
+    let builder = MessageBuilder::new().embed(content);
+    command.update(&ctx, &builder).await?;
+
+    Ok(())
+}
+
+/// Aggregated stats of multiple matches
+pub struct StageStats {
+    /// Amount of matches that had games beyond the warmups
+    pub matches: usize,
+    /// Sorted by average rating
+    pub players: Vec<StagePlayer>,
+    /// Sorted by amount of picks
+    pub maps: Vec<StageMap>,
+}
+
+pub struct StagePlayer {
+    pub user_id: u32,
+    pub username: Username,
+    /// Average rating across all matches the player participated in
+    pub avg_rating: f32,
+    pub matches: usize,
+    pub maps_played: usize,
+    pub maps_won: usize,
+}
+
+impl StagePlayer {
+    pub fn win_rate(&self) -> f32 {
+        if self.maps_played == 0 {
+            0.0
+        } else {
+            100.0 * self.maps_won as f32 / self.maps_played as f32
+        }
+    }
+}
+
+pub struct StageMap {
+    pub map_id: u32,
+    pub title: Option<String>,
+    pub picks: usize,
+    pub avg_score: f32,
+    pub avg_acc: f32,
+    /// Username and score of the highest score on the map
+    pub best: Option<(Username, u32)>,
+}
+
+#[derive(Default)]
+struct PlayerAcc {
+    rating_sum: f32,
+    matches: usize,
+    maps_played: usize,
+    maps_won: usize,
+}
+
+#[derive(Default)]
+struct MapAcc {
+    title: Option<String>,
+    picks: usize,
+    score_sum: u64,
+    acc_sum: f32,
+    scores: usize,
+    best: Option<(u32, u32)>,
+}
+
+enum GameWinner {
+    Team(Team),
+    User(u32),
+    None,
+}
+
+impl GameWinner {
+    fn new(game: &MatchGame) -> Self {
+        let scores = game.scores.iter().filter(|score| score.score > 0);
+
+        if game.team_type == TeamType::TeamVS {
+            let mut blue = 0_u64;
+            let mut red = 0_u64;
+
+            for score in scores {
+                match score.team {
+                    Team::Blue => blue += score.score as u64,
+                    Team::Red => red += score.score as u64,
+                    Team::None => {}
+                }
+            }
+
+            match blue.cmp(&red) {
+                Ordering::Greater => Self::Team(Team::Blue),
+                Ordering::Less => Self::Team(Team::Red),
+                Ordering::Equal => Self::None,
+            }
+        } else {
+            scores
+                .max_by_key(|score| score.score)
+                .map_or(Self::None, |score| Self::User(score.user_id))
+        }
+    }
+
+    fn won(&self, user_id: u32, team: Team) -> bool {
+        match self {
+            Self::Team(winner) => *winner == team,
+            Self::User(winner) => *winner == user_id,
+            Self::None => false,
+        }
+    }
+}
+
+impl StageStats {
+    fn new(osu_matches: Vec<OsuMatch>, warmups: usize, formula: MatchCostFormula) -> Self {
+        let mut usernames: HashMap<u32, Username, IntHasher> = HashMap::default();
+        let mut players: HashMap<u32, PlayerAcc, IntHasher> = HashMap::default();
+        let mut maps: HashMap<u32, MapAcc, IntHasher> = HashMap::default();
+        let mut matches = 0;
+
+        for mut osu_match in osu_matches {
+            let games: Vec<_> = osu_match.drain_games().skip(warmups).collect();
+
+            usernames.extend(
+                osu_match
+                    .users
+                    .iter()
+                    .map(|(user_id, user)| (*user_id, user.username.clone())),
+            );
+
+            if games.is_empty() {
+                continue;
+            }
+
+            matches += 1;
+            let finished = osu_match.end_time.is_some();
+            let result = process_match(&games, finished, &osu_match.users, formula);
+
+            for &(user_id, rating) in result.players() {
+                let player = players.entry(user_id).or_default();
+                player.rating_sum += rating;
+                player.matches += 1;
+            }
+
+            for game in games.iter() {
+                let winner = GameWinner::new(game);
+
+                let mut map_acc = match game.map {
+                    Some(ref map) => {
+                        let entry = match maps.entry(map.map_id) {
+                            Entry::Occupied(e) => e.into_mut(),
+                            Entry::Vacant(e) => e.insert(MapAcc {
+                                title: map.mapset.as_ref().map(|mapset| {
+                                    format!(
+                                        "{} - {} [{}]",
+                                        mapset.artist, mapset.title, map.version
+                                    )
+                                }),
+                                ..Default::default()
+                            }),
+                        };
+
+                        entry.picks += 1;
+
+                        Some(entry)
+                    }
+                    None => None,
+                };
+
+                for score in game.scores.iter().filter(|score| score.score > 0) {
+                    if let Some(player) = players.get_mut(&score.user_id) {
+                        player.maps_played += 1;
+                        player.maps_won += winner.won(score.user_id, score.team) as usize;
+                    }
+
+                    if let Some(ref mut map) = map_acc {
+                        map.score_sum += score.score as u64;
+                        map.acc_sum += score.accuracy;
+                        map.scores += 1;
+
+                        if map.best.map_or(true, |(_, best)| score.score > best) {
+                            map.best = Some((score.user_id, score.score));
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut players: Vec<_> = players
+            .into_iter()
+            .map(|(user_id, acc)| StagePlayer {
+                user_id,
+                username: usernames
+                    .get(&user_id)
+                    .cloned()
+                    .unwrap_or_else(|| user_id.to_string().into()),
+                avg_rating: acc.rating_sum / acc.matches as f32,
+                matches: acc.matches,
+                maps_played: acc.maps_played,
+                maps_won: acc.maps_won,
+            })
+            .collect();
+
+        players.sort_unstable_by(|a, b| {
+            b.avg_rating
+                .partial_cmp(&a.avg_rating)
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let mut maps: Vec<_> = maps
+            .into_iter()
+            .map(|(map_id, acc)| {
+                let scores = acc.scores.max(1) as f32;
+
+                StageMap {
+                    map_id,
+                    title: acc.title,
+                    picks: acc.picks,
+                    avg_score: acc.score_sum as f32 / scores,
+                    avg_acc: acc.acc_sum / scores,
+                    best: acc.best.map(|(user_id, score)| {
+                        let username = usernames
+                            .get(&user_id)
+                            .cloned()
+                            .unwrap_or_else(|| user_id.to_string().into());
+
+                        (username, score)
+                    }),
+                }
+            })
+            .collect();
+
+        maps.sort_unstable_by(|a, b| {
+            b.picks.cmp(&a.picks).then_with(|| {
+                b.avg_score
+                    .partial_cmp(&a.avg_score)
+                    .unwrap_or(Ordering::Equal)
+            })
+        });
+
+        Self {
+            matches,
+            players,
+            maps,
+        }
+    }
+}
@@ -154,6 +154,7 @@ struct MedalInfo_<'a> {
 #[command(name = "list")]
 /// List all achieved medals of a user
 pub struct MedalList<'a> {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     /// Specify a medal order
@@ -193,6 +194,7 @@ impl Default for MedalListOrder {
 #[command(name = "missing")]
 /// Display a list of medals that a user is missing
 pub struct MedalMissing<'a> {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
@@ -212,6 +214,7 @@ pub struct MedalMissing<'a> {
 )]
 /// Display recent medals of a user
 pub struct MedalRecent<'a> {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(min_value = 1)]
@@ -230,6 +233,7 @@ pub struct MedalRecent<'a> {
 #[command(name = "stats")]
 /// Display medal stats for a user
 pub struct MedalStats<'a> {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
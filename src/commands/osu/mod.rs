@@ -126,9 +126,10 @@ use crate::{
 pub use self::{
     attributes::*, avatar::*, badges::*, bws::*, cards::*, claim_name::*, compare::*,
     country_top::*, fix::*, graphs::*, leaderboard::*, map::*, map_search::*, mapper::*,
-    match_compare::*, match_costs::*, medals::*, most_played::*, nochoke::*, osekai::*,
-    osustats::*, pinned::*, popular::*, pp::*, profile::*, rank::*, ranking::*, ratios::*,
-    recent::*, serverleaderboard::*, simulate::*, snipe::*, top::*, whatif::*,
+    match_compare::*, match_costs::*, match_stage::*, medals::*, most_played::*, nochoke::*,
+    osekai::*, osustats::*, pinned::*, popular::*, pp::*, profile::*, progress::*, rank::*,
+    ranking::*, ratios::*, recent::*, recent_maps::*, replay::*, serverleaderboard::*, simulate::*,
+    snipe::*, top::*, username::*, whatif::*,
 };
 
 #[cfg(feature = "matchlive")]
@@ -155,6 +156,7 @@ mod mapper;
 mod match_compare;
 mod match_costs;
 mod match_live;
+mod match_stage;
 mod medals;
 mod most_played;
 mod nochoke;
@@ -164,14 +166,18 @@ mod pinned;
 mod popular;
 mod pp;
 mod profile;
+mod progress;
 mod rank;
 mod ranking;
 mod ratios;
 mod recent;
+mod recent_maps;
+mod replay;
 mod serverleaderboard;
 mod simulate;
 mod snipe;
 mod top;
+mod username;
 mod whatif;
 
 pub trait HasMods {
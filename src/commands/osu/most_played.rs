@@ -23,6 +23,7 @@ use super::{require_link, UserArgs};
 #[command(name = "mostplayed")]
 /// Display the most played maps of a user
 pub struct MostPlayed<'a> {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
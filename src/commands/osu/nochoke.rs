@@ -33,6 +33,7 @@ pub struct Nochoke<'a> {
         Since combo does not matter in mania, its scores can't be unchoked.")]
     /// Specify a gamemode
     mode: Option<NochokeGameMode>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(min_value = 0)]
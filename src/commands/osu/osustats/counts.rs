@@ -30,6 +30,7 @@ use super::OsuStatsCount;
 pub struct Osc<'a> {
     /// Specify a gamemode
     mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
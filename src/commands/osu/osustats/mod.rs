@@ -40,6 +40,7 @@ pub enum OsuStats<'a> {
 pub struct OsuStatsCount<'a> {
     /// Specify a gamemode
     mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
@@ -73,6 +74,7 @@ pub struct OsuStatsPlayers<'a> {
 pub struct OsuStatsScores<'a> {
     /// Specify a gamemode
     mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     /// Choose how the scores should be ordered
@@ -38,6 +38,7 @@ use super::{prepare_scores, require_link, HasMods, ModsResult, ScoreOrder};
 pub struct Pinned {
     /// Specify a gamemode
     mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<String>,
     /// Choose how the scores should be ordered
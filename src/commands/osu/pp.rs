@@ -28,6 +28,7 @@ pub struct Pp<'a> {
     pp: f32,
     /// Specify a gamemode
     mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(min_value = 0.0)]
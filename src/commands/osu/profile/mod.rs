@@ -40,6 +40,7 @@ mod size;
 pub struct Profile<'a> {
     /// Specify a gamemode
     mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(help = "Specify the initial size of the embed.\n\
@@ -0,0 +1,344 @@
+use std::sync::Arc;
+
+use command_macros::{HasName, SlashCommand};
+use eyre::{Report, Result, WrapErr};
+use image::{codecs::png::PngEncoder, ColorType, ImageEncoder};
+use plotters::{
+    prelude::{BitMapBackend, ChartBuilder, Circle, IntoDrawingArea, LineSeries, PathElement},
+    style::{Color, RGBColor, WHITE},
+};
+use plotters_backend::FontStyle;
+use rosu_v2::prelude::OsuError;
+use time::{Duration, OffsetDateTime};
+use twilight_interactions::command::{CommandModel, CommandOption, CreateCommand, CreateOption};
+use twilight_model::id::{marker::UserMarker, Id};
+
+use crate::{
+    commands::GameModeOption,
+    core::{commands::CommandOrigin, Context},
+    database::UserStatsSnapshot,
+    embeds::{EmbedData, ProgressEmbed},
+    util::{
+        builder::MessageBuilder,
+        constants::{GENERAL_ISSUE, OSU_API_ISSUE},
+        interaction::InteractionCommand,
+        numbers::with_comma_int,
+    },
+};
+
+use super::{get_user, UserArgs};
+
+#[derive(CommandModel, CreateCommand, HasName, SlashCommand)]
+#[command(
+    name = "progress",
+    help = "Display how much pp, rank, accuracy, playcount, and ranked score \
+    a user gained over some time period.\n\
+    Data is only available for the times in which the user was requested through any command \
+    so the first data point might be later than the start of the period."
+)]
+/// Display how much a user progressed over some time
+pub struct Progress {
+    /// Specify a gamemode
+    mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
+    /// Specify a username
+    name: Option<String>,
+    /// Specify the time period (defaults to a week)
+    period: Option<ProgressPeriod>,
+    #[command(
+        help = "Instead of specifying an osu! username with the `name` option, \
+        you can use this option to choose a discord user.\n\
+        Only works on users who have used the `/link` command."
+    )]
+    /// Specify a linked discord user
+    discord: Option<Id<UserMarker>>,
+}
+
+#[derive(Copy, Clone, CommandOption, CreateOption)]
+pub enum ProgressPeriod {
+    #[option(name = "Day", value = "day")]
+    Day,
+    #[option(name = "Week", value = "week")]
+    Week,
+    #[option(name = "Month", value = "month")]
+    Month,
+    #[option(name = "Three months", value = "three_months")]
+    ThreeMonths,
+    #[option(name = "Year", value = "year")]
+    Year,
+}
+
+impl Default for ProgressPeriod {
+    fn default() -> Self {
+        Self::Week
+    }
+}
+
+impl ProgressPeriod {
+    fn duration(self) -> Duration {
+        match self {
+            Self::Day => Duration::DAY,
+            Self::Week => Duration::WEEK,
+            Self::Month => Duration::days(30),
+            Self::ThreeMonths => Duration::days(90),
+            Self::Year => Duration::days(365),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+            Self::ThreeMonths => "three months",
+            Self::Year => "year",
+        }
+    }
+}
+
+async fn slash_progress(ctx: Arc<Context>, mut command: InteractionCommand) -> Result<()> {
+    let args = Progress::from_interaction(command.input_data())?;
+
+    progress(ctx, (&mut command).into(), args).await
+}
+
+async fn progress(ctx: Arc<Context>, orig: CommandOrigin<'_>, args: Progress) -> Result<()> {
+    let (name, mode) = name_mode!(ctx, orig, args);
+    let period = args.period.unwrap_or_default();
+
+    let user_args = UserArgs::new(name.as_str(), mode);
+
+    let user = match get_user(&ctx, &user_args).await {
+        Ok(user) => user,
+        Err(OsuError::NotFound) => {
+            let content = format!("Could not find user `{name}`");
+
+            return orig.error(&ctx, content).await;
+        }
+        Err(err) => {
+            let _ = orig.error(&ctx, OSU_API_ISSUE).await;
+            let report = Report::new(err).wrap_err("failed to get user");
+
+            return Err(report);
+        }
+    };
+
+    let now = OffsetDateTime::now_utc();
+    let since = now - period.duration();
+
+    let snapshots_fut = ctx.psql().get_osu_user_snapshots(user.user_id, mode, since);
+
+    let mut snapshots = match snapshots_fut.await {
+        Ok(snapshots) => snapshots,
+        Err(err) => {
+            let _ = orig.error(&ctx, GENERAL_ISSUE).await;
+
+            return Err(err.wrap_err("failed to get user snapshots"));
+        }
+    };
+
+    // The user might have been cached so the latest snapshot could be outdated
+    if let Some(stats) = user.statistics.as_ref() {
+        let current = UserStatsSnapshot {
+            taken_at: now,
+            pp: stats.pp,
+            global_rank: stats.global_rank.unwrap_or(0),
+            accuracy: stats.accuracy,
+            playcount: stats.playcount,
+            ranked_score: stats.ranked_score,
+        };
+
+        let outdated = snapshots.last().map_or(true, |last| {
+            last.pp != current.pp || last.playcount != current.playcount
+        });
+
+        if outdated {
+            snapshots.push(current);
+        }
+    }
+
+    if snapshots.len() < 2 {
+        let content = format!(
+            "There is no data from before for `{name}` yet.\n\
+            From now on I'll remember their stats whenever they're requested \
+            so check back later!",
+            name = user.username,
+        );
+
+        let builder = MessageBuilder::new().embed(content);
+        orig.create_message(&ctx, &builder).await?;
+
+        return Ok(());
+    }
+
+    let graph = match graph(&snapshots, now) {
+        Ok(graph) => Some(graph),
+        Err(err) => {
+            warn!("{:?}", err.wrap_err("Failed to create progress graph"));
+
+            None
+        }
+    };
+
+    let first = snapshots[0];
+    let last = snapshots[snapshots.len() - 1];
+
+    let embed = ProgressEmbed::new(&user, &first, &last, since, period, graph.is_some()).build();
+    let mut builder = MessageBuilder::new().embed(embed);
+
+    if let Some(bytes) = graph {
+        builder = builder.attachment("progress.png", bytes);
+    }
+
+    orig.create_message(&ctx, &builder).await?;
+
+    Ok(())
+}
+
+const W: u32 = 1350;
+const H: u32 = 711;
+
+const PP_COLOR: RGBColor = RGBColor(0, 208, 138);
+const RANK_COLOR: RGBColor = RGBColor(2, 186, 213);
+
+fn graph(snapshots: &[UserStatsSnapshot], now: OffsetDateTime) -> Result<Vec<u8>> {
+    // x-values are days relative to now so they're negative
+    let days_ago =
+        |snapshot: &UserStatsSnapshot| -(now - snapshot.taken_at).as_seconds_f64() / 86_400.0;
+
+    // At least an hour so the range is never empty
+    let x_min = days_ago(&snapshots[0]).min(-1.0 / 24.0);
+
+    let (pp_min, pp_max) = snapshots
+        .iter()
+        .fold((f32::MAX, f32::MIN), |(min, max), snapshot| {
+            (min.min(snapshot.pp), max.max(snapshot.pp))
+        });
+
+    let pp_margin = ((pp_max - pp_min) * 0.1).max(1.0);
+
+    // Ranks are negated so that better ranks are higher up
+    let (rank_min, rank_max) = snapshots
+        .iter()
+        .filter(|snapshot| snapshot.global_rank > 0)
+        .fold((i64::MAX, i64::MIN), |(min, max), snapshot| {
+            let rank = -(snapshot.global_rank as i64);
+
+            (min.min(rank), max.max(rank))
+        });
+
+    let has_rank = rank_min <= rank_max;
+
+    let (rank_min, rank_max) = if has_rank {
+        let rank_margin = ((rank_max - rank_min) / 10).max(1);
+
+        (rank_min - rank_margin, (rank_max + rank_margin).min(-1))
+    } else {
+        (-2, -1)
+    };
+
+    let len = (W * H) as usize;
+    let mut buf = vec![0; len * 3];
+
+    {
+        let root = BitMapBackend::with_buffer(&mut buf, (W, H)).into_drawing_area();
+        let background = RGBColor(19, 43, 33);
+        root.fill(&background)
+            .wrap_err("failed to fill background")?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .x_label_area_size(40_i32)
+            .y_label_area_size(75_i32)
+            .right_y_label_area_size(75_i32)
+            .margin(10_i32)
+            .build_cartesian_2d(x_min..0.0, (pp_min - pp_margin)..(pp_max + pp_margin))
+            .wrap_err("failed to build chart")?
+            .set_secondary_coord(x_min..0.0, rank_min..rank_max);
+
+        chart
+            .configure_mesh()
+            .disable_x_mesh()
+            .x_desc("Days ago")
+            .x_label_formatter(&|x| format!("{:.1}", -*x))
+            .y_desc("pp")
+            .y_label_formatter(&|pp| format!("{pp:.0}"))
+            .label_style(("sans-serif", 16_i32, &WHITE))
+            .bold_line_style(&WHITE.mix(0.3))
+            .axis_style(RGBColor(7, 18, 14))
+            .axis_desc_style(("sans-serif", 16_i32, FontStyle::Bold, &WHITE))
+            .draw()
+            .wrap_err("failed to draw mesh")?;
+
+        if has_rank {
+            chart
+                .configure_secondary_axes()
+                .y_desc("Rank")
+                .y_label_formatter(&|rank| format!("#{}", with_comma_int(-*rank)))
+                .label_style(("sans-serif", 16_i32, &WHITE))
+                .axis_style(RGBColor(7, 18, 14))
+                .axis_desc_style(("sans-serif", 16_i32, FontStyle::Bold, &WHITE))
+                .draw()
+                .wrap_err("failed to draw secondary mesh")?;
+        }
+
+        let pp_iter = snapshots
+            .iter()
+            .map(|snapshot| (days_ago(snapshot), snapshot.pp));
+
+        chart
+            .draw_series(LineSeries::new(pp_iter.clone(), PP_COLOR.stroke_width(2)))
+            .wrap_err("failed to draw pp line")?
+            .label("pp")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], PP_COLOR.stroke_width(2)));
+
+        let pp_circles = pp_iter.map(|coords| Circle::new(coords, 3_i32, PP_COLOR.filled()));
+
+        chart
+            .draw_series(pp_circles)
+            .wrap_err("failed to draw pp circles")?;
+
+        if has_rank {
+            let rank_iter = snapshots
+                .iter()
+                .filter(|snapshot| snapshot.global_rank > 0)
+                .map(|snapshot| (days_ago(snapshot), -(snapshot.global_rank as i64)));
+
+            chart
+                .draw_secondary_series(LineSeries::new(
+                    rank_iter.clone(),
+                    RANK_COLOR.stroke_width(2),
+                ))
+                .wrap_err("failed to draw rank line")?
+                .label("Rank")
+                .legend(|(x, y)| {
+                    PathElement::new(vec![(x, y), (x + 20, y)], RANK_COLOR.stroke_width(2))
+                });
+
+            let rank_circles =
+                rank_iter.map(|coords| Circle::new(coords, 3_i32, RANK_COLOR.filled()));
+
+            chart
+                .draw_secondary_series(rank_circles)
+                .wrap_err("failed to draw rank circles")?;
+        }
+
+        chart
+            .configure_series_labels()
+            .border_style(WHITE.mix(0.6).stroke_width(1))
+            .background_style(RGBColor(7, 23, 17))
+            .legend_area_size(25_i32)
+            .label_font(("sans-serif", 16_i32, FontStyle::Bold, &WHITE))
+            .draw()
+            .wrap_err("failed to draw legend")?;
+    }
+
+    // Encode buf to png
+    let mut png_bytes: Vec<u8> = Vec::with_capacity(len);
+    let png_encoder = PngEncoder::new(&mut png_bytes);
+
+    png_encoder
+        .write_image(&buf, W, H, ColorType::Rgb8)
+        .wrap_err("failed to encode image")?;
+
+    Ok(png_bytes)
+}
@@ -35,6 +35,7 @@ pub struct RankPp<'a> {
     rank: u32,
     /// Specify a gamemode
     mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(min_value = 0.0)]
@@ -60,6 +61,7 @@ pub struct RankScore<'a> {
     rank: usize,
     /// Specify a gamemode
     mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
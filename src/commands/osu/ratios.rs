@@ -32,6 +32,7 @@ use super::{require_link, ScoreArgs, UserArgs};
 )]
 /// Ratio related stats about a user's mania top100
 pub struct Ratios<'a> {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
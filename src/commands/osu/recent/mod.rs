@@ -58,6 +58,7 @@ pub struct RecentScore<'a> {
     with ratio being `n320/n300`.")]
     /// Specify a gamemode
     mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
@@ -89,6 +90,7 @@ pub struct RecentScore<'a> {
 pub struct RecentBest {
     /// Specify a gamemode
     mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<String>,
     #[command(help = "Filter out all scores that don't match the specified mods.\n\
@@ -185,6 +187,7 @@ impl<'a> TryFrom<RecentBest> for TopArgs<'a> {
 pub struct RecentLeaderboard<'a> {
     /// Specify a gamemode
     mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
@@ -217,6 +220,7 @@ pub struct RecentLeaderboard<'a> {
 pub struct RecentList<'a> {
     /// Specify a gamemode
     mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
@@ -258,6 +262,7 @@ pub struct RecentFix {
         Since combo does not matter in mania, its scores can't be fixed.")]
     /// Specify a gamemode
     mode: Option<RecentFixGameMode>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<String>,
     #[command(
@@ -322,6 +327,7 @@ pub enum RecentSimulate<'a> {
 )]
 /// Simulate an osu!standard score
 pub struct RecentSimulateOsu<'a> {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
@@ -373,6 +379,7 @@ pub struct RecentSimulateOsu<'a> {
 )]
 /// Simulate an osu!taiko score
 pub struct RecentSimulateTaiko<'a> {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
@@ -421,6 +428,7 @@ pub struct RecentSimulateTaiko<'a> {
 )]
 /// Simulate an osu!ctb score
 pub struct RecentSimulateCatch<'a> {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
@@ -472,6 +480,7 @@ pub struct RecentSimulateCatch<'a> {
 )]
 /// Simulate an osu!mania score
 pub struct RecentSimulateMania<'a> {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
@@ -514,6 +523,7 @@ pub struct RecentSimulateMania<'a> {
 pub struct Rb {
     /// Specify a gamemode
     mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<String>,
     #[command(help = "Filter out all scores that don't match the specified mods.\n\
@@ -674,6 +674,7 @@ pub struct Rs<'a> {
     with ratio being `n320/n300`.")]
     /// Specify a gamemode
     mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
@@ -0,0 +1,87 @@
+use eyre::{Result, WrapErr};
+use twilight_model::application::command::CommandOptionChoice;
+
+use crate::{
+    util::{interaction::InteractionCommand, osu::MapIdType, CowUtils, InteractionCommandExt},
+    Context,
+};
+
+/// Discord's limit for the amount of choices
+const MAX_CHOICES: usize = 25;
+
+/// Discord's limit for the name of a choice
+const MAX_CHOICE_NAME_LEN: usize = 100;
+
+/// Suggest maps that were recently shown in the channel and whose
+/// id or `artist - title [version]` contains the given input.
+pub async fn handle_map_autocomplete(
+    ctx: &Context,
+    command: &InteractionCommand,
+    input: &str,
+) -> Result<()> {
+    let mut map_ids = Vec::new();
+
+    for map in ctx.recent_maps(command.channel_id) {
+        if let MapIdType::Map(id) = map {
+            let id = id as i32;
+
+            if !map_ids.contains(&id) {
+                map_ids.push(id);
+            }
+
+            if map_ids.len() == MAX_CHOICES {
+                break;
+            }
+        }
+    }
+
+    let maps = ctx
+        .psql()
+        .get_beatmaps(&map_ids, true)
+        .await
+        .wrap_err("failed to get recent maps")?;
+
+    let input = input.trim().cow_to_ascii_lowercase();
+
+    let choices = map_ids
+        .into_iter()
+        .filter_map(|map_id| {
+            let map_id = map_id as u32;
+
+            let mut name = match maps.get(&map_id) {
+                Some(map) => match map.mapset {
+                    Some(ref mapset) => {
+                        format!("{} - {} [{}]", mapset.artist, mapset.title, map.version)
+                    }
+                    None => format!("[{}] (map id {map_id})", map.version),
+                },
+                None => format!("Map id {map_id}"),
+            };
+
+            let value = map_id.to_string();
+
+            let matches = input.is_empty()
+                || value.starts_with(&*input)
+                || name.to_ascii_lowercase().contains(&*input);
+
+            if !matches {
+                return None;
+            }
+
+            if name.chars().count() > MAX_CHOICE_NAME_LEN {
+                name = name.chars().take(MAX_CHOICE_NAME_LEN - 3).collect();
+                name.push_str("...");
+            }
+
+            Some(CommandOptionChoice::String {
+                name,
+                name_localizations: None,
+                value,
+            })
+        })
+        .collect();
+
+    command.autocomplete(ctx, choices).await?;
+
+    Ok(())
+}
@@ -0,0 +1,294 @@
+use std::sync::Arc;
+
+use command_macros::SlashCommand;
+use eyre::{Report, Result, WrapErr};
+use image::{codecs::png::PngEncoder, ColorType, ImageEncoder};
+use plotters::{
+    prelude::{AreaSeries, BitMapBackend, ChartBuilder, IntoDrawingArea},
+    style::{Color, RGBColor, ShapeStyle, WHITE},
+};
+use plotters_backend::FontStyle;
+use rosu_v2::prelude::{Grade, OsuError, Score, ScoreStatistics};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::channel::{embed::EmbedImage, Attachment};
+
+use crate::{
+    database::MinimizedPp,
+    embeds::{attachment, RecentEmbed},
+    util::{
+        builder::MessageBuilder,
+        constants::{GENERAL_ISSUE, OSU_API_ISSUE},
+        interaction::InteractionCommand,
+        InteractionCommandExt,
+    },
+    Context,
+};
+
+use self::osr::{LifeBarPoint, ReplayHeader};
+
+use super::{get_user, UserArgs};
+
+mod osr;
+
+/// Even long replays are well below this so larger files are not downloaded at all
+const MAX_REPLAY_SIZE: u64 = 5 * 1024 * 1024;
+
+#[derive(CommandModel, CreateCommand, SlashCommand)]
+#[command(
+    name = "replay",
+    help = "Display the score of an uploaded `.osr` replay file.\n\
+    The map is looked up by the checksum stored in the replay \
+    so it must be submitted on the osu! website."
+)]
+/// Display the score of an uploaded replay
+pub struct Replay {
+    /// Specify a .osr replay file
+    replay: Attachment,
+}
+
+async fn slash_replay(ctx: Arc<Context>, mut command: InteractionCommand) -> Result<()> {
+    let Replay { replay } = Replay::from_interaction(command.input_data())?;
+
+    if !replay.filename.ends_with(".osr") {
+        let content = "The attachment must be a replay file, e.g. `replay.osr`";
+        command.error(&ctx, content).await?;
+
+        return Ok(());
+    }
+
+    if replay.size > MAX_REPLAY_SIZE {
+        let content = "The replay file is too large, it must be at most 5MB";
+        command.error(&ctx, content).await?;
+
+        return Ok(());
+    }
+
+    let bytes = match ctx.client().get_discord_attachment(&replay).await {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            let _ = command.error(&ctx, GENERAL_ISSUE).await;
+
+            return Err(err.wrap_err("failed to get discord attachment"));
+        }
+    };
+
+    let header = match ReplayHeader::parse(&bytes) {
+        Ok(header) => header,
+        Err(err) => {
+            let content = format!("Failed to parse replay: {err}");
+            command.error(&ctx, content).await?;
+
+            return Ok(());
+        }
+    };
+
+    let map_fut = ctx.psql().get_beatmap_by_checksum(&header.map_md5);
+
+    let mut map = match map_fut.await {
+        Ok(Some(map)) => map,
+        res => {
+            if let Err(err) = res {
+                warn!("{:?}", err.wrap_err("Failed to get map by checksum"));
+            }
+
+            match ctx.osu().beatmap().checksum(&header.map_md5).await {
+                Ok(map) => {
+                    // Add map to database if its not in already
+                    if let Err(err) = ctx.psql().insert_beatmap(&map).await {
+                        warn!("{:?}", err.wrap_err("Failed to insert map in database"));
+                    }
+
+                    ctx.map_garbage_collector(&map).execute(&ctx);
+
+                    map
+                }
+                Err(OsuError::NotFound) => {
+                    let content = "Could not find the replay's beatmap. \
+                        Be sure it's submitted and up to date.";
+                    command.error(&ctx, content).await?;
+
+                    return Ok(());
+                }
+                Err(err) => {
+                    let _ = command.error(&ctx, OSU_API_ISSUE).await;
+                    let report = Report::new(err).wrap_err("failed to get beatmap");
+
+                    return Err(report);
+                }
+            }
+        }
+    };
+
+    let user_args = UserArgs::new(&header.player, header.mode);
+
+    let user = match get_user(&ctx, &user_args).await {
+        Ok(user) => user,
+        Err(OsuError::NotFound) => {
+            let content = format!("Could not find user `{}`", header.player);
+            command.error(&ctx, content).await?;
+
+            return Ok(());
+        }
+        Err(err) => {
+            let _ = command.error(&ctx, OSU_API_ISSUE).await;
+            let report = Report::new(err).wrap_err("failed to get user");
+
+            return Err(report);
+        }
+    };
+
+    // Converted maps are shown in the replay's mode
+    map.mode = header.mode;
+    let mapset = map.mapset.take().map(From::from);
+
+    let mut score = Score {
+        accuracy: 0.0,
+        ended_at: header.timestamp,
+        grade: Grade::D,
+        max_combo: header.max_combo as u32,
+        map: Some(map),
+        mapset,
+        mode: header.mode,
+        mods: header.mods,
+        perfect: header.perfect,
+        pp: None,
+        rank_country: None,
+        rank_global: None,
+        replay: Some(true),
+        score: header.score,
+        score_id: header.score_id,
+        statistics: ScoreStatistics {
+            count_geki: header.count_geki as u32,
+            count_300: header.count_300 as u32,
+            count_katu: header.count_katu as u32,
+            count_100: header.count_100 as u32,
+            count_50: header.count_50 as u32,
+            count_miss: header.count_miss as u32,
+        },
+        user: None,
+        user_id: user.user_id,
+        weight: None,
+    };
+
+    score.accuracy = score.accuracy();
+    score.grade = score.grade(None);
+
+    let minimized_pp = match command.guild_id {
+        Some(guild) => ctx.guild_minimized_pp(guild).await,
+        None => MinimizedPp::default(),
+    };
+
+    #[cfg(feature = "twitch")]
+    let embed_fut = RecentEmbed::new(&user, &score, None, None, None, minimized_pp, &ctx);
+
+    #[cfg(not(feature = "twitch"))]
+    let embed_fut = RecentEmbed::new(&user, &score, None, None, minimized_pp, &ctx);
+
+    let embed_data = match embed_fut.await {
+        Ok(data) => data,
+        Err(err) => {
+            let _ = command.error(&ctx, GENERAL_ISSUE).await;
+
+            return Err(err.wrap_err("failed to create embed"));
+        }
+    };
+
+    let graph = if header.life_bar.is_empty() {
+        None
+    } else {
+        match life_bar_graph(&header.life_bar) {
+            Ok(graph) => Some(graph),
+            Err(err) => {
+                warn!("{:?}", err.wrap_err("Failed to create life bar graph"));
+
+                None
+            }
+        }
+    };
+
+    let mut embed = embed_data.as_maximized();
+    let mut builder = MessageBuilder::new();
+
+    if let Some(bytes) = graph {
+        embed.image = Some(EmbedImage {
+            height: None,
+            proxy_url: None,
+            url: attachment("life_bar.png"),
+            width: None,
+        });
+
+        builder = builder.attachment("life_bar.png", bytes);
+    }
+
+    let builder = builder.embed(embed);
+    command.update(&ctx, &builder).await?;
+
+    Ok(())
+}
+
+const W: u32 = 1350;
+const H: u32 = 400;
+
+fn life_bar_graph(life_bar: &[LifeBarPoint]) -> Result<Vec<u8>> {
+    let len = (W * H) as usize;
+    let mut buf = vec![0; len * 3];
+
+    let last_time = life_bar.last().map_or(0, |point| point.time);
+    let max_secs = (last_time as f32 / 1000.0).max(1.0);
+
+    {
+        let root = BitMapBackend::with_buffer(&mut buf, (W, H)).into_drawing_area();
+        let background = RGBColor(19, 43, 33);
+        root.fill(&background)
+            .wrap_err("failed to fill background")?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .x_label_area_size(40)
+            .y_label_area_size(50)
+            .margin(10)
+            .margin_left(6)
+            .build_cartesian_2d(0.0_f32..max_secs, 0.0_f32..100.0)
+            .wrap_err("failed to build chart")?;
+
+        chart
+            .configure_mesh()
+            .disable_x_mesh()
+            .x_labels(20)
+            .x_desc("Seconds")
+            .x_label_formatter(&|x| format!("{}", *x as u32))
+            .y_labels(6)
+            .y_desc("Health %")
+            .y_label_formatter(&|y| format!("{}", *y as u32))
+            .label_style(("sans-serif", 15, &WHITE))
+            .bold_line_style(&WHITE.mix(0.3))
+            .axis_style(RGBColor(7, 18, 14))
+            .axis_desc_style(("sans-serif", 16, FontStyle::Bold, &WHITE))
+            .draw()
+            .wrap_err("failed to draw mesh")?;
+
+        let data = life_bar
+            .iter()
+            .map(|point| (point.time.max(0) as f32 / 1000.0, point.life * 100.0));
+
+        let area_style = RGBColor(2, 186, 213).mix(0.7).filled();
+
+        let border_style = ShapeStyle {
+            color: RGBColor(0, 208, 138).to_rgba(),
+            filled: false,
+            stroke_width: 2,
+        };
+
+        let series = AreaSeries::new(data, 0.0, area_style).border_style(border_style);
+        chart.draw_series(series).wrap_err("failed to draw area")?;
+    }
+
+    // Encode buf to png
+    let mut png_bytes: Vec<u8> = Vec::with_capacity(len);
+    let png_encoder = PngEncoder::new(&mut png_bytes);
+
+    png_encoder
+        .write_image(&buf, W, H, ColorType::Rgb8)
+        .wrap_err("failed to encode image")?;
+
+    Ok(png_bytes)
+}
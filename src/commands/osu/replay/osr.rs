@@ -0,0 +1,293 @@
+use eyre::{ContextCompat, Result, WrapErr};
+use rosu_v2::prelude::{GameMode, GameMods};
+use time::OffsetDateTime;
+
+/// Seconds between 0001-01-01 and the unix epoch
+const TICKS_EPOCH_OFFSET: i64 = 62_135_596_800;
+
+/// Amount of ticks per second, a tick being 100 nanoseconds
+const TICKS_PER_SECOND: i64 = 10_000_000;
+
+/// Everything of a `.osr` replay file except for the compressed replay data
+pub struct ReplayHeader {
+    pub mode: GameMode,
+    pub version: i32,
+    pub map_md5: String,
+    pub player: String,
+    pub count_300: u16,
+    pub count_100: u16,
+    pub count_50: u16,
+    pub count_geki: u16,
+    pub count_katu: u16,
+    pub count_miss: u16,
+    pub score: u32,
+    pub max_combo: u16,
+    pub perfect: bool,
+    pub mods: GameMods,
+    pub life_bar: Vec<LifeBarPoint>,
+    pub timestamp: OffsetDateTime,
+    pub score_id: Option<u64>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LifeBarPoint {
+    /// Milliseconds into the map
+    pub time: i32,
+    /// Between 0.0 and 1.0
+    pub life: f32,
+}
+
+impl ReplayHeader {
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        let mut reader = Reader { bytes };
+
+        let mode = match reader.u8().wrap_err("missing mode")? {
+            0 => GameMode::Osu,
+            1 => GameMode::Taiko,
+            2 => GameMode::Catch,
+            3 => GameMode::Mania,
+            other => bail!("invalid mode {other}"),
+        };
+
+        let version = reader.i32().wrap_err("missing version")?;
+
+        let map_md5 = reader
+            .string()
+            .wrap_err("invalid map hash")?
+            .wrap_err("missing map hash")?;
+
+        let player = reader
+            .string()
+            .wrap_err("invalid player name")?
+            .wrap_err("missing player name")?;
+
+        // Hash of the replay itself
+        reader.string().wrap_err("invalid replay hash")?;
+
+        let count_300 = reader.u16().wrap_err("missing 300 count")?;
+        let count_100 = reader.u16().wrap_err("missing 100 count")?;
+        let count_50 = reader.u16().wrap_err("missing 50 count")?;
+        let count_geki = reader.u16().wrap_err("missing geki count")?;
+        let count_katu = reader.u16().wrap_err("missing katu count")?;
+        let count_miss = reader.u16().wrap_err("missing miss count")?;
+        let score = reader.i32().wrap_err("missing score")? as u32;
+        let max_combo = reader.u16().wrap_err("missing max combo")?;
+        let perfect = reader.u8().wrap_err("missing perfect flag")? == 1;
+        let mods = GameMods::from_bits_truncate(reader.i32().wrap_err("missing mods")? as u32);
+
+        let life_bar = reader
+            .string()
+            .wrap_err("invalid life bar")?
+            .map_or_else(Vec::new, |life_bar| parse_life_bar(&life_bar));
+
+        let ticks = reader.i64().wrap_err("missing timestamp")?;
+        let timestamp =
+            OffsetDateTime::from_unix_timestamp(ticks / TICKS_PER_SECOND - TICKS_EPOCH_OFFSET)
+                .wrap_err("invalid timestamp")?;
+
+        // The online score id comes after the compressed replay data.
+        // Older or modified replays might not contain it so it's optional.
+        let score_id = reader
+            .i32()
+            .and_then(|len| reader.skip(len.max(0) as usize))
+            .and_then(|_| reader.i64())
+            .filter(|&id| id > 0)
+            .map(|id| id as u64);
+
+        Ok(Self {
+            mode,
+            version,
+            map_md5,
+            player,
+            count_300,
+            count_100,
+            count_50,
+            count_geki,
+            count_katu,
+            count_miss,
+            score,
+            max_combo,
+            perfect,
+            mods,
+            life_bar,
+            timestamp,
+            score_id,
+        })
+    }
+}
+
+/// The life bar is stored as comma-separated pairs of time in milliseconds
+/// and life between 0 and 1. Both orders of the pair are accepted.
+fn parse_life_bar(life_bar: &str) -> Vec<LifeBarPoint> {
+    life_bar
+        .split(',')
+        .filter_map(|point| {
+            let (a, b) = point.split_once('|')?;
+            let (a, b) = (a.trim(), b.trim());
+
+            let (time, life) = match (a.parse(), b.parse::<f32>()) {
+                (Ok(time), Ok(life)) if (0.0..=1.0).contains(&life) => (time, life),
+                _ => (b.parse().ok()?, a.parse().ok()?),
+            };
+
+            (0.0..=1.0)
+                .contains(&life)
+                .then_some(LifeBarPoint { time, life })
+        })
+        .collect()
+}
+
+struct Reader<'b> {
+    bytes: &'b [u8],
+}
+
+impl<'b> Reader<'b> {
+    fn take(&mut self, len: usize) -> Option<&'b [u8]> {
+        if self.bytes.len() < len {
+            return None;
+        }
+
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+
+        Some(taken)
+    }
+
+    fn skip(&mut self, len: usize) -> Option<()> {
+        self.take(len).map(|_| ())
+    }
+
+    fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take(N).and_then(|bytes| bytes.try_into().ok())
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.array().map(u8::from_le_bytes)
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.array().map(u16::from_le_bytes)
+    }
+
+    fn i32(&mut self) -> Option<i32> {
+        self.array().map(i32::from_le_bytes)
+    }
+
+    fn i64(&mut self) -> Option<i64> {
+        self.array().map(i64::from_le_bytes)
+    }
+
+    fn uleb128(&mut self) -> Option<usize> {
+        let mut value = 0;
+        let mut shift = 0;
+
+        loop {
+            let byte = self.u8()?;
+            value |= ((byte & 0x7F) as usize).checked_shl(shift)?;
+
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+
+            shift += 7;
+        }
+    }
+
+    /// Strings are either a single `0x00` byte if they're not present
+    /// or `0x0b`, followed by the ULEB128 encoded length, and then the UTF-8 content.
+    fn string(&mut self) -> Result<Option<String>> {
+        match self.u8() {
+            Some(0x00) => Ok(None),
+            Some(0x0b) => {
+                let len = self.uleb128().wrap_err("missing string length")?;
+                let bytes = self.take(len).wrap_err("string is too short")?;
+
+                String::from_utf8(bytes.to_vec())
+                    .map(Some)
+                    .wrap_err("string is not valid UTF-8")
+            }
+            Some(other) => bail!("invalid string indicator {other:#x}"),
+            None => bail!("missing string"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_string(bytes: &mut Vec<u8>, s: &str) {
+        bytes.push(0x0b);
+        bytes.push(s.len() as u8);
+        bytes.extend_from_slice(s.as_bytes());
+    }
+
+    fn replay_bytes(with_score_id: bool) -> Vec<u8> {
+        let mut bytes = vec![1];
+        bytes.extend_from_slice(&20221210_i32.to_le_bytes());
+        push_string(&mut bytes, "d41d8cd98f00b204e9800998ecf8427e");
+        push_string(&mut bytes, "Badewanne3");
+        bytes.push(0x00);
+
+        for count in [500_u16, 20, 3, 40, 10, 1] {
+            bytes.extend_from_slice(&count.to_le_bytes());
+        }
+
+        bytes.extend_from_slice(&1_234_567_i32.to_le_bytes());
+        bytes.extend_from_slice(&789_u16.to_le_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(&72_i32.to_le_bytes());
+        push_string(&mut bytes, "0|1,0.5|1500,3000|1,");
+
+        // 2022-12-10 00:00:00 UTC
+        let ticks = (1_670_630_400 + TICKS_EPOCH_OFFSET) * TICKS_PER_SECOND;
+        bytes.extend_from_slice(&ticks.to_le_bytes());
+
+        if with_score_id {
+            bytes.extend_from_slice(&3_i32.to_le_bytes());
+            bytes.extend_from_slice(&[1, 2, 3]);
+            bytes.extend_from_slice(&4_100_000_000_i64.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    #[test]
+    fn parse_header() {
+        let header = ReplayHeader::parse(&replay_bytes(true)).unwrap();
+
+        assert_eq!(header.mode, GameMode::Taiko);
+        assert_eq!(header.version, 20221210);
+        assert_eq!(header.map_md5, "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(header.player, "Badewanne3");
+        assert_eq!(header.count_300, 500);
+        assert_eq!(header.count_miss, 1);
+        assert_eq!(header.score, 1_234_567);
+        assert_eq!(header.max_combo, 789);
+        assert!(!header.perfect);
+        assert_eq!(header.mods, GameMods::Hidden | GameMods::DoubleTime);
+        assert_eq!(header.timestamp.unix_timestamp(), 1_670_630_400);
+        assert_eq!(header.score_id, Some(4_100_000_000));
+
+        let life_bar =
+            [(0, 1.0), (1500, 0.5), (3000, 1.0)].map(|(time, life)| LifeBarPoint { time, life });
+
+        assert_eq!(header.life_bar, life_bar);
+    }
+
+    #[test]
+    fn parse_header_without_score_id() {
+        let header = ReplayHeader::parse(&replay_bytes(false)).unwrap();
+        assert_eq!(header.score_id, None);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(ReplayHeader::parse(&[]).is_err());
+        assert!(ReplayHeader::parse(&[4]).is_err());
+
+        let mut bytes = replay_bytes(false);
+        bytes.truncate(40);
+        assert!(ReplayHeader::parse(&bytes).is_err());
+    }
+}
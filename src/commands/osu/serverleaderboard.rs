@@ -23,7 +23,9 @@ use crate::{
     The leaderboards will contain all members of this server that are linked to an osu! username \
     which was cached through some command beforehand.\n\
     Since only the cached data is used, no values are guaranteed to be up-to-date. \
-    They're just snapshots from the last time the user was retrieved through a command.\n\n\
+    They're just snapshots from the last time the user was retrieved through a command \
+    unless the server enabled `leaderboard_refresh` via `/serverconfig edit`, \
+    in which case the stats of linked members are refreshed every few hours.\n\n\
     There are three reasons why a user might be missing from the leaderboard:\n\
     - They are not linked through the `/link` command\n\
     - Their osu! user stats have not been cached yet. \
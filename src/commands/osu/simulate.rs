@@ -32,9 +32,12 @@ use super::{HasMods, ModsResult};
 )]
 /// Simulate a score on a map
 pub struct Simulate {
-    #[command(help = "Specify a map either by map url or map id.\n\
+    #[command(
+        autocomplete = true,
+        help = "Specify a map either by map url or map id.\n\
         If none is specified, it will search in the recent channel history \
-        and pick the first map it can find.")]
+        and pick the first map it can find."
+    )]
     /// Specify a map url or map id
     map: Option<String>,
     #[command(
@@ -172,16 +175,16 @@ async fn simulate(ctx: Arc<Context>, orig: CommandOrigin<'_>, args: SimulateArgs
             return orig.error(&ctx, content).await;
         }
         None => {
-            let msgs = match ctx.retrieve_channel_history(orig.channel_id()).await {
-                Ok(msgs) => msgs,
+            let map_id = match ctx.recent_map_id(orig.channel_id(), 0).await {
+                Ok(map_id) => map_id,
                 Err(err) => {
                     let _ = orig.error(&ctx, GENERAL_ISSUE).await;
 
-                    return Err(err.wrap_err("failed to retrieve channel history"));
+                    return Err(err.wrap_err("failed to get recent map"));
                 }
             };
 
-            match MapIdType::map_from_msgs(&msgs, 0) {
+            match map_id {
                 Some(id) => id,
                 None => {
                     let content = "No beatmap specified and none found in recent channel history. \
@@ -116,6 +116,7 @@ pub enum SnipePlayer<'a> {
 )]
 /// Display a user's recent national #1 scores
 pub struct SnipePlayerGain<'a> {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
@@ -131,6 +132,7 @@ pub struct SnipePlayerGain<'a> {
 #[command(name = "list")]
 /// List all national #1 scores of a player
 pub struct SnipePlayerList<'a> {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
@@ -198,6 +200,7 @@ impl fmt::Display for SnipePlayerListOrder {
 )]
 /// Display a user's recently lost national #1 scores
 pub struct SnipePlayerLoss<'a> {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
@@ -213,6 +216,7 @@ pub struct SnipePlayerLoss<'a> {
 #[command(name = "stats")]
 /// Stats about a user's national #1 scores
 pub struct SnipePlayerStats<'a> {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
@@ -231,6 +235,7 @@ pub struct SnipePlayerStats<'a> {
 )]
 /// Sniped users of the last 8 weeks
 pub struct SnipePlayerSniped<'a> {
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
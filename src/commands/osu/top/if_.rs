@@ -41,6 +41,7 @@ pub struct TopIf<'a> {
     mods: Cow<'a, str>,
     /// Specify a gamemode
     mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
@@ -49,6 +49,7 @@ mod old;
 pub struct Top {
     /// Specify a gamemode
     mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<String>,
     #[command(help = "Choose how the scores should be ordered, defaults to `pp`.")]
@@ -57,6 +57,7 @@ pub enum TopOld<'a> {
 pub struct TopOldOsu<'a> {
     /// Choose which version should replace the current pp system
     version: TopOldOsuVersion,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
@@ -123,6 +124,7 @@ impl TryFrom<i32> for TopOldOsuVersion {
 pub struct TopOldTaiko<'a> {
     /// Choose which version should replace the current pp system
     version: TopOldTaikoVersion,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
@@ -166,6 +168,7 @@ impl TryFrom<i32> for TopOldTaikoVersion {
 pub struct TopOldCatch<'a> {
     /// Choose which version should replace the current pp system
     version: TopOldCatchVersion,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
@@ -209,6 +212,7 @@ impl TryFrom<i32> for TopOldCatchVersion {
 pub struct TopOldMania<'a> {
     /// Choose which version should replace the current pp system
     version: TopOldManiaVersion,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(
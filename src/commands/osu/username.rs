@@ -0,0 +1,52 @@
+use eyre::{Result, WrapErr};
+use twilight_model::application::command::CommandOptionChoice;
+
+use crate::{
+    util::{interaction::InteractionCommand, InteractionCommandExt},
+    Context,
+};
+
+/// osu! usernames can't be longer than this
+const MAX_USERNAME_LEN: usize = 15;
+
+/// Suggest usernames that start with the given input.
+///
+/// Names that were recently used in the guild are suggested first,
+/// followed by names of linked guild members.
+pub async fn handle_username_autocomplete(
+    ctx: &Context,
+    command: &InteractionCommand,
+    name: &str,
+) -> Result<()> {
+    let name = name.trim();
+
+    if name.len() > MAX_USERNAME_LEN {
+        command.autocomplete(ctx, Vec::new()).await?;
+
+        return Ok(());
+    }
+
+    let discord_ids: Vec<_> = match command.guild_id {
+        Some(guild) if !name.is_empty() => ctx.cache.members(guild, |id| id.get() as i64),
+        _ => Vec::new(),
+    };
+
+    let names = ctx
+        .psql()
+        .get_name_suggestions(name, command.guild_id, &discord_ids)
+        .await
+        .wrap_err("failed to get name suggestions")?;
+
+    let choices = names
+        .into_iter()
+        .map(|name| CommandOptionChoice::String {
+            name: name.to_string(),
+            name_localizations: None,
+            value: name.to_string(),
+        })
+        .collect();
+
+    command.autocomplete(ctx, choices).await?;
+
+    Ok(())
+}
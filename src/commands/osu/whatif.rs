@@ -58,6 +58,7 @@ pub struct WhatIf<'a> {
     pp: f32,
     /// Specify a gamemode
     mode: Option<GameModeOption>,
+    #[command(autocomplete = true)]
     /// Specify a username
     name: Option<Cow<'a, str>>,
     #[command(min_value = 1, max_value = 1000)]
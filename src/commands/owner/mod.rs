@@ -87,7 +87,7 @@ pub enum OwnerTracking {
 #[cfg(feature = "osutracking")]
 #[derive(CommandModel, CreateCommand)]
 #[command(name = "interval")]
-/// Adjust the tracking interval
+/// Adjust the base interval from which each user's tracking interval is derived
 pub struct OwnerTrackingInterval {
     /// Specify the interval in seconds, defaults to 9000
     number: Option<i64>,
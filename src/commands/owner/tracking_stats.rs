@@ -23,23 +23,26 @@ pub async fn trackingstats(ctx: Arc<Context>, command: InteractionCommand) -> Re
             inline: true,
         },
         EmbedField {
-            name: "Interval per user".to_owned(),
+            name: "Base interval".to_owned(),
             value: format!("{}s", stats.interval),
             inline: true,
         },
         EmbedField {
-            name: "Min interval".to_owned(),
+            name: "Next pop in".to_owned(),
             value: format!("{}s", stats.wait_interval),
             inline: true,
         },
         EmbedField {
-            name: "Milliseconds per user".to_owned(),
-            value: format!("{}ms", stats.ms_per_track),
+            name: "User intervals (min/avg/max)".to_owned(),
+            value: format!(
+                "{}s / {}s / {}s",
+                stats.min_user_interval, stats.avg_user_interval, stats.max_user_interval
+            ),
             inline: true,
         },
         EmbedField {
             name: "Next pop".to_owned(),
-            value: format!("{} | {}", entry.user_id, entry.mode),
+            value: format!("{} | {} | {}", entry.user_id, entry.mode, entry.kind),
             inline: true,
         },
     ];
@@ -2,23 +2,31 @@
 
 use std::{borrow::Cow, sync::Arc};
 
-use command_macros::SlashCommand;
+use command_macros::{HasMods, SlashCommand};
 use eyre::Result;
 use hashbrown::HashMap;
 use rosu_v2::prelude::{GameMode, OsuError, Username};
-use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_interactions::command::{CommandModel, CommandOption, CreateCommand, CreateOption};
 
 use crate::{
     core::commands::prefix::Args,
-    util::{interaction::InteractionCommand, CowUtils, InteractionCommandExt},
+    tracking::TrackingKind,
+    util::{
+        interaction::InteractionCommand, matcher, osu::ModSelection, CowUtils,
+        InteractionCommandExt,
+    },
     Context,
 };
 
-pub use self::{track::*, track_list::*, untrack::*, untrack_all::*};
+pub use self::{track::*, track_history::*, track_list::*, untrack::*, untrack_all::*};
 
-use super::{osu::UserArgs, GameModeOption};
+use super::{
+    osu::{HasMods, ModsResult, UserArgs},
+    GameModeOption,
+};
 
 mod track;
+mod track_history;
 mod track_list;
 mod untrack;
 mod untrack_all;
@@ -26,7 +34,7 @@ mod untrack_all;
 #[derive(CommandModel, CreateCommand, SlashCommand)]
 #[command(name = "track")]
 #[flags(AUTHORITY, ONLY_GUILDS)]
-/// Track top score updates for players
+/// Track top score updates or recent passes of players
 pub enum Track {
     #[command(name = "add")]
     Add(TrackAdd),
@@ -34,29 +42,57 @@ pub enum Track {
     Remove(TrackRemove),
     #[command(name = "list")]
     List(TrackList),
+    #[command(name = "history")]
+    History(TrackHistory),
 }
 
-#[derive(CommandModel, CreateCommand)]
+#[derive(CommandModel, CreateCommand, HasMods)]
 #[command(
     name = "add",
     help = "Add users to the tracking list for this channel.\n\
-    If a tracked user gets a new top score, this channel will be notified about it."
+    If a tracked user gets a new top score, this channel will be notified about it.\n\
+    With the `kind` option set to `Recent passes`, the channel will instead be notified \
+    when the user gets a new #1 on a map or passes a map for the first time.\n\
+    Notifications can be narrowed down further through the `min_pp`, `min_stars`, and `mods` options.\n\
+    If a user is already tracked in this channel, their filters will be overwritten."
 )]
-/// Track top scores of a player
+/// Track top scores or recent passes of a player
 pub struct TrackAdd {
     /// Choose a username to be tracked
     name: String,
     /// Specify a mode for the tracked users
     mode: GameModeOption,
+    /// Specify whether top scores or recent passes should be tracked, defaults to top scores
+    kind: Option<TrackKindOption>,
     #[command(
         min_value = 1,
         max_value = 100,
         help = "If not specified, updates in the user's top50 will trigger notification messages.\n\
         Instead of the top50, this `limit` option allows to adjust the maximum index within \
-        the top scores.\nThe value must be between 1 and 100."
+        the top scores.\nWhen tracking recent passes, this is the amount of recent scores \
+        that will be checked each time.\nThe value must be between 1 and 100."
     )]
     /// Between 1-100, default 50, notify on updates of the user's top X scores
     limit: Option<i64>,
+    #[command(min_value = 0.0)]
+    /// Only notify on top scores with at least this much pp
+    min_pp: Option<f32>,
+    #[command(
+        min_value = 0.0,
+        help = "Only notify on top scores whose star rating, including mods, is at least this value."
+    )]
+    /// Only notify on top scores with at least this star rating
+    min_stars: Option<f32>,
+    #[command(help = "Only notify on top scores that match the specified mods.\n\
+        Mods must be given as `+mods` for included mods, `+mods!` for exact mods, \
+        or `-mods!` for excluded mods.\n\
+        Examples:\n\
+        - `+hd`: Scores must have at least `HD` but can also have more other mods\n\
+        - `+hdhr!`: Scores must have exactly `HDHR`\n\
+        - `-ezhd!`: Scores must have neither `EZ` nor `HD` e.g. `HDDT` would get filtered out\n\
+        - `-nm!`: Scores can not be nomod so there must be any other mod")]
+    /// Specify mods (`+mods` for included, `+mods!` for exact, `-mods!` for excluded)
+    mods: Option<String>,
     /// Specify a second username
     name2: Option<String>,
     /// Specify a third username
@@ -88,6 +124,8 @@ pub struct TrackRemoveUser {
     name: String,
     /// Specify a mode for the tracked users
     mode: Option<GameModeOption>,
+    /// Only untrack top scores or recent passes
+    kind: Option<TrackKindOption>,
 }
 
 #[derive(CommandModel, CreateCommand)]
@@ -103,9 +141,53 @@ pub struct TrackRemoveAll {
 /// List all players that are tracked in this channel
 pub struct TrackList;
 
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "history",
+    help = "List all score notifications that were sent to this channel, latest first.\n\
+    Notifications can be filtered by user, mode, and the date on which they were sent.\n\
+    Dates must be of the form `YYYY-MM-DD`, e.g. `2022-08-21`."
+)]
+/// List previous score notifications of this channel
+pub struct TrackHistory {
+    /// Only show notifications for this user
+    name: Option<String>,
+    /// Only show notifications for this mode
+    mode: Option<GameModeOption>,
+    /// Only show notifications sent on this date or later (YYYY-MM-DD)
+    since: Option<String>,
+    /// Only show notifications sent on this date or earlier (YYYY-MM-DD)
+    until: Option<String>,
+}
+
+#[derive(Copy, Clone, CommandOption, CreateOption)]
+pub enum TrackKindOption {
+    #[option(name = "Top scores", value = "top")]
+    Top,
+    #[option(name = "Recent passes", value = "recent")]
+    Recent,
+}
+
+impl From<TrackKindOption> for TrackingKind {
+    #[inline]
+    fn from(kind: TrackKindOption) -> Self {
+        match kind {
+            TrackKindOption::Top => Self::Top,
+            TrackKindOption::Recent => Self::Recent,
+        }
+    }
+}
+
 async fn slash_track(ctx: Arc<Context>, mut command: InteractionCommand) -> Result<()> {
     match Track::from_interaction(command.input_data())? {
-        Track::Add(add) => track(ctx, (&mut command).into(), add.into()).await,
+        Track::Add(add) => match TrackArgs::try_from(add) {
+            Ok(args) => track(ctx, (&mut command).into(), args).await,
+            Err(content) => {
+                command.error(&ctx, content).await?;
+
+                Ok(())
+            }
+        },
         Track::Remove(TrackRemove::User(user)) => {
             untrack(ctx, (&mut command).into(), user.into()).await
         }
@@ -113,6 +195,7 @@ async fn slash_track(ctx: Arc<Context>, mut command: InteractionCommand) -> Resu
             untrackall(ctx, (&mut command).into(), all.mode.map(GameMode::from)).await
         }
         Track::List(_) => tracklist(ctx, (&mut command).into()).await,
+        Track::History(history) => trackhistory(ctx, (&mut command).into(), history).await,
     }
 }
 
@@ -158,16 +241,31 @@ async fn get_names<'n>(
 
 struct TrackArgs {
     mode: Option<GameMode>,
+    kind: Option<TrackingKind>,
     name: String,
     limit: Option<u64>,
+    min_pp: Option<f32>,
+    min_stars: Option<f32>,
+    mods: Option<ModSelection>,
     more_names: Vec<String>,
 }
 
+impl TrackArgs {
+    const ERR_PARSE_MODS: &'static str = "Failed to parse mods.\n\
+        If you want included mods, specify it e.g. as `+hrdt`.\n\
+        If you want exact mods, specify it e.g. as `+hdhr!`.\n\
+        And if you want to exclude mods, specify it e.g. as `-hdnf!`.";
+}
+
 impl TrackArgs {
     async fn args(mode: Option<GameMode>, args: Args<'_>) -> Result<Self, Cow<'static, str>> {
         let mut name = None;
         let mut more_names = Vec::new();
         let mut limit = args.num;
+        let mut kind = None;
+        let mut min_pp = None;
+        let mut min_stars = None;
+        let mut mods = None;
 
         for arg in args.map(CowUtils::cow_to_ascii_lowercase) {
             if let Some(idx) = arg.find('=').filter(|&i| i > 0) {
@@ -183,9 +281,40 @@ impl TrackArgs {
                             return Err(content.into());
                         }
                     },
+                    "kind" | "k" => match value {
+                        "top" | "best" => kind = Some(TrackingKind::Top),
+                        "recent" | "r" => kind = Some(TrackingKind::Recent),
+                        _ => {
+                            let content =
+                                "Failed to parse `kind`. Must be either `top` or `recent`.";
+
+                            return Err(content.into());
+                        }
+                    },
+                    "pp" | "minpp" => match value.parse() {
+                        Ok(num) => min_pp = Some(num),
+                        Err(_) => {
+                            let content = "Failed to parse `pp`. Must be a number.";
+
+                            return Err(content.into());
+                        }
+                    },
+                    "stars" | "minstars" => match value.parse() {
+                        Ok(num) => min_stars = Some(num),
+                        Err(_) => {
+                            let content = "Failed to parse `stars`. Must be a number.";
+
+                            return Err(content.into());
+                        }
+                    },
+                    "mods" => match matcher::get_mods(value) {
+                        Some(selection) => mods = Some(selection),
+                        None => return Err(Self::ERR_PARSE_MODS.into()),
+                    },
                     _ => {
                         let content = format!(
-                            "Unrecognized option `{key}`.\nAvailable options are: `limit`."
+                            "Unrecognized option `{key}`.\n\
+                            Available options are: `limit`, `kind`, `pp`, `stars`, or `mods`."
                         );
 
                         return Err(content.into());
@@ -203,9 +332,17 @@ impl TrackArgs {
             None => return Err("You must specify at least one username".into()),
         };
 
+        if let Some(Err(content)) = mods.map(ModSelection::validate) {
+            return Err(content.into());
+        }
+
         let args = Self {
             name,
+            kind,
             limit,
+            min_pp,
+            min_stars,
+            mods,
             more_names,
             mode,
         };
@@ -214,12 +351,28 @@ impl TrackArgs {
     }
 }
 
-impl From<TrackAdd> for TrackArgs {
-    fn from(add: TrackAdd) -> Self {
+impl TryFrom<TrackAdd> for TrackArgs {
+    type Error = &'static str;
+
+    fn try_from(add: TrackAdd) -> Result<Self, Self::Error> {
+        let mods = match add.mods() {
+            ModsResult::Mods(mods) => {
+                mods.validate()?;
+
+                Some(mods)
+            }
+            ModsResult::None => None,
+            ModsResult::Invalid => return Err(Self::ERR_PARSE_MODS),
+        };
+
         let TrackAdd {
             name,
             mode,
+            kind,
             limit,
+            min_pp,
+            min_stars,
+            mods: _,
             name2,
             name3,
             name4,
@@ -244,23 +397,31 @@ impl From<TrackAdd> for TrackArgs {
             more_names.push(name);
         }
 
-        Self {
+        Ok(Self {
             mode: Some(mode.into()),
+            kind: kind.map(TrackingKind::from),
             name,
             limit: limit.map(|l| l as u64),
+            min_pp,
+            min_stars,
+            mods,
             more_names,
-        }
+        })
     }
 }
 
 impl From<TrackRemoveUser> for TrackArgs {
     fn from(remove: TrackRemoveUser) -> Self {
-        let TrackRemoveUser { name, mode } = remove;
+        let TrackRemoveUser { name, mode, kind } = remove;
 
         Self {
             mode: mode.map(GameMode::from),
+            kind: kind.map(TrackingKind::from),
             name,
             limit: None,
+            min_pp: None,
+            min_stars: None,
+            mods: None,
             more_names: Vec::new(),
         }
     }
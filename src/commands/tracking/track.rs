@@ -7,7 +7,9 @@ use time::OffsetDateTime;
 
 use crate::{
     core::commands::CommandOrigin,
+    database::TrackingFilters,
     embeds::{EmbedData, TrackEmbed},
+    tracking::{TrackingEntry, TrackingKind},
     util::{builder::MessageBuilder, constants::OSU_API_ISSUE, ChannelExt},
     Context,
 };
@@ -22,7 +24,11 @@ pub(super) async fn track(
     let TrackArgs {
         name,
         mode,
+        kind,
         limit,
+        min_pp,
+        min_stars,
+        mods,
         mut more_names,
     } = args;
 
@@ -48,7 +54,15 @@ pub(super) async fn track(
         }
     };
 
+    let filters = TrackingFilters {
+        limit,
+        min_pp,
+        min_stars,
+        mods,
+    };
+
     let mode = mode.unwrap_or(GameMode::Osu);
+    let kind = kind.unwrap_or(TrackingKind::Top);
 
     let users = match super::get_names(&ctx, &more_names, mode).await {
         Ok(map) => map,
@@ -70,12 +84,17 @@ pub(super) async fn track(
     let mut failure = Vec::new();
 
     for (username, user_id) in users {
-        let add_fut = ctx.tracking().add(
+        let entry = TrackingEntry {
             user_id,
             mode,
+            kind,
+        };
+
+        let add_fut = ctx.tracking().add(
+            entry,
             OffsetDateTime::now_utc(),
             channel,
-            limit,
+            filters,
             ctx.psql(),
         );
 
@@ -85,7 +104,8 @@ pub(super) async fn track(
             Err(err) => {
                 warn!("{:?}", err.wrap_err("Failed to add tracked entry"));
 
-                let embed = TrackEmbed::new(mode, success, failure, Some(username), limit).build();
+                let embed =
+                    TrackEmbed::new(mode, kind, success, failure, Some(username), filters).build();
 
                 let builder = MessageBuilder::new().embed(embed);
                 orig.create_message(&ctx, &builder).await?;
@@ -95,7 +115,7 @@ pub(super) async fn track(
         }
     }
 
-    let embed = TrackEmbed::new(mode, success, failure, None, limit);
+    let embed = TrackEmbed::new(mode, kind, success, failure, None, filters);
     let builder = MessageBuilder::new().embed(embed.build());
     orig.create_message(&ctx, &builder).await?;
 
@@ -113,9 +133,16 @@ pub(super) async fn track(
     a new score in his top 42.\n\
     Alternatively, you can provide a limit by specifying `limit=number`, \
     e.g. `track limit=42 badewanne3`.\n\
-    The limit must be between 1 and 100, **defaults to 50** if none is given."
+    The limit must be between 1 and 100, **defaults to 50** if none is given.\n\
+    To only be notified about some top scores, you can also specify `pp=number` for a minimum pp value, \
+    `stars=number` for a minimum star rating, and `mods=+mods` (or `+mods!`, `-mods!`) for a mod filter, \
+    e.g. `track pp=300 mods=+hd badewanne3`.\n\
+    To be notified about new #1s and first passes of maps instead of top scores, specify `kind=recent`.\n\
+    Tracking an already tracked user again will update their filters."
+)]
+#[usage(
+    "[limit=number] [kind=top/recent] [pp=number] [stars=number] [mods=+mods] [username1] [username2] ..."
 )]
-#[usage("[limit=number] [username1] [username2] ...")]
 #[examples(
     "badewanne3 \"freddie benson\" peppy limit=23",
     "limit=45 cookiezi whitecat",
@@ -145,9 +172,16 @@ async fn prefix_track(ctx: Arc<Context>, msg: &Message, args: Args<'_>) -> Resul
     a new score in his top 42.\n\
     Alternatively, you can provide a limit by specifying `limit=number`, \
     e.g. `trackmania limit=42 badewanne3`.\n\
-    The limit must be between 1 and 100, **defaults to 50** if none is given."
+    The limit must be between 1 and 100, **defaults to 50** if none is given.\n\
+    To only be notified about some top scores, you can also specify `pp=number` for a minimum pp value, \
+    `stars=number` for a minimum star rating, and `mods=+mods` (or `+mods!`, `-mods!`) for a mod filter, \
+    e.g. `trackmania pp=300 mods=+hd badewanne3`.\n\
+    To be notified about new #1s and first passes of maps instead of top scores, specify `kind=recent`.\n\
+    Tracking an already tracked user again will update their filters."
+)]
+#[usage(
+    "[limit=number] [kind=top/recent] [pp=number] [stars=number] [mods=+mods] [username1] [username2] ..."
 )]
-#[usage("[limit=number] [username1] [username2] ...")]
 #[examples(
     "badewanne3 \"freddie benson\" peppy limit=23",
     "limit=45 cookiezi whitecat",
@@ -177,9 +211,16 @@ pub async fn prefix_trackmania(ctx: Arc<Context>, msg: &Message, args: Args<'_>)
     a new score in his top 42.\n\
     Alternatively, you can provide a limit by specifying `limit=number`, \
     e.g. `tracktaiko limit=42 badewanne3`.\n\
-    The limit must be between 1 and 100, **defaults to 50** if none is given."
+    The limit must be between 1 and 100, **defaults to 50** if none is given.\n\
+    To only be notified about some top scores, you can also specify `pp=number` for a minimum pp value, \
+    `stars=number` for a minimum star rating, and `mods=+mods` (or `+mods!`, `-mods!`) for a mod filter, \
+    e.g. `tracktaiko pp=300 mods=+hd badewanne3`.\n\
+    To be notified about new #1s and first passes of maps instead of top scores, specify `kind=recent`.\n\
+    Tracking an already tracked user again will update their filters."
+)]
+#[usage(
+    "[limit=number] [kind=top/recent] [pp=number] [stars=number] [mods=+mods] [username1] [username2] ..."
 )]
-#[usage("[limit=number] [username1] [username2] ...")]
 #[examples(
     "badewanne3 \"freddie benson\" peppy limit=23",
     "limit=45 cookiezi whitecat",
@@ -209,9 +250,16 @@ pub async fn prefix_tracktaiko(ctx: Arc<Context>, msg: &Message, args: Args<'_>)
     a new score in his top 42.\n\
     Alternatively, you can provide a limit by specifying `limit=number`, \
     e.g. `trackctb limit=42 badewanne3`.\n\
-    The limit must be between 1 and 100, **defaults to 50** if none is given."
+    The limit must be between 1 and 100, **defaults to 50** if none is given.\n\
+    To only be notified about some top scores, you can also specify `pp=number` for a minimum pp value, \
+    `stars=number` for a minimum star rating, and `mods=+mods` (or `+mods!`, `-mods!`) for a mod filter, \
+    e.g. `trackctb pp=300 mods=+hd badewanne3`.\n\
+    To be notified about new #1s and first passes of maps instead of top scores, specify `kind=recent`.\n\
+    Tracking an already tracked user again will update their filters."
+)]
+#[usage(
+    "[limit=number] [kind=top/recent] [pp=number] [stars=number] [mods=+mods] [username1] [username2] ..."
 )]
-#[usage("[limit=number] [username1] [username2] ...")]
 #[examples(
     "badewanne3 \"freddie benson\" peppy limit=23",
     "limit=45 cookiezi whitecat",
@@ -0,0 +1,107 @@
+use std::{slice, sync::Arc};
+
+use eyre::{Report, Result};
+use rosu_v2::prelude::{GameMode, OsuError};
+use time::{Date, Duration, OffsetDateTime, PrimitiveDateTime, Time};
+
+use crate::{
+    core::commands::CommandOrigin,
+    pagination::TrackHistoryPagination,
+    util::{
+        builder::MessageBuilder,
+        constants::{GENERAL_ISSUE, OSU_API_ISSUE},
+        datetime::DATE_FORMAT,
+    },
+    Context,
+};
+
+use super::TrackHistory;
+
+pub(super) async fn trackhistory(
+    ctx: Arc<Context>,
+    orig: CommandOrigin<'_>,
+    args: TrackHistory,
+) -> Result<()> {
+    let TrackHistory {
+        name,
+        mode,
+        since,
+        until,
+    } = args;
+
+    let mode = mode.map(GameMode::from);
+
+    let since = match since.as_deref().map(parse_date).transpose() {
+        Ok(since) => since.unwrap_or(OffsetDateTime::UNIX_EPOCH),
+        Err(content) => return orig.error(&ctx, content).await,
+    };
+
+    // Include the whole day of the `until` date
+    let until = match until.as_deref().map(parse_date).transpose() {
+        Ok(Some(until)) => until + Duration::days(1),
+        Ok(None) => OffsetDateTime::now_utc(),
+        Err(content) => return orig.error(&ctx, content).await,
+    };
+
+    if since >= until {
+        let content = "The `since` date must come before the `until` date";
+
+        return orig.error(&ctx, content).await;
+    }
+
+    let user_id = match name {
+        Some(name) => {
+            let names = slice::from_ref(&name);
+
+            match super::get_names(&ctx, names, mode.unwrap_or(GameMode::Osu)).await {
+                Ok(users) => users.into_values().next(),
+                Err((OsuError::NotFound, name)) => {
+                    let content = format!("User `{name}` was not found");
+
+                    return orig.error(&ctx, content).await;
+                }
+                Err((err, _)) => {
+                    let _ = orig.error(&ctx, OSU_API_ISSUE).await;
+                    let report = Report::new(err).wrap_err("failed to get names");
+
+                    return Err(report);
+                }
+            }
+        }
+        None => None,
+    };
+
+    let channel = orig.channel_id();
+
+    let notifs_fut = ctx
+        .psql()
+        .get_osu_tracking_notifications(channel, user_id, mode, since, until);
+
+    let notifs = match notifs_fut.await {
+        Ok(notifs) => notifs,
+        Err(err) => {
+            let _ = orig.error(&ctx, GENERAL_ISSUE).await;
+
+            return Err(err.wrap_err("failed to get tracking notifications"));
+        }
+    };
+
+    if notifs.is_empty() {
+        let content = "No score notifications were found for this channel";
+        let builder = MessageBuilder::new().content(content);
+        orig.create_message(&ctx, &builder).await?;
+
+        return Ok(());
+    }
+
+    TrackHistoryPagination::builder(notifs)
+        .start_by_update()
+        .start(ctx, orig)
+        .await
+}
+
+fn parse_date(date: &str) -> Result<OffsetDateTime, String> {
+    Date::parse(date.trim(), DATE_FORMAT)
+        .map(|date| PrimitiveDateTime::new(date, Time::MIDNIGHT).assume_utc())
+        .map_err(|_| format!("Failed to parse date `{date}`, must be of the form `YYYY-MM-DD`"))
+}
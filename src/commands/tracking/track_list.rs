@@ -11,7 +11,9 @@ use twilight_model::id::{marker::ChannelMarker, Id};
 
 use crate::{
     core::commands::CommandOrigin,
+    database::TrackingFilters,
     embeds::{EmbedData, TrackListEmbed},
+    tracking::{TrackingEntry, TrackingKind},
     util::{builder::MessageBuilder, constants::OSU_API_ISSUE},
     Context,
 };
@@ -19,7 +21,8 @@ use crate::{
 pub struct TracklistUserEntry {
     pub name: Username,
     pub mode: GameMode,
-    pub limit: usize,
+    pub kind: TrackingKind,
+    pub filters: TrackingFilters,
 }
 
 #[command]
@@ -47,6 +50,7 @@ pub async fn tracklist(ctx: Arc<Context>, orig: CommandOrigin<'_>) -> Result<()>
     users.sort_unstable_by(|a, b| {
         (a.mode as u8)
             .cmp(&(b.mode as u8))
+            .then((a.kind as u8).cmp(&(b.kind as u8)))
             .then(a.name.cmp(&b.name))
     });
 
@@ -70,9 +74,12 @@ pub async fn tracklist(ctx: Arc<Context>, orig: CommandOrigin<'_>) -> Result<()>
 async fn get_users(
     ctx: &Context,
     channel: Id<ChannelMarker>,
-    tracked: Vec<(u32, GameMode, usize)>,
+    tracked: Vec<(TrackingEntry, TrackingFilters)>,
 ) -> OsuResult<Vec<TracklistUserEntry>> {
-    let user_ids: Vec<_> = tracked.iter().map(|(id, ..)| *id as i32).collect();
+    let user_ids: Vec<_> = tracked
+        .iter()
+        .map(|(entry, _)| entry.user_id as i32)
+        .collect();
 
     // Get all names that are stored in the DB
     let stored_names = match ctx.psql().get_names_by_ids(&user_ids).await {
@@ -87,12 +94,19 @@ async fn get_users(
     let mut users = Vec::with_capacity(tracked.len());
 
     // Get all missing names from the api
-    for (user_id, mode, limit) in tracked {
+    for (entry, filters) in tracked {
+        let TrackingEntry {
+            user_id,
+            mode,
+            kind,
+        } = entry;
+
         let entry = match stored_names.get(&user_id) {
             Some(name) => TracklistUserEntry {
                 name: name.to_owned(),
                 mode,
-                limit,
+                kind,
+                filters,
             },
             None => match ctx.osu().user(user_id).mode(mode).await {
                 Ok(user) => {
@@ -103,13 +117,14 @@ async fn get_users(
                     TracklistUserEntry {
                         name: user.username,
                         mode,
-                        limit,
+                        kind,
+                        filters,
                     }
                 }
                 Err(OsuError::NotFound) => {
-                    let remove_fut = ctx
-                        .tracking()
-                        .remove_user(user_id, None, channel, ctx.psql());
+                    let remove_fut =
+                        ctx.tracking()
+                            .remove_user(user_id, None, None, channel, ctx.psql());
 
                     if let Err(err) = remove_fut.await {
                         let report = err.wrap_err(format!(
@@ -19,9 +19,10 @@ use super::TrackArgs;
 #[help(
     "Stop notifying a channel about new plays in a user's top100.\n\
     Specified users will be untracked for all modes.\n\
-    You can specify up to ten usernames per command invocation."
+    You can specify up to ten usernames per command invocation.\n\
+    To only untrack top scores or recent passes, specify `kind=top` or `kind=recent`."
 )]
-#[usage("[username1] [username2] ...")]
+#[usage("[kind=top/recent] [username1] [username2] ...")]
 #[example("badewanne3 cookiezi \"freddie benson\" peppy")]
 #[flags(AUTHORITY, ONLY_GUILDS)]
 #[group(Tracking)]
@@ -44,6 +45,7 @@ pub(super) async fn untrack(
     let TrackArgs {
         name,
         mode,
+        kind,
         mut more_names,
         ..
     } = args;
@@ -77,7 +79,7 @@ pub(super) async fn untrack(
     for (username, user_id) in users.into_iter() {
         let remove_fut = ctx
             .tracking()
-            .remove_user(user_id, mode, channel, ctx.psql());
+            .remove_user(user_id, mode, kind, channel, ctx.psql());
 
         match remove_fut.await {
             Ok(_) => success.insert(username),
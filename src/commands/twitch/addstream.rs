@@ -3,6 +3,7 @@ use eyre::Result;
 
 use crate::{
     core::commands::CommandOrigin,
+    database::{StreamTrack, StreamTrackSettings},
     util::{
         builder::MessageBuilder,
         constants::{GENERAL_ISSUE, TWITCH_API_ISSUE},
@@ -11,7 +12,7 @@ use crate::{
     Context,
 };
 
-use std::sync::Arc;
+use std::{fmt::Write, sync::Arc};
 
 #[command]
 #[flags(AUTHORITY, ONLY_GUILDS)]
@@ -31,10 +32,15 @@ async fn prefix_addstream(ctx: Arc<Context>, msg: &Message, mut args: Args<'_>)
         }
     };
 
-    addstream(ctx, msg.into(), name.as_ref()).await
+    addstream(ctx, msg.into(), name.as_ref(), None).await
 }
 
-pub async fn addstream(ctx: Arc<Context>, orig: CommandOrigin<'_>, name: &'_ str) -> Result<()> {
+pub async fn addstream(
+    ctx: Arc<Context>,
+    orig: CommandOrigin<'_>,
+    name: &'_ str,
+    settings: Option<StreamTrackSettings>,
+) -> Result<()> {
     let twitch_id = match ctx.client().get_twitch_user(name).await {
         Ok(Some(user)) => user.user_id,
         Ok(None) => {
@@ -49,29 +55,74 @@ pub async fn addstream(ctx: Arc<Context>, orig: CommandOrigin<'_>, name: &'_ str
         }
     };
 
-    let channel = orig.channel_id().get();
-    ctx.add_tracking(twitch_id, channel);
+    let channel = orig.channel_id();
 
-    match ctx.psql().add_stream_track(channel, twitch_id).await {
-        Ok(true) => {
-            let content = format!("I'm now tracking `{name}`'s twitch stream in this channel");
-            let builder = MessageBuilder::new().content(content);
+    let previous = ctx
+        .tracked_users_in(channel)
+        .into_iter()
+        .find_map(|(user_id, settings)| (user_id == twitch_id).then_some(settings));
 
-            trace!("Now tracking twitch stream {name} for channel {channel}");
+    // Without explicit settings, the existing ones are kept
+    let settings = match settings {
+        Some(settings) => settings,
+        None => previous.clone().unwrap_or_default(),
+    };
 
-            orig.create_message(&ctx, &builder).await?;
+    if previous.as_ref() == Some(&settings) {
+        let content = format!("Twitch user `{name}` is already being tracked in this channel");
 
-            Ok(())
-        }
-        Ok(false) => {
-            let content = format!("Twitch user `{name}` is already being tracked in this channel");
+        return orig.error(&ctx, content).await;
+    }
 
-            orig.error(&ctx, content).await
-        }
-        Err(err) => {
-            let _ = orig.error(&ctx, GENERAL_ISSUE).await;
+    let upsert_fut = ctx
+        .psql()
+        .upsert_stream_track(channel.get(), twitch_id, &settings);
 
-            Err(err.wrap_err("failed to add stream track"))
-        }
+    if let Err(err) = upsert_fut.await {
+        let _ = orig.error(&ctx, GENERAL_ISSUE).await;
+
+        return Err(err.wrap_err("failed to upsert stream track"));
+    }
+
+    let mut content = if previous.is_some() {
+        format!("Updated the settings for `{name}`'s twitch stream in this channel")
+    } else {
+        format!("I'm now tracking `{name}`'s twitch stream in this channel")
+    };
+
+    let options = settings.to_string();
+
+    if !options.is_empty() {
+        let _ = write!(content, " ({options})");
+    }
+
+    let track = StreamTrack { channel, settings };
+    ctx.add_tracking(twitch_id, track);
+
+    // Notifications are received through subscriptions if possible,
+    // otherwise the stream will be polled
+    #[cfg(all(feature = "server", feature = "twitchtracking"))]
+    if previous.is_none() {
+        let ctx = Arc::clone(&ctx);
+
+        tokio::spawn(async move {
+            if let Err(err) = ctx
+                .twitch_eventsub()
+                .subscribe(ctx.client(), twitch_id)
+                .await
+            {
+                let wrap = format!("failed to subscribe to twitch user {twitch_id}");
+                warn!("{:?}", err.wrap_err(wrap));
+            }
+        });
     }
+
+    // Embed so that role mentions don't ping
+    let builder = MessageBuilder::new().embed(content);
+
+    trace!("Now tracking twitch stream {name} for channel {channel}");
+
+    orig.create_message(&ctx, &builder).await?;
+
+    Ok(())
 }
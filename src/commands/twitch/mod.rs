@@ -5,8 +5,10 @@ use std::sync::Arc;
 use command_macros::SlashCommand;
 use eyre::Result;
 use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::id::{marker::RoleMarker, Id};
 
 use crate::{
+    database::StreamTrackSettings,
     util::{interaction::InteractionCommand, InteractionCommandExt},
     Context,
 };
@@ -38,12 +40,41 @@ pub enum TrackStream {
 #[command(
     name = "add",
     help = "Track a twitch stream in this channel.\n\
-    When the stream goes online, a notification will be send to this channel within a few minutes."
+    When the stream goes online, a notification will be send to this channel within a few minutes.\n\
+    Tracking an already tracked stream again will overwrite its settings."
 )]
 /// Track a twitch stream in this channel
 pub struct TrackStreamAdd {
     /// Name of the twitch channel
     name: String,
+    /// Only notify if the stream is in the osu! category
+    osu_only: Option<bool>,
+    #[command(
+        max_length = 64,
+        help = "Only notify if the stream title contains this keyword, case-insensitive."
+    )]
+    /// Only notify if the stream title contains this keyword
+    title: Option<String>,
+    #[command(
+        help = "Whether the notification should be edited once the stream ends \
+        to show the stream duration and a link to the VOD.\n\
+        Defaults to true."
+    )]
+    /// Edit the notification to show the VOD once the stream ends
+    vod: Option<bool>,
+    /// Role that should be pinged with the notification
+    role: Option<Id<RoleMarker>>,
+}
+
+impl TrackStreamAdd {
+    fn settings(&self) -> StreamTrackSettings {
+        StreamTrackSettings {
+            osu_only: self.osu_only.unwrap_or(false),
+            title_filter: self.title.as_deref().map(str::to_lowercase),
+            vod_edit: self.vod.unwrap_or(true),
+            role: self.role,
+        }
+    }
 }
 
 #[derive(CommandModel, CreateCommand)]
@@ -61,7 +92,11 @@ pub struct TrackStreamList;
 
 pub async fn slash_trackstream(ctx: Arc<Context>, mut command: InteractionCommand) -> Result<()> {
     match TrackStream::from_interaction(command.input_data())? {
-        TrackStream::Add(add) => addstream(ctx, (&mut command).into(), add.name.as_ref()).await,
+        TrackStream::Add(add) => {
+            let settings = Some(add.settings());
+
+            addstream(ctx, (&mut command).into(), add.name.as_ref(), settings).await
+        }
         TrackStream::Remove(remove) => {
             removestream(ctx, (&mut command).into(), remove.name.as_ref()).await
         }
@@ -57,6 +57,29 @@ pub async fn removestream(ctx: Arc<Context>, orig: CommandOrigin<'_>, name: &'_
         Ok(true) => {
             trace!("No longer tracking {name}'s twitch for channel {channel}");
 
+            #[cfg(all(feature = "server", feature = "twitchtracking"))]
+            {
+                let untracked = ctx
+                    .tracked_channels_for(twitch_id)
+                    .map_or(true, |tracks| tracks.is_empty());
+
+                if untracked {
+                    let ctx = Arc::clone(&ctx);
+
+                    tokio::spawn(async move {
+                        if let Err(err) = ctx
+                            .twitch_eventsub()
+                            .unsubscribe(ctx.client(), twitch_id)
+                            .await
+                        {
+                            let wrap =
+                                format!("failed to unsubscribe from twitch user {twitch_id}");
+                            warn!("{:?}", err.wrap_err(wrap));
+                        }
+                    });
+                }
+            }
+
             let content =
                 format!("I'm no longer tracking `{name}`'s twitch stream in this channel");
 
@@ -18,10 +18,19 @@ async fn prefix_trackedstreams(ctx: Arc<Context>, msg: &Message) -> Result<()> {
 }
 
 pub async fn tracked(ctx: Arc<Context>, orig: CommandOrigin<'_>) -> Result<()> {
-    let twitch_ids = ctx.tracked_users_in(orig.channel_id());
+    let tracked = ctx.tracked_users_in(orig.channel_id());
+    let twitch_ids: Vec<_> = tracked.iter().map(|(user_id, _)| *user_id).collect();
 
     let mut twitch_users: Vec<_> = match ctx.client().get_twitch_users(&twitch_ids).await {
-        Ok(users) => users.into_iter().map(|user| user.display_name).collect(),
+        Ok(users) => users
+            .into_iter()
+            .filter_map(|user| {
+                tracked
+                    .iter()
+                    .find(|(user_id, _)| *user_id == user.user_id)
+                    .map(|(_, settings)| (user.display_name, settings.to_string()))
+            })
+            .collect(),
         Err(err) => {
             let _ = orig.error(&ctx, GENERAL_ISSUE).await;
 
@@ -30,20 +39,22 @@ pub async fn tracked(ctx: Arc<Context>, orig: CommandOrigin<'_>) -> Result<()> {
     };
 
     twitch_users.sort_unstable();
-    let mut content = "Tracked twitch streams in this channel:\n".to_owned();
-    let mut users = twitch_users.into_iter();
+    let mut content = "Tracked twitch streams in this channel:".to_owned();
 
-    if let Some(user) = users.next() {
-        let _ = write!(content, "`{user}`");
+    if twitch_users.is_empty() {
+        content.push_str("\nNone");
+    }
+
+    for (user, options) in twitch_users {
+        let _ = write!(content, "\n• `{user}`");
 
-        for user in users {
-            let _ = write!(content, ", `{user}`");
+        if !options.is_empty() {
+            let _ = write!(content, " ({options})");
         }
-    } else {
-        content.push_str("None");
     }
 
-    let builder = MessageBuilder::new().content(content);
+    // Embed so that role mentions don't ping
+    let builder = MessageBuilder::new().embed(content);
     orig.create_message(&ctx, &builder).await?;
 
     Ok(())
@@ -9,7 +9,13 @@ mod role_assign;
 mod roll;
 mod server_config;
 
+#[cfg(any(feature = "osutracking", feature = "twitchtracking"))]
+mod track_webhook;
+
 pub use self::{
     authorities::*, command_count::*, config::*, invite::*, ping::*, prefix::*, prune::*,
     role_assign::*, roll::*, server_config::*,
 };
+
+#[cfg(any(feature = "osutracking", feature = "twitchtracking"))]
+pub use self::track_webhook::*;
@@ -3,10 +3,10 @@ use std::sync::Arc;
 use command_macros::{command, SlashCommand};
 use eyre::{Report, Result};
 use twilight_cache_inmemory::model::CachedGuild;
-use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_interactions::command::{CommandModel, CommandOption, CreateCommand, CreateOption};
 use twilight_model::{
     id::{
-        marker::{GuildMarker, RoleMarker},
+        marker::{ChannelMarker, GuildMarker, RoleMarker},
         Id,
     },
     util::ImageHash,
@@ -149,6 +149,49 @@ pub struct ServerConfigEdit {
     track_limit: Option<i64>,
     /// Specify whether the recent command should show max or if-fc pp when minimized
     minimized_pp: Option<ConfigMinimizedPp>,
+    #[command(
+        help = "Should the osu! stats of linked members be refreshed periodically?\n\
+        If enabled, the `serverleaderboard` command will show values that are \
+        at most a few hours old instead of relying on members using commands."
+    )]
+    /// Should the stats of linked members be refreshed periodically for leaderboards?
+    leaderboard_refresh: Option<EnableDisable>,
+    #[command(
+        help = "Should a weekly digest of the server's osu!standard leaderboard be posted?\n\
+        The digest contains the biggest pp gainers, rank climbers, most playtime, \
+        and newly linked members since the previous digest.\n\
+        It will be posted in the channel specified by `digest_channel` \
+        or in this channel if none is specified.\n\
+        Consider enabling `leaderboard_refresh` so the values are up-to-date."
+    )]
+    /// Should a weekly leaderboard digest be posted?
+    digest: Option<EnableDisable>,
+    #[command(channel_types = "guild_text")]
+    /// Specify the channel for the weekly digest
+    digest_channel: Option<Id<ChannelMarker>>,
+    /// Specify the day of the weekly digest (defaults to monday)
+    digest_day: Option<ConfigDigestDay>,
+    #[command(min_value = 0, max_value = 23)]
+    /// Specify the hour in UTC of the weekly digest (defaults to 12)
+    digest_hour: Option<i64>,
+}
+
+#[derive(Copy, Clone, CommandOption, CreateOption)]
+pub enum ConfigDigestDay {
+    #[option(name = "Monday", value = "mon")]
+    Monday = 0,
+    #[option(name = "Tuesday", value = "tue")]
+    Tuesday = 1,
+    #[option(name = "Wednesday", value = "wed")]
+    Wednesday = 2,
+    #[option(name = "Thursday", value = "thu")]
+    Thursday = 3,
+    #[option(name = "Friday", value = "fri")]
+    Friday = 4,
+    #[option(name = "Saturday", value = "sat")]
+    Saturday = 5,
+    #[option(name = "Sunday", value = "sun")]
+    Sunday = 6,
 }
 
 impl ServerConfigEdit {
@@ -160,6 +203,11 @@ impl ServerConfigEdit {
             || self.retries.is_some()
             || self.track_limit.is_some()
             || self.minimized_pp.is_some()
+            || self.leaderboard_refresh.is_some()
+            || self.digest.is_some()
+            || self.digest_channel.is_some()
+            || self.digest_day.is_some()
+            || self.digest_hour.is_some()
     }
 }
 
@@ -167,6 +215,7 @@ async fn slash_serverconfig(ctx: Arc<Context>, mut command: InteractionCommand)
     let args = ServerConfig::from_interaction(command.input_data())?;
 
     let guild_id = command.guild_id.unwrap();
+    let channel_id = command.channel_id;
 
     let guild = match ctx.cache.guild(guild_id, |guild| guild.into()) {
         Ok(guild) => guild,
@@ -187,7 +236,12 @@ async fn slash_serverconfig(ctx: Arc<Context>, mut command: InteractionCommand)
     if args.any() {
         let f = |config: &mut GuildConfig| {
             let ServerConfigEdit {
+                digest,
+                digest_channel,
+                digest_day,
+                digest_hour,
                 score_embeds,
+                leaderboard_refresh,
                 list_embeds,
                 minimized_pp,
                 profile,
@@ -223,6 +277,30 @@ async fn slash_serverconfig(ctx: Arc<Context>, mut command: InteractionCommand)
             if let Some(with_lyrics) = song_commands {
                 config.with_lyrics = Some(with_lyrics == EnableDisable::Enable);
             }
+
+            if let Some(refresh) = leaderboard_refresh {
+                config.leaderboard_refresh = Some(refresh == EnableDisable::Enable);
+            }
+
+            match digest {
+                Some(EnableDisable::Enable) => {
+                    config.digest_channel = Some(digest_channel.unwrap_or(channel_id));
+                }
+                Some(EnableDisable::Disable) => config.digest_channel = None,
+                None => {
+                    if let Some(channel) = digest_channel {
+                        config.digest_channel = Some(channel);
+                    }
+                }
+            }
+
+            if let Some(day) = digest_day {
+                config.digest_day = Some(day as u8);
+            }
+
+            if let Some(hour) = digest_hour {
+                config.digest_hour = Some(hour as u8);
+            }
         };
 
         if let Err(err) = ctx.update_guild_config(guild_id, f).await {
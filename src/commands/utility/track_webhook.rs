@@ -0,0 +1,168 @@
+use std::{borrow::Cow, sync::Arc};
+
+use command_macros::SlashCommand;
+use eyre::{Report, Result};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+
+use crate::{
+    core::BotConfig,
+    database::TrackingWebhook,
+    util::{
+        builder::MessageBuilder, constants::GENERAL_ISSUE, interaction::InteractionCommand,
+        matcher, InteractionCommandExt,
+    },
+    Context,
+};
+
+#[derive(CommandModel, CreateCommand, SlashCommand)]
+#[command(
+    name = "trackwebhook",
+    help = "Instead of sending osu! and twitch tracking notifications myself, \
+    I can deliver them through a webhook of the channel.\n\
+    That way the notifications can have their own name and avatar.\n\
+    Should the webhook be deleted, I'll go back to sending the notifications myself."
+)]
+#[flags(AUTHORITY, ONLY_GUILDS)]
+/// Deliver tracking notifications of this channel through a webhook
+pub enum TrackWebhook<'a> {
+    #[command(name = "set")]
+    Set(TrackWebhookSet<'a>),
+    #[command(name = "remove")]
+    Remove(TrackWebhookRemove),
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "set",
+    help = "Deliver tracking notifications of this channel through a webhook.\n\
+    To create a webhook, go to the channel settings, then `Integrations > Webhooks`, \
+    create a new webhook, and click on `Copy Webhook URL`.\n\
+    The webhook must belong to this channel."
+)]
+/// Deliver tracking notifications of this channel through a webhook
+pub struct TrackWebhookSet<'a> {
+    /// Specify the webhook url
+    url: Cow<'a, str>,
+    /// Specify a name to use instead of the webhook's default name
+    name: Option<Cow<'a, str>>,
+    /// Specify an image url to use instead of the webhook's default avatar
+    avatar_url: Option<Cow<'a, str>>,
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "remove")]
+/// Stop delivering tracking notifications of this channel through a webhook
+pub struct TrackWebhookRemove;
+
+async fn slash_trackwebhook(ctx: Arc<Context>, mut command: InteractionCommand) -> Result<()> {
+    let args = TrackWebhook::from_interaction(command.input_data())?;
+
+    // The webhook url contains the token so all responses are ephemeral
+    command.defer(&ctx, true).await?;
+
+    let channel = command.channel_id;
+
+    match args {
+        TrackWebhook::Set(set) => {
+            if BotConfig::get().tokens.webhook_key.is_none() {
+                let content = "Tracking webhooks are not enabled for this bot";
+                command.error(&ctx, content).await?;
+
+                return Ok(());
+            }
+
+            let (webhook_id, token) = match matcher::get_webhook_url(&set.url) {
+                Some(tuple) => tuple,
+                None => {
+                    let content = "Failed to parse webhook url.\n\
+                        Be sure it's of the form `https://discord.com/api/webhooks/{id}/{token}`.";
+
+                    command.error(&ctx, content).await?;
+
+                    return Ok(());
+                }
+            };
+
+            if let Some(ref name) = set.name {
+                let len = name.chars().count();
+
+                if !(1..=80).contains(&len) || name.to_lowercase().contains("clyde") {
+                    let content = "The name must be between 1 and 80 characters long \
+                        and must not contain `clyde`.";
+
+                    command.error(&ctx, content).await?;
+
+                    return Ok(());
+                }
+            }
+
+            let webhook_fut = ctx.http.webhook(webhook_id).token(token).exec();
+
+            let webhook = match webhook_fut.await {
+                Ok(response) => match response.model().await {
+                    Ok(webhook) => webhook,
+                    Err(err) => {
+                        let _ = command.error(&ctx, GENERAL_ISSUE).await;
+                        let report = Report::new(err).wrap_err("failed to deserialize webhook");
+
+                        return Err(report);
+                    }
+                },
+                Err(err) => {
+                    let report = Report::new(err).wrap_err("failed to request webhook");
+                    debug!("{report:?}");
+
+                    let content = "Failed to find the webhook, is the url correct?";
+                    command.error(&ctx, content).await?;
+
+                    return Ok(());
+                }
+            };
+
+            if webhook.channel_id != channel {
+                let content = format!(
+                    "The webhook belongs to <#{}>, not to this channel",
+                    webhook.channel_id
+                );
+
+                command.error(&ctx, content).await?;
+
+                return Ok(());
+            }
+
+            let webhook = TrackingWebhook {
+                webhook_id,
+                token: token.to_owned(),
+                username: set.name.map(Cow::into_owned),
+                avatar_url: set.avatar_url.map(Cow::into_owned),
+            };
+
+            if let Err(err) = ctx.set_tracking_webhook(channel, webhook).await {
+                let _ = command.error(&ctx, GENERAL_ISSUE).await;
+
+                return Err(err);
+            }
+
+            let content =
+                "Tracking notifications of this channel will now be delivered through the webhook";
+            let builder = MessageBuilder::new().embed(content);
+            command.update(&ctx, &builder).await?;
+        }
+        TrackWebhook::Remove(_) => {
+            let content = match ctx.remove_tracking_webhook(channel).await {
+                Ok(true) => "Tracking notifications of this channel will no longer be delivered through a webhook",
+                Ok(false) => "This channel had no webhook for tracking notifications",
+                Err(err) => {
+                    let _ = command.error(&ctx, GENERAL_ISSUE).await;
+
+                    return Err(err);
+                }
+            };
+
+            let builder = MessageBuilder::new().embed(content);
+            command.update(&ctx, &builder).await?;
+        }
+    }
+
+    Ok(())
+}
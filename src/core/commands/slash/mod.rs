@@ -69,6 +69,7 @@ impl SlashCommands {
                 Mapper => MAPPER_SLASH,
                 MatchCompare => MATCHCOMPARE_SLASH,
                 MatchCost => MATCHCOST_SLASH,
+                MatchStage => MATCHSTAGE_SLASH,
                 #[cfg(feature = "matchlive")]
                 Matchlive => MATCHLIVE_SLASH,
                 Medal => MEDAL_SLASH,
@@ -84,6 +85,7 @@ impl SlashCommands {
                 Popular => POPULAR_SLASH,
                 Pp => PP_SLASH,
                 Profile => PROFILE_SLASH,
+                Progress => PROGRESS_SLASH,
                 Prune => PRUNE_SLASH,
                 Rank => RANK_SLASH,
                 Ranking => RANKING_SLASH,
@@ -91,6 +93,7 @@ impl SlashCommands {
                 Rb => RB_SLASH,
                 Rs => RS_SLASH,
                 Recent => RECENT_SLASH,
+                Replay => REPLAY_SLASH,
                 RoleAssign => ROLEASSIGN_SLASH,
                 Roll => ROLL_SLASH,
                 Search => SEARCH_SLASH,
@@ -107,6 +110,8 @@ impl SlashCommands {
                 Track => TRACK_SLASH,
                 #[cfg(feature = "twitch")]
                 TrackStream => TRACKSTREAM_SLASH,
+                #[cfg(any(feature = "osutracking", feature = "twitchtracking"))]
+                TrackWebhook => TRACKWEBHOOK_SLASH,
                 WhatIf => WHATIF_SLASH,
             }
         })
@@ -34,6 +34,8 @@ pub struct Paths {
     pub backgrounds: PathBuf,
     pub cards: PathBuf,
     pub maps: PathBuf,
+    /// Audio previews for the bg game, the audio variant is disabled if it's not specified
+    pub previews: Option<PathBuf>,
     #[cfg(feature = "server")]
     pub website: PathBuf,
 }
@@ -56,6 +58,14 @@ pub struct Tokens {
     pub twitch_client_id: String,
     #[cfg(feature = "twitch")]
     pub twitch_token: String,
+    /// Secret to verify twitch EventSub notifications.
+    /// EventSub is disabled and streams are polled if it's not specified.
+    #[cfg(all(feature = "server", feature = "twitchtracking"))]
+    pub twitch_eventsub_secret: Option<String>,
+    /// Key to encrypt webhook tokens in the database.
+    /// Tracking webhooks are disabled if it's not specified.
+    #[cfg(any(feature = "osutracking", feature = "twitchtracking"))]
+    pub webhook_key: Option<[u8; 32]>,
 }
 
 impl BotConfig {
@@ -127,11 +137,16 @@ impl BotConfig {
                 twitch_client_id: env_var("TWITCH_CLIENT_ID")?,
                 #[cfg(feature = "twitch")]
                 twitch_token: env_var("TWITCH_TOKEN")?,
+                #[cfg(all(feature = "server", feature = "twitchtracking"))]
+                twitch_eventsub_secret: env_var_opt("TWITCH_EVENTSUB_SECRET")?,
+                #[cfg(any(feature = "osutracking", feature = "twitchtracking"))]
+                webhook_key: env_var_opt("WEBHOOK_KEY")?,
             },
             paths: Paths {
                 backgrounds: env_var("BG_PATH")?,
                 cards: env_var("CARDS_REPO_PATH")?,
                 maps: env_var("MAP_PATH")?,
+                previews: env_var_opt("PREVIEW_PATH")?,
                 #[cfg(feature = "server")]
                 website: env_var("WEBSITE_PATH")?,
             },
@@ -212,11 +227,39 @@ env_kind! {
 
         Ok(array)
     },
+    [u8; 32]: s => {
+        if s.len() != 64 {
+            return Err(s);
+        }
+
+        let mut key = [0; 32];
+
+        for (byte, i) in key.iter_mut().zip((0..).step_by(2)) {
+            *byte = match s.get(i..i + 2).map(|hex| u8::from_str_radix(hex, 16)) {
+                Some(Ok(n)) => n,
+                _ => return Err(s),
+            };
+        }
+
+        Ok(key)
+    },
 }
 
 fn env_var<T: EnvKind>(name: &'static str) -> Result<T> {
     let value = env::var(name).map_err(|_| eyre!("missing env variable `{name}`"))?;
 
+    parse_env_var(name, value)
+}
+
+/// Same as [`env_var`] but a missing or empty variable is not an error
+fn env_var_opt<T: EnvKind>(name: &'static str) -> Result<Option<T>> {
+    match env::var(name) {
+        Ok(value) if !value.is_empty() => parse_env_var(name, value).map(Some),
+        _ => Ok(None),
+    }
+}
+
+fn parse_env_var<T: EnvKind>(name: &'static str, value: String) -> Result<T> {
     T::from_str(value).map_err(|value| {
         eyre!(
             "failed to parse env variable `{name}={value}`; expected {expected}",
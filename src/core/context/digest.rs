@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use eyre::{Result, WrapErr};
+use time::OffsetDateTime;
+use tokio::time::{Duration, MissedTickBehavior};
+use twilight_model::id::{
+    marker::{ChannelMarker, GuildMarker},
+    Id,
+};
+
+use crate::{
+    embeds::{EmbedData, ServerDigestEmbed},
+    util::{builder::MessageBuilder, ChannelExt},
+    Context,
+};
+
+const HOUR: Duration = Duration::from_secs(60 * 60);
+
+impl Context {
+    /// Once every hour, post the weekly digest for all guilds
+    /// whose configured day and hour match the current time
+    #[cold]
+    pub async fn digest_loop(ctx: Arc<Context>) {
+        // Align ticks to the start of an hour
+        let now = OffsetDateTime::now_utc();
+        let into_hour = now.minute() as u64 * 60 + now.second() as u64;
+        let start = tokio::time::Instant::now() + HOUR - Duration::from_secs(into_hour);
+
+        let mut interval = tokio::time::interval_at(start, HOUR);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        loop {
+            interval.tick().await;
+
+            let now = OffsetDateTime::now_utc();
+            let weekday = now.weekday().number_days_from_monday();
+            let hour = now.hour();
+
+            let guilds: Vec<_> = ctx
+                .data
+                .guilds
+                .pin()
+                .iter()
+                .filter_map(|(guild, config)| {
+                    let (channel, digest_day, digest_hour) = config.digest()?;
+
+                    (digest_day == weekday && digest_hour == hour).then_some((*guild, channel))
+                })
+                .collect();
+
+            for (guild, channel) in guilds {
+                if let Err(err) = ctx.post_digest(guild, channel, now).await {
+                    let wrap = format!("failed to post digest for guild {guild}");
+                    warn!("{:?}", err.wrap_err(wrap));
+                }
+            }
+        }
+    }
+
+    async fn post_digest(
+        &self,
+        guild: Id<GuildMarker>,
+        channel: Id<ChannelMarker>,
+        now: OffsetDateTime,
+    ) -> Result<()> {
+        let previous = self
+            .psql()
+            .get_digest_values(guild)
+            .await
+            .wrap_err("failed to get previous digest values")?;
+
+        // Don't post twice within the same week e.g. after a restart
+        let recently_posted = previous
+            .iter()
+            .any(|value| now - value.taken_at < time::Duration::days(6));
+
+        if recently_posted {
+            return Ok(());
+        }
+
+        let (guild_name, guild_icon) = self.cache.guild(guild, |g| {
+            let icon = g.icon().map(|hash| {
+                format!(
+                    "https://cdn.discordapp.com/icons/{guild}/{hash}.{}",
+                    if hash.is_animated() { "gif" } else { "webp" }
+                )
+            });
+
+            (g.name().to_owned(), icon)
+        })?;
+
+        let discord_ids: Vec<_> = self.cache.members(guild, |id| id.get() as i64);
+
+        let members = self
+            .psql()
+            .get_digest_members(&discord_ids)
+            .await
+            .wrap_err("failed to get digest members")?;
+
+        let embed = ServerDigestEmbed::new(guild_name, guild_icon, &members, &previous).build();
+        let builder = MessageBuilder::new().embed(embed);
+
+        channel
+            .create_message(self, &builder)
+            .await
+            .wrap_err("failed to create digest message")?;
+
+        self.psql()
+            .replace_digest_values(guild, &members, now)
+            .await
+            .wrap_err("failed to store digest values")?;
+
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
-use crate::Context;
+use crate::{games::hl::HlDaily, Context};
 
-use super::{BgGames, HlGames, HlRetries};
+use super::{BgGames, HlGames, HlRetries, MinesweeperGames};
 
 impl Context {
     pub fn bg_games(&self) -> &BgGames {
@@ -11,7 +11,15 @@ impl Context {
         &self.data.games.hl
     }
 
+    pub fn hl_daily(&self) -> &HlDaily {
+        &self.data.games.hl_daily
+    }
+
     pub fn hl_retries(&self) -> &HlRetries {
         &self.data.games.hl_retries
     }
+
+    pub fn minesweeper_games(&self) -> &MinesweeperGames {
+        &self.data.games.minesweeper
+    }
 }
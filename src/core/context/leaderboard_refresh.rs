@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use eyre::Report;
+use hashbrown::HashSet;
+use rosu_v2::prelude::OsuError;
+use time::OffsetDateTime;
+use tokio::time::{interval, Duration, MissedTickBehavior};
+
+use crate::{util::hasher::IntHasher, Context};
+
+/// How often the stats of linked members should be refreshed
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60 * 60 * 6);
+
+/// Delay between two user requests so the refresh
+/// does not take away too much of the osu!api ratelimit
+const REQUEST_DELAY: Duration = Duration::from_secs(2);
+
+impl Context {
+    /// Periodically refresh the osu! stats of linked members of
+    /// guilds that enabled `leaderboard_refresh` in their config
+    #[cold]
+    pub async fn leaderboard_refresh_loop(ctx: Arc<Context>) {
+        let mut interval = interval(REFRESH_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+
+            let (refreshed, total) = ctx.refresh_leaderboard_members().await;
+            info!("[LeaderboardRefresh] Refreshed {refreshed}/{total} members");
+        }
+    }
+
+    /// Returns how many users were refreshed and how many were due
+    async fn refresh_leaderboard_members(&self) -> (usize, usize) {
+        let guilds: Vec<_> = self
+            .data
+            .guilds
+            .pin()
+            .iter()
+            .filter(|(_, config)| config.leaderboard_refresh())
+            .map(|(guild, _)| *guild)
+            .collect();
+
+        let members: HashSet<_, IntHasher> = guilds
+            .into_iter()
+            .flat_map(|guild| {
+                self.cache
+                    .members::<_, _, Vec<_>>(guild, |id| id.get() as i64)
+            })
+            .collect();
+
+        if members.is_empty() {
+            return (0, 0);
+        }
+
+        let members: Vec<_> = members.into_iter().collect();
+
+        // Users that were updated recently, e.g. through a command, don't need a refresh
+        let updated_before = OffsetDateTime::now_utc() - REFRESH_INTERVAL;
+
+        let users = match self
+            .psql()
+            .get_stale_osu_users(&members, updated_before)
+            .await
+        {
+            Ok(users) => users,
+            Err(err) => {
+                warn!("{:?}", err.wrap_err("failed to get stale osu users"));
+
+                return (0, 0);
+            }
+        };
+
+        let total = users.len();
+        let mut refreshed = 0;
+
+        let mut interval = interval(REQUEST_DELAY);
+
+        for (user_id, mode) in users {
+            interval.tick().await;
+
+            let user = match self.osu().user(user_id).mode(mode).await {
+                Ok(user) => user,
+                Err(OsuError::NotFound) => {
+                    debug!("[LeaderboardRefresh] User {user_id} not found");
+
+                    continue;
+                }
+                Err(err) => {
+                    let report = Report::new(err).wrap_err("failed to request user");
+                    warn!("{report:?}");
+
+                    continue;
+                }
+            };
+
+            if let Err(err) = self.psql().upsert_osu_user(&user, mode).await {
+                warn!("{:?}", err.wrap_err("failed to upsert osu user"));
+            } else {
+                refreshed += 1;
+            }
+        }
+
+        (refreshed, total)
+    }
+}
@@ -3,15 +3,19 @@
 use std::{slice, sync::Arc};
 
 use eyre::Report;
-use hashbrown::hash_map::Entry;
+use hashbrown::{hash_map::Entry, HashMap};
 use rosu_v2::prelude::{MatchEvent, OsuError};
+use smallvec::SmallVec;
 use tokio::time::{interval, Duration};
 use twilight_model::id::{marker::ChannelMarker, Id};
 
 use crate::{
+    commands::osu::retrieve_previous,
+    database::MatchLiveTrack,
     embeds::MatchLiveEmbed,
-    matchlive::{send_match_messages, Channel, MatchEntry, MatchTrackResult, TrackedMatch},
-    util::ChannelExt,
+    matchlive::{
+        scoreboard_embed, send_match_messages, Channel, MatchEntry, MatchTrackResult, TrackedMatch,
+    },
 };
 
 use super::Context;
@@ -39,6 +43,7 @@ impl Context {
         &self,
         channel: Id<ChannelMarker>,
         match_id: u32,
+        warmups: usize,
     ) -> MatchTrackResult {
         let mut match_live = self.data.matchlive.inner.lock().await;
 
@@ -65,8 +70,8 @@ impl Context {
 
                 let embeds = &entry.tracked.embeds;
 
-                let channel = match send_match_messages(self, channel, embeds).await {
-                    Ok(msg) => Channel::new(channel, msg),
+                let mut channel = match send_match_messages(self, channel, embeds).await {
+                    Ok(msg) => Channel::new(channel, msg, warmups),
                     Err(report) => {
                         error!("{report:?}");
 
@@ -74,19 +79,37 @@ impl Context {
                     }
                 };
 
-                let id = channel.id;
+                let track = MatchLiveTrack {
+                    match_id,
+                    channel: channel.id,
+                    msg: channel.msg_id,
+                    last_event_id: entry.tracked.osu_match.latest_event_id,
+                    warmups,
+                    scoreboard: None,
+                };
+
+                self.store_match_live_track(&track).await;
+                self.update_scoreboard(match_id, &entry.tracked, &mut channel)
+                    .await;
+
                 entry.channels.push(channel);
-                *match_live.channel_count.entry(id).or_insert(0) += 1;
+                *match_live.channel_count.entry(track.channel).or_insert(0) += 1;
 
                 MatchTrackResult::Added
             }
             // The match is not yet tracked -> request and store it
             Entry::Vacant(e) => match self.osu().osu_match(match_id).await {
-                Ok(osu_match) => {
+                Ok(mut osu_match) => {
+                    // Previous games are required for the scoreboard
+                    if let Err(err) = retrieve_previous(&mut osu_match, self.osu()).await {
+                        let report = Report::new(err).wrap_err("failed to get previous events");
+                        warn!("{report:?}");
+                    }
+
                     let embeds = MatchLiveEmbed::new(&osu_match);
 
-                    let channel = match send_match_messages(self, channel, &embeds).await {
-                        Ok(msg) => Channel::new(channel, msg),
+                    let mut channel = match send_match_messages(self, channel, &embeds).await {
+                        Ok(msg) => Channel::new(channel, msg, warmups),
                         Err(report) => {
                             error!("{report:?}");
 
@@ -96,10 +119,23 @@ impl Context {
 
                     // Only add to tracking if it's not already disbanded
                     if !matches!(osu_match.events.last(), Some(MatchEvent::Disbanded { .. })) {
+                        let track = MatchLiveTrack {
+                            match_id,
+                            channel: channel.id,
+                            msg: channel.msg_id,
+                            last_event_id: osu_match.latest_event_id,
+                            warmups,
+                            scoreboard: None,
+                        };
+
+                        self.store_match_live_track(&track).await;
+
                         let tracked_match = TrackedMatch::new(osu_match, embeds);
-                        let id = channel.id;
+                        self.update_scoreboard(match_id, &tracked_match, &mut channel)
+                            .await;
+
                         e.insert(MatchEntry::new(tracked_match, channel));
-                        *match_live.channel_count.entry(id).or_insert(0) += 1;
+                        *match_live.channel_count.entry(track.channel).or_insert(0) += 1;
                     }
 
                     MatchTrackResult::Added
@@ -140,6 +176,11 @@ impl Context {
                     .entry(channel)
                     .and_modify(|count| *count -= 1);
 
+                if let Err(err) = self.psql().remove_match_live_track(match_id, channel).await {
+                    let wrap = format!("failed to remove match live track of match {match_id}");
+                    warn!("{:?}", err.wrap_err(wrap));
+                }
+
                 return true;
             }
         }
@@ -149,6 +190,8 @@ impl Context {
 
     /// Returns how many channels tracked the match before it ended
     async fn remove_all_match_tracks(&self, match_id: u32) -> usize {
+        self.forget_match_live_tracks(match_id).await;
+
         let mut match_live = self.data.matchlive.inner.lock().await;
 
         if let Some(entry) = match_live.match_channels.remove(&match_id) {
@@ -166,6 +209,8 @@ impl Context {
     }
 
     pub async fn match_live_loop(ctx: Arc<Context>) {
+        ctx.restore_match_live_tracks().await;
+
         // Update all matches every 10 seconds
         let mut interval = interval(Duration::from_secs(10));
         interval.tick().await;
@@ -181,7 +226,7 @@ impl Context {
                 let mut match_live = ctx.data.matchlive.inner.lock().await;
 
                 // For every match that is being tracked
-                for entry in match_live.match_channels.values_mut() {
+                for (&match_id, entry) in match_live.match_channels.iter_mut() {
                     let mut tracked_match = &mut entry.tracked;
 
                     // Request an update
@@ -206,14 +251,24 @@ impl Context {
                         remove.push(next_match.match_id);
                     }
 
+                    let refresh_scoreboard =
+                        tracked_match.update_games(&next_match) || next_match.end_time.is_some();
+
+                    let new_event_id = (next_match.latest_event_id
+                        != tracked_match.osu_match.latest_event_id)
+                        .then_some(next_match.latest_event_id);
+
                     tracked_match.osu_match = next_match;
 
+                    // Whether all channels received the update
+                    let mut delivered = true;
+
                     // If there was an update for the last embed
                     if update {
                         let data = tracked_match.embeds.last().unwrap();
 
                         // For every channel that's tracking the match
-                        for Channel { id, msg_id } in entry.channels.iter() {
+                        for Channel { id, msg_id, .. } in entry.channels.iter() {
                             let embed = Some(data.as_embed());
 
                             // Update the last message
@@ -228,6 +283,7 @@ impl Context {
                                     let report =
                                         Report::new(err).wrap_err("failed to build msg update");
                                     warn!("{report:?}");
+                                    delivered = false;
 
                                     continue;
                                 }
@@ -236,26 +292,57 @@ impl Context {
                             if let Err(err) = update_fut.await {
                                 let report = Report::new(err).wrap_err("failed to update msg");
                                 warn!("{report:?}");
+                                delivered = false;
                             }
                         }
                     }
 
                     // For all new embeds, send them to all channels
                     if let Some(embeds) = new_embeds {
-                        for Channel { id, msg_id } in entry.channels.iter_mut() {
+                        for Channel { id, msg_id, .. } in entry.channels.iter_mut() {
                             match send_match_messages(&ctx, *id, &embeds).await {
-                                Ok(msg) => *msg_id = msg,
+                                Ok(msg) => {
+                                    *msg_id = msg;
+                                    let update_fut =
+                                        ctx.psql().update_match_live_msg(match_id, *id, msg);
+
+                                    if let Err(err) = update_fut.await {
+                                        let wrap = format!(
+                                            "failed to update last msg of match {match_id}"
+                                        );
+                                        warn!("{:?}", err.wrap_err(wrap));
+                                    }
+                                }
                                 Err(report) => {
                                     let report = report.wrap_err(format!(
                                         "failed to send last msg in channel {id}"
                                     ));
-                                    error!("{report:?}")
+                                    error!("{report:?}");
+                                    delivered = false;
                                 }
                             }
                         }
 
                         tracked_match.embeds.extend(embeds);
                     }
+
+                    // Only store the event once it reached all channels so that
+                    // undelivered events are sent again after a restart
+                    if let Some(event_id) = new_event_id.filter(|_| delivered) {
+                        let update_fut = ctx.psql().update_match_live_event(match_id, event_id);
+
+                        if let Err(err) = update_fut.await {
+                            let wrap = format!("failed to update last event of match {match_id}");
+                            warn!("{:?}", err.wrap_err(wrap));
+                        }
+                    }
+
+                    if refresh_scoreboard {
+                        for channel in entry.channels.iter_mut() {
+                            ctx.update_scoreboard(match_id, &entry.tracked, channel)
+                                .await;
+                        }
+                    }
                 }
             }
 
@@ -268,24 +355,207 @@ impl Context {
         }
     }
 
-    pub async fn notify_match_live_shutdown(&self) -> usize {
+    /// Stops all match tracking until the next startup.
+    ///
+    /// Returns in how many channels matches were tracked.
+    pub async fn pause_match_live(&self) -> usize {
         let mut match_live = self.data.matchlive.inner.lock().await;
         match_live.match_channels.clear();
 
-        let content = "I'm about to reboot so the match tracking will be aborted, \
-            you can restart it in just a moment...";
+        match_live
+            .channel_count
+            .drain()
+            .filter(|(_, count)| *count > 0)
+            .count()
+    }
+
+    /// Rebuild the tracked matches from the database so that the
+    /// updates continue from the last processed event.
+    #[cold]
+    async fn restore_match_live_tracks(&self) {
+        let tracks = match self.psql().get_match_live_tracks().await {
+            Ok(tracks) => tracks,
+            Err(err) => {
+                warn!("{:?}", err.wrap_err("failed to get match live tracks"));
+
+                return;
+            }
+        };
+
+        let mut matches: HashMap<u32, (u64, SmallVec<[Channel; 2]>)> = HashMap::new();
+
+        for track in tracks {
+            let (last_event_id, channels) = matches
+                .entry(track.match_id)
+                .or_insert_with(|| (track.last_event_id, SmallVec::new()));
+
+            *last_event_id = (*last_event_id).min(track.last_event_id);
+            let mut channel = Channel::new(track.channel, track.msg, track.warmups);
+            channel.scoreboard = track.scoreboard;
+            channels.push(channel);
+        }
+
+        let mut match_live = self.data.matchlive.inner.lock().await;
+        let mut restored = 0;
+
+        for (match_id, (last_event_id, channels)) in matches {
+            let mut osu_match = match self.osu().osu_match(match_id).await {
+                Ok(osu_match) => osu_match,
+                Err(OsuError::NotFound) => {
+                    self.forget_match_live_tracks(match_id).await;
+
+                    continue;
+                }
+                Err(err) => {
+                    let wrap = format!("failed to request match {match_id} to restore");
+                    warn!("{:?}", Report::new(err).wrap_err(wrap));
+
+                    continue;
+                }
+            };
+
+            if let Err(err) = retrieve_previous(&mut osu_match, self.osu()).await {
+                let wrap = format!("failed to get previous events of match {match_id}");
+                warn!("{:?}", Report::new(err).wrap_err(wrap));
+            }
+
+            let has_last_event = osu_match
+                .events
+                .iter()
+                .any(|event| event.event_id() == last_event_id);
+
+            if has_last_event {
+                // Rebuild the state as of the last processed event so that
+                // the loop sends out all events that happened in the meanwhile
+                osu_match
+                    .events
+                    .retain(|event| event.event_id() <= last_event_id);
+                osu_match.latest_event_id = last_event_id;
+            } else {
+                // The last processed event is no longer available so
+                // continue from the current state instead of dropping the match
+                warn!(
+                    "Last event {last_event_id} of match {match_id} not found, \
+                    resuming from event {}",
+                    osu_match.latest_event_id
+                );
+            }
+
+            let embeds = MatchLiveEmbed::new(&osu_match);
 
-        let mut notified = 0;
+            if embeds.is_empty() {
+                self.forget_match_live_tracks(match_id).await;
 
-        for (channel, count) in match_live.channel_count.iter() {
-            if *count > 0 {
-                let _ = channel.plain_message(self, content).await;
-                notified += 1;
+                continue;
             }
+
+            for channel in channels.iter() {
+                *match_live.channel_count.entry(channel.id).or_insert(0) += 1;
+            }
+
+            let entry = MatchEntry {
+                tracked: TrackedMatch::new(osu_match, embeds),
+                channels,
+            };
+
+            match_live.match_channels.insert(match_id, entry);
+            restored += 1;
         }
 
-        match_live.channel_count.clear();
+        info!("Restored {restored} live-tracked matches");
+    }
 
-        notified
+    /// Create or edit the scoreboard message of the channel
+    async fn update_scoreboard(
+        &self,
+        match_id: u32,
+        tracked: &TrackedMatch,
+        channel: &mut Channel,
+    ) {
+        let embed = match scoreboard_embed(tracked, channel.warmups) {
+            Some(embed) => embed,
+            None => return,
+        };
+
+        let embeds = slice::from_ref(&embed);
+
+        if let Some(msg) = channel.scoreboard {
+            let update_fut = match self
+                .http
+                .update_message(channel.id, msg)
+                .embeds(Some(embeds))
+            {
+                Ok(update_fut) => update_fut.exec(),
+                Err(err) => {
+                    let report = Report::new(err).wrap_err("failed to build scoreboard update");
+                    warn!("{report:?}");
+
+                    return;
+                }
+            };
+
+            if let Err(err) = update_fut.await {
+                let report = Report::new(err).wrap_err("failed to update scoreboard");
+                warn!("{report:?}");
+            }
+
+            return;
+        }
+
+        let create_fut = match self.http.create_message(channel.id).embeds(embeds) {
+            Ok(create_fut) => create_fut.exec(),
+            Err(err) => {
+                let report = Report::new(err).wrap_err("failed to build scoreboard msg");
+                warn!("{report:?}");
+
+                return;
+            }
+        };
+
+        let msg = match create_fut.await {
+            Ok(response) => match response.model().await {
+                Ok(msg) => msg.id,
+                Err(err) => {
+                    let report = Report::new(err).wrap_err("failed to deserialize scoreboard msg");
+                    warn!("{report:?}");
+
+                    return;
+                }
+            },
+            Err(err) => {
+                let report = Report::new(err).wrap_err("failed to send scoreboard msg");
+                warn!("{report:?}");
+
+                return;
+            }
+        };
+
+        channel.scoreboard = Some(msg);
+        let update_fut = self
+            .psql()
+            .update_match_live_scoreboard(match_id, channel.id, msg);
+
+        if let Err(err) = update_fut.await {
+            let wrap = format!("failed to store scoreboard of match {match_id}");
+            warn!("{:?}", err.wrap_err(wrap));
+        }
+    }
+
+    async fn store_match_live_track(&self, track: &MatchLiveTrack) {
+        if let Err(err) = self.psql().insert_match_live_track(track).await {
+            let wrap = format!(
+                "failed to store match live track of match {}",
+                track.match_id
+            );
+            warn!("{:?}", err.wrap_err(wrap));
+        }
+    }
+
+    /// Remove the stored tracks of the match from the database
+    async fn forget_match_live_tracks(&self, match_id: u32) {
+        if let Err(err) = self.psql().remove_match_live_tracks(match_id).await {
+            let wrap = format!("failed to remove match live tracks of match {match_id}");
+            warn!("{:?}", err.wrap_err(wrap));
+        }
     }
 }
@@ -26,28 +26,37 @@ use twilight_standby::Standby;
 use crate::{
     core::BotConfig,
     custom_client::CustomClient,
-    database::{Database, GuildConfig},
+    database::{Database, GuildConfig, TrackedStreams},
     games::{
         bg::GameState as BgGameState,
-        hl::{retry::RetryState, GameState as HlGameState},
+        hl::{retry::RetryState, GameState as HlGameState, HlDaily},
+        minesweeper::GameState as MinesweeperGameState,
     },
     pagination::Pagination,
     util::{hasher::IntHasher, CountryCode},
 };
 
+use self::recent_maps::RecentMaps;
+
 use super::{buckets::Buckets, cluster::build_cluster, BotStats, Cache, RedisCache};
 
 mod background_loop;
 mod configs;
 mod countries;
+mod digest;
 mod games;
+mod leaderboard_refresh;
 mod map_collect;
 mod matchlive;
 mod messages;
+mod recent_maps;
 mod role_assign;
 mod shutdown;
 mod twitch;
 
+#[cfg(any(feature = "osutracking", feature = "twitchtracking"))]
+mod webhooks;
+
 pub type Redis = Pool<RedisConnectionManager>;
 pub type AssignRoles = SmallVec<[u64; 1]>;
 
@@ -243,9 +252,14 @@ struct ContextData {
     msgs_to_process: Mutex<HashSet<Id<MessageMarker>, IntHasher>>,
     #[cfg(feature = "osutracking")]
     osu_tracking: crate::tracking::OsuTracking,
+    recent_maps: RecentMaps,
     role_assigns: FlurryMap<(u64, u64), AssignRoles>, // read-heavy
     snipe_countries: FlurryMap<CountryCode, String>,  // read-heavy
-    tracked_streams: FlurryMap<u64, Vec<u64>, IntHasher>, // read-heavy
+    tracked_streams: TrackedStreams,                  // read-heavy
+    #[cfg(any(feature = "osutracking", feature = "twitchtracking"))]
+    tracking_webhooks: crate::database::TrackingWebhooks, // read-heavy
+    #[cfg(all(feature = "server", feature = "twitchtracking"))]
+    twitch_eventsub: crate::tracking::TwitchEventSub,
 }
 
 impl ContextData {
@@ -262,6 +276,7 @@ impl ContextData {
             osu_tracking: crate::tracking::OsuTracking::new(psql)
                 .await
                 .wrap_err("failed to create osu tracking")?,
+            recent_maps: RecentMaps::default(),
             role_assigns: psql
                 .get_role_assigns()
                 .await
@@ -274,6 +289,13 @@ impl ContextData {
                 .get_stream_tracks()
                 .await
                 .wrap_err("failed to get stream tracks")?,
+            #[cfg(any(feature = "osutracking", feature = "twitchtracking"))]
+            tracking_webhooks: psql
+                .get_tracking_webhooks()
+                .await
+                .wrap_err("failed to get tracking webhooks")?,
+            #[cfg(all(feature = "server", feature = "twitchtracking"))]
+            twitch_eventsub: crate::tracking::TwitchEventSub::from_config(),
         })
     }
 }
@@ -281,7 +303,9 @@ impl ContextData {
 struct Games {
     bg: BgGames,
     hl: HlGames,
+    hl_daily: HlDaily,
     hl_retries: HlRetries,
+    minesweeper: MinesweeperGames,
 }
 
 impl Games {
@@ -289,7 +313,9 @@ impl Games {
         Self {
             bg: BgGames::with_shard_amount_and_hasher(16, IntHasher),
             hl: HlGames::with_shard_amount_and_hasher(16, IntHasher),
+            hl_daily: HlDaily::default(),
             hl_retries: HlRetries::with_shard_amount_and_hasher(4, IntHasher),
+            minesweeper: MinesweeperGames::with_shard_amount_and_hasher(4, IntHasher),
         }
     }
 }
@@ -297,3 +323,4 @@ impl Games {
 type BgGames = TokioRwLockMap<Id<ChannelMarker>, BgGameState, IntHasher>;
 type HlGames = TokioMutexMap<Id<UserMarker>, HlGameState, IntHasher>;
 type HlRetries = StdMutexMap<Id<MessageMarker>, RetryState, IntHasher>;
+type MinesweeperGames = TokioMutexMap<Id<UserMarker>, MinesweeperGameState, IntHasher>;
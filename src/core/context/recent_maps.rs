@@ -0,0 +1,334 @@
+use std::collections::VecDeque;
+
+use eyre::{Result, WrapErr};
+use hashbrown::HashMap;
+use parking_lot::Mutex;
+use twilight_model::id::{
+    marker::{ChannelMarker, MessageMarker},
+    Id,
+};
+
+use crate::{
+    util::{hasher::IntHasher, osu::MapIdType},
+    Context,
+};
+
+/// Amount of maps that are remembered per channel.
+/// Same as the amount of messages that are checked in the channel history.
+const RECENT_MAPS_PER_CHANNEL: usize = 50;
+
+/// Amount of channels whose maps are remembered.
+/// Once reached, the channel that was updated the longest time ago is forgotten.
+const MAX_CHANNELS: usize = 10_000;
+
+/// Maps that were recently shown in channels
+#[derive(Default)]
+pub struct RecentMaps {
+    inner: Mutex<RecentMapsInner>,
+}
+
+#[derive(Default)]
+struct RecentMapsInner {
+    channels: HashMap<Id<ChannelMarker>, ChannelMaps, IntHasher>,
+    /// Incremented on every insert to keep track of when channels were updated
+    tick: u64,
+}
+
+struct ChannelMaps {
+    last_update: u64,
+    /// Latest first
+    entries: VecDeque<RecentMap>,
+}
+
+#[derive(Copy, Clone)]
+struct RecentMap {
+    msg: Id<MessageMarker>,
+    /// Same priority as [`MapIdType::from_msg`]
+    map: Option<MapIdType>,
+    /// Same priority as [`MapIdType::map_from_msg`]
+    map_id: Option<u32>,
+}
+
+impl RecentMaps {
+    /// Remember the maps of a message.
+    ///
+    /// If the message was already stored, e.g. because it has been edited,
+    /// its entry is updated in place so that the order matches the channel history.
+    fn insert(
+        &self,
+        channel: Id<ChannelMarker>,
+        msg: Id<MessageMarker>,
+        map: Option<MapIdType>,
+        map_id: Option<u32>,
+    ) {
+        if map.is_none() && map_id.is_none() {
+            return;
+        }
+
+        let mut inner = self.inner.lock();
+        inner.tick += 1;
+        let tick = inner.tick;
+
+        if inner.channels.len() >= MAX_CHANNELS && !inner.channels.contains_key(&channel) {
+            let oldest = inner
+                .channels
+                .iter()
+                .min_by_key(|(_, maps)| maps.last_update)
+                .map(|(channel, _)| *channel);
+
+            if let Some(oldest) = oldest {
+                inner.channels.remove(&oldest);
+            }
+        }
+
+        let maps = inner
+            .channels
+            .entry(channel)
+            .or_insert_with(|| ChannelMaps {
+                last_update: tick,
+                entries: VecDeque::with_capacity(RECENT_MAPS_PER_CHANNEL),
+            });
+
+        maps.last_update = tick;
+
+        if let Some(entry) = maps.entries.iter_mut().find(|entry| entry.msg == msg) {
+            entry.map = map;
+            entry.map_id = map_id;
+
+            return;
+        }
+
+        if maps.entries.len() == RECENT_MAPS_PER_CHANNEL {
+            maps.entries.pop_back();
+        }
+
+        maps.entries.push_front(RecentMap { msg, map, map_id });
+    }
+
+    /// Forget the maps of a deleted message
+    fn remove(&self, channel: Id<ChannelMarker>, msg: Id<MessageMarker>) {
+        let mut inner = self.inner.lock();
+
+        let is_empty = match inner.channels.get_mut(&channel) {
+            Some(maps) => {
+                maps.entries.retain(|entry| entry.msg != msg);
+
+                maps.entries.is_empty()
+            }
+            None => return,
+        };
+
+        if is_empty {
+            inner.channels.remove(&channel);
+        }
+    }
+
+    fn all(&self, channel: Id<ChannelMarker>) -> Vec<MapIdType> {
+        self.inner
+            .lock()
+            .channels
+            .get(&channel)
+            .map_or_else(Vec::new, |maps| {
+                maps.entries.iter().filter_map(|entry| entry.map).collect()
+            })
+    }
+
+    fn get(&self, channel: Id<ChannelMarker>, idx: usize) -> Option<MapIdType> {
+        self.inner
+            .lock()
+            .channels
+            .get(&channel)
+            .and_then(|maps| maps.entries.iter().filter_map(|entry| entry.map).nth(idx))
+    }
+
+    fn get_map_id(&self, channel: Id<ChannelMarker>, idx: usize) -> Option<u32> {
+        self.inner.lock().channels.get(&channel).and_then(|maps| {
+            maps.entries
+                .iter()
+                .filter_map(|entry| entry.map_id)
+                .nth(idx)
+        })
+    }
+}
+
+impl Context {
+    /// Remember the maps that were shown in a message of the channel.
+    ///
+    /// `map` and `map_id` should be retrieved the same way as
+    /// [`MapIdType::from_msg`] and [`MapIdType::map_from_msg`] do.
+    pub fn store_recent_map(
+        &self,
+        channel: Id<ChannelMarker>,
+        msg: Id<MessageMarker>,
+        map: Option<MapIdType>,
+        map_id: Option<u32>,
+    ) {
+        self.data.recent_maps.insert(channel, msg, map, map_id);
+    }
+
+    /// Forget the maps of a deleted message
+    pub fn remove_recent_map(&self, channel: Id<ChannelMarker>, msg: Id<MessageMarker>) {
+        self.data.recent_maps.remove(channel, msg);
+    }
+
+    /// Maps that were recently shown in the channel, latest first
+    pub fn recent_maps(&self, channel: Id<ChannelMarker>) -> Vec<MapIdType> {
+        self.data.recent_maps.all(channel)
+    }
+
+    /// The `idx`'th latest map or mapset that was shown in the channel.
+    ///
+    /// If not enough maps were remembered, e.g. because the bot restarted
+    /// recently, the channel history is checked instead.
+    pub async fn recent_map(
+        &self,
+        channel: Id<ChannelMarker>,
+        idx: usize,
+    ) -> Result<Option<MapIdType>> {
+        let stored = self.data.recent_maps.get(channel, idx);
+
+        if stored.is_some() {
+            return Ok(stored);
+        }
+
+        let msgs = self
+            .retrieve_channel_history(channel)
+            .await
+            .wrap_err("failed to retrieve channel history")?;
+
+        Ok(MapIdType::from_msgs(&msgs, idx))
+    }
+
+    /// The `idx`'th latest map id that was shown in the channel.
+    ///
+    /// If not enough maps were remembered, e.g. because the bot restarted
+    /// recently, the channel history is checked instead.
+    pub async fn recent_map_id(
+        &self,
+        channel: Id<ChannelMarker>,
+        idx: usize,
+    ) -> Result<Option<u32>> {
+        let stored = self.data.recent_maps.get_map_id(channel, idx);
+
+        if stored.is_some() {
+            return Ok(stored);
+        }
+
+        let msgs = self
+            .retrieve_channel_history(channel)
+            .await
+            .wrap_err("failed to retrieve channel history")?;
+
+        Ok(MapIdType::map_from_msgs(&msgs, idx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(id: u64) -> Id<ChannelMarker> {
+        Id::new(id)
+    }
+
+    fn msg(id: u64) -> Id<MessageMarker> {
+        Id::new(id)
+    }
+
+    #[test]
+    fn test_latest_first() {
+        let maps = RecentMaps::default();
+
+        for id in 1..=3 {
+            maps.insert(
+                channel(1),
+                msg(id),
+                Some(MapIdType::Map(id as u32)),
+                Some(id as u32),
+            );
+        }
+
+        let expected = [MapIdType::Map(3), MapIdType::Map(2), MapIdType::Map(1)];
+        assert_eq!(maps.all(channel(1)), expected);
+        assert_eq!(maps.get(channel(1), 1), Some(MapIdType::Map(2)));
+        assert_eq!(maps.get(channel(1), 3), None);
+        assert!(maps.all(channel(2)).is_empty());
+    }
+
+    #[test]
+    fn test_capacity() {
+        let maps = RecentMaps::default();
+        let count = RECENT_MAPS_PER_CHANNEL as u64 + 10;
+
+        for id in 1..=count {
+            maps.insert(
+                channel(1),
+                msg(id),
+                Some(MapIdType::Map(id as u32)),
+                Some(id as u32),
+            );
+        }
+
+        let all = maps.all(channel(1));
+        assert_eq!(all.len(), RECENT_MAPS_PER_CHANNEL);
+        assert_eq!(all[0], MapIdType::Map(count as u32));
+        assert_eq!(all[RECENT_MAPS_PER_CHANNEL - 1], MapIdType::Map(11));
+    }
+
+    #[test]
+    fn test_edited_message() {
+        let maps = RecentMaps::default();
+        maps.insert(channel(1), msg(1), Some(MapIdType::Map(1)), Some(1));
+        maps.insert(channel(1), msg(2), Some(MapIdType::Map(2)), Some(2));
+        maps.insert(channel(1), msg(1), Some(MapIdType::Map(3)), Some(3));
+
+        assert_eq!(maps.all(channel(1)), [MapIdType::Map(2), MapIdType::Map(3)]);
+    }
+
+    #[test]
+    fn test_deleted_message() {
+        let maps = RecentMaps::default();
+        maps.insert(channel(1), msg(1), Some(MapIdType::Map(1)), Some(1));
+        maps.insert(channel(1), msg(2), Some(MapIdType::Map(2)), Some(2));
+
+        maps.remove(channel(1), msg(2));
+        assert_eq!(maps.all(channel(1)), [MapIdType::Map(1)]);
+
+        maps.remove(channel(1), msg(1));
+        assert!(maps.inner.lock().channels.is_empty());
+    }
+
+    #[test]
+    fn test_map_id_priority() {
+        let maps = RecentMaps::default();
+
+        // Mapset link in the content and a map in the embed
+        maps.insert(channel(1), msg(1), Some(MapIdType::Set(10)), Some(20));
+        maps.insert(channel(1), msg(2), Some(MapIdType::Set(30)), None);
+
+        assert_eq!(maps.get(channel(1), 0), Some(MapIdType::Set(30)));
+        assert_eq!(maps.get(channel(1), 1), Some(MapIdType::Set(10)));
+        assert_eq!(maps.get_map_id(channel(1), 0), Some(20));
+        assert_eq!(maps.get_map_id(channel(1), 1), None);
+    }
+
+    #[test]
+    fn test_channel_limit() {
+        let maps = RecentMaps::default();
+
+        for id in 1..=MAX_CHANNELS as u64 {
+            maps.insert(channel(id), msg(id), Some(MapIdType::Map(1)), Some(1));
+        }
+
+        let new = MAX_CHANNELS as u64 + 1;
+
+        // Channel 1 was updated the longest time ago but is refreshed now
+        maps.insert(channel(1), msg(new), Some(MapIdType::Map(2)), Some(2));
+        maps.insert(channel(new), msg(new), Some(MapIdType::Map(3)), Some(3));
+
+        assert_eq!(maps.inner.lock().channels.len(), MAX_CHANNELS);
+        assert_eq!(maps.get(channel(1), 0), Some(MapIdType::Map(2)));
+        assert_eq!(maps.get(channel(new), 0), Some(MapIdType::Map(3)));
+        assert!(maps.all(channel(2)).is_empty());
+    }
+}
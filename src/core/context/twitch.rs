@@ -1,28 +1,37 @@
-use std::iter;
-
-use crate::Context;
+use crate::{
+    database::{StreamTrack, StreamTrackSettings},
+    Context,
+};
 
 use twilight_model::id::{marker::ChannelMarker, Id};
 
 impl Context {
-    pub fn add_tracking(&self, twitch_id: u64, channel_id: u64) {
+    /// Add the track or overwrite the settings if the channel already tracks the stream
+    pub fn add_tracking(&self, twitch_id: u64, track: StreamTrack) {
         let streams = &self.data.tracked_streams;
         let guard = streams.guard();
+        let mut track = Some(track);
+
+        streams.compute_if_present(
+            &twitch_id,
+            |_, tracks| {
+                let track = track.take()?;
+
+                let mut tracks: Vec<_> = tracks
+                    .iter()
+                    .filter(|tracked| tracked.channel != track.channel)
+                    .cloned()
+                    .collect();
 
-        let missing = streams
-            .compute_if_present(
-                &twitch_id,
-                |_, channels| {
-                    let channels = channels.iter().copied().chain(iter::once(channel_id));
+                tracks.push(track);
 
-                    Some(channels.collect())
-                },
-                &guard,
-            )
-            .is_none();
+                Some(tracks)
+            },
+            &guard,
+        );
 
-        if missing {
-            streams.insert(twitch_id, vec![channel_id], &guard);
+        if let Some(track) = track {
+            streams.insert(twitch_id, vec![track], &guard);
         }
     }
 
@@ -30,33 +39,41 @@ impl Context {
         self.data
             .tracked_streams
             .pin()
-            .compute_if_present(&twitch_id, |_, channels| {
-                let channels = channels.iter().copied().filter(|&id| id != channel_id);
+            .compute_if_present(&twitch_id, |_, tracks| {
+                let tracks = tracks
+                    .iter()
+                    .filter(|track| track.channel.get() != channel_id)
+                    .cloned();
 
-                Some(channels.collect())
+                Some(tracks.collect())
             });
     }
 
+    #[cfg(all(feature = "server", feature = "twitchtracking"))]
+    pub fn twitch_eventsub(&self) -> &crate::tracking::TwitchEventSub {
+        &self.data.twitch_eventsub
+    }
+
     pub fn tracked_users(&self) -> Vec<u64> {
         self.data.tracked_streams.pin().keys().copied().collect()
     }
 
-    pub fn tracked_channels_for(&self, twitch_id: u64) -> Option<Vec<Id<ChannelMarker>>> {
-        self.data
-            .tracked_streams
-            .pin()
-            .get(&twitch_id)
-            .map(|channels| channels.iter().copied().map(Id::new).collect())
+    pub fn tracked_channels_for(&self, twitch_id: u64) -> Option<Vec<StreamTrack>> {
+        self.data.tracked_streams.pin().get(&twitch_id).cloned()
     }
 
-    pub fn tracked_users_in(&self, channel: Id<ChannelMarker>) -> Vec<u64> {
-        let channel = channel.get();
-
+    /// Twitch user ids that are tracked in the channel alongside their settings
+    pub fn tracked_users_in(&self, channel: Id<ChannelMarker>) -> Vec<(u64, StreamTrackSettings)> {
         self.data
             .tracked_streams
             .pin()
             .iter()
-            .filter_map(|(user, channels)| channels.contains(&channel).then_some(*user))
+            .filter_map(|(user, tracks)| {
+                tracks
+                    .iter()
+                    .find(|track| track.channel == channel)
+                    .map(|track| (*user, track.settings.clone()))
+            })
             .collect()
     }
 }
@@ -0,0 +1,37 @@
+use eyre::{Result, WrapErr};
+use twilight_model::id::{marker::ChannelMarker, Id};
+
+use crate::{database::TrackingWebhook, Context};
+
+impl Context {
+    /// Returns the webhook through which tracking notifications
+    /// should be delivered to the channel, if any
+    pub fn tracking_webhook(&self, channel: Id<ChannelMarker>) -> Option<TrackingWebhook> {
+        self.data.tracking_webhooks.pin().get(&channel).cloned()
+    }
+
+    pub async fn set_tracking_webhook(
+        &self,
+        channel: Id<ChannelMarker>,
+        webhook: TrackingWebhook,
+    ) -> Result<()> {
+        self.psql()
+            .upsert_tracking_webhook(channel, &webhook)
+            .await
+            .wrap_err("failed to upsert tracking webhook")?;
+
+        self.data.tracking_webhooks.pin().insert(channel, webhook);
+
+        Ok(())
+    }
+
+    /// Returns whether the channel had a webhook
+    pub async fn remove_tracking_webhook(&self, channel: Id<ChannelMarker>) -> Result<bool> {
+        self.data.tracking_webhooks.pin().remove(&channel);
+
+        self.psql()
+            .remove_tracking_webhook(channel)
+            .await
+            .wrap_err("failed to remove tracking webhook")
+    }
+}
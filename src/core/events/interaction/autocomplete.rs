@@ -3,7 +3,7 @@ use std::{mem, sync::Arc};
 use crate::{
     commands::{
         help::slash_help,
-        osu::{slash_badges, slash_medal},
+        osu::{handle_map_autocomplete, handle_username_autocomplete, slash_badges, slash_medal},
     },
     core::{events::EventKind, Context},
     util::interaction::InteractionCommand,
@@ -16,9 +16,14 @@ pub async fn handle_autocomplete(ctx: Arc<Context>, mut command: InteractionComm
 
     let res = match name.as_str() {
         "help" => slash_help(ctx, command).await,
-        "badges" => slash_badges(ctx, command).await,
-        "medal" => slash_medal(ctx, command).await,
-        _ => return error!("Unknown autocomplete command `{name}`"),
+        "badges" if is_subcommand(&command, "query") => slash_badges(ctx, command).await,
+        "medal" if is_subcommand(&command, "info") => slash_medal(ctx, command).await,
+        // All other commands only autocomplete their osu! username or map option
+        _ => match command.focused_option() {
+            Some(("name", input)) => handle_username_autocomplete(&ctx, &command, input).await,
+            Some(("map", input)) => handle_map_autocomplete(&ctx, &command, input).await,
+            _ => return error!("Unknown autocomplete command `{name}`"),
+        },
     };
 
     if let Err(err) = res {
@@ -26,3 +31,7 @@ pub async fn handle_autocomplete(ctx: Arc<Context>, mut command: InteractionComm
         error!("{:?}", err.wrap_err(wrap));
     }
 }
+
+fn is_subcommand(command: &InteractionCommand, name: &str) -> bool {
+    matches!(command.data.options.first(), Some(option) if option.name == name)
+}
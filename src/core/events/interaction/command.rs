@@ -24,8 +24,21 @@ pub async fn handle_command(ctx: Arc<Context>, mut command: InteractionCommand)
         None => return error!("unknown slash command `{name}`"),
     };
 
-    match process_command(ctx, command, slash).await {
-        Ok(ProcessResult::Success) => info!("Processed slash command `{name}`"),
+    // Used usernames are suggested first when autocompleting in the same guild
+    let used_name = command
+        .guild_id
+        .zip(command.string_option("name").map(str::to_owned));
+
+    match process_command(Arc::clone(&ctx), command, slash).await {
+        Ok(ProcessResult::Success) => {
+            info!("Processed slash command `{name}`");
+
+            if let Some((guild, used_name)) = used_name {
+                if let Err(err) = ctx.psql().record_osu_name_usage(guild, &used_name).await {
+                    warn!("{:?}", err.wrap_err("Failed to record username usage"));
+                }
+            }
+        }
         Ok(res) => info!("Command `/{name}` was not processed: {res:?}"),
         Err(err) => {
             let wrap = format!("Failed to process slash command `{name}`");
@@ -3,7 +3,7 @@ use std::{mem, sync::Arc};
 use crate::{
     commands::help::{handle_help_category, handle_help_component},
     core::{events::EventKind, Context},
-    games::{bg::components::*, hl::components::*},
+    games::{bg::components::*, hl::components::*, minesweeper::components::*},
     pagination::components::*,
     util::interaction::InteractionComponent,
 };
@@ -18,13 +18,23 @@ pub async fn handle_component(ctx: Arc<Context>, mut component: InteractionCompo
         "bg_start_include" => handle_bg_start_include(&ctx, component).await,
         "bg_start_exclude" => handle_bg_start_exclude(&ctx, component).await,
         "bg_start_effects" => handle_bg_start_effects(&ctx, component).await,
+        "bg_start_preset" => handle_bg_start_preset(&ctx, component).await,
         "bg_start_button" => handle_bg_start_button(ctx, component).await,
         "bg_start_cancel" => handle_bg_start_cancel(&ctx, component).await,
+        "bg_team_red" => handle_bg_team_red(&ctx, component).await,
+        "bg_team_blue" => handle_bg_team_blue(&ctx, component).await,
         "help_category" => handle_help_category(&ctx, component).await,
         "higher_button" => handle_higher(ctx, component).await,
         "lower_button" => handle_lower(ctx, component).await,
         "try_again_button" => handle_try_again(ctx, component).await,
         "next_higherlower" => handle_next_higherlower(ctx, component).await,
+        "minesweeper_column" => handle_minesweeper_column(ctx, component).await,
+        "minesweeper_row" => handle_minesweeper_row(ctx, component).await,
+        "minesweeper_reveal" => handle_minesweeper_reveal(ctx, component).await,
+        "minesweeper_flag" => handle_minesweeper_flag(ctx, component).await,
+        "minesweeper_previous" => handle_minesweeper_previous(ctx, component).await,
+        "minesweeper_next" => handle_minesweeper_next(ctx, component).await,
+        "minesweeper_give_up" => handle_minesweeper_give_up(ctx, component).await,
         "pagination_start" => handle_pagination_start(ctx, component).await,
         "pagination_back" => handle_pagination_back(ctx, component).await,
         "pagination_custom" => handle_pagination_custom(ctx, component).await,
@@ -8,7 +8,7 @@ use futures::StreamExt;
 use twilight_gateway::{cluster::Events, Event};
 use twilight_model::id::Id;
 
-use crate::util::Authored;
+use crate::util::{osu::MapIdType, Authored};
 
 use self::{interaction::handle_interaction, message::handle_message};
 
@@ -217,20 +217,36 @@ async fn handle_event(ctx: Arc<Context>, event: Event, shard_id: u64) -> Result<
                 ctx.stats.message_counts.other_bot_messages.inc()
             }
 
+            let map = MapIdType::from_msg(&msg);
+            let map_id = MapIdType::map_from_msg(&msg);
+            ctx.store_recent_map(msg.channel_id, msg.id, map, map_id);
+
             handle_message(ctx, msg.0).await;
         }
         Event::MessageDelete(msg) => {
             ctx.stats.event_counts.message_delete.inc();
             ctx.remove_msg(msg.id);
+            ctx.remove_recent_map(msg.channel_id, msg.id);
         }
         Event::MessageDeleteBulk(msgs) => {
             ctx.stats.event_counts.message_delete_bulk.inc();
 
             for id in msgs.ids.into_iter() {
                 ctx.remove_msg(id);
+                ctx.remove_recent_map(msgs.channel_id, id);
+            }
+        }
+        Event::MessageUpdate(msg) => {
+            ctx.stats.event_counts.message_update.inc();
+
+            // Edited embeds might show a different map, e.g. when paginating
+            if let Some(ref embeds) = msg.embeds {
+                let content = msg.content.as_deref().unwrap_or_default();
+                let map = MapIdType::from_content(content, embeds);
+                let map_id = MapIdType::map_from_content(content, embeds);
+                ctx.store_recent_map(msg.channel_id, msg.id, map, map_id);
             }
         }
-        Event::MessageUpdate(_) => ctx.stats.event_counts.message_update.inc(),
         Event::PresenceUpdate(_) => {}
         Event::PresencesReplace => {}
         Event::ReactionAdd(reaction_add) => {
@@ -237,6 +237,33 @@ impl CustomClient {
         Self::error_for_status(response, url).await
     }
 
+    #[cfg(all(feature = "server", feature = "twitchtracking"))]
+    async fn make_twitch_request(&self, method: Method, url: &str, body: Body) -> Result<Bytes> {
+        trace!("{method} request of url {url}");
+
+        let req = Request::builder()
+            .uri(url)
+            .method(method)
+            .header(USER_AGENT, MY_USER_AGENT)
+            .header(CONTENT_TYPE, "application/json")
+            .header("Client-ID", self.twitch.client_id.clone())
+            .header(
+                http::header::AUTHORIZATION,
+                format!("Bearer {}", self.twitch.oauth_token),
+            )
+            .body(body)?;
+
+        self.ratelimit(Site::Twitch).await;
+
+        let response = self
+            .client
+            .request(req)
+            .await
+            .wrap_err("failed to receive response from twitch")?;
+
+        Self::error_for_status(response, url).await
+    }
+
     fn make_get_request_(&self, url: impl AsRef<str>, site: Site) -> RequestBuilder {
         let req = Request::builder()
             .uri(url.as_ref())
@@ -886,6 +913,15 @@ mod twitch_impls {
 
     use super::{CustomClient, TwitchDataList, TwitchStream, TwitchUser, TwitchVideo};
 
+    #[cfg(all(feature = "server", feature = "twitchtracking"))]
+    use {
+        super::{TwitchSubscription, TwitchSubscriptionList},
+        crate::util::constants::TWITCH_EVENTSUB_ENDPOINT,
+        eyre::ContextCompat,
+        hyper::{Body, Method},
+        serde_json::json,
+    };
+
     impl CustomClient {
         pub async fn get_twitch_user(&self, name: &str) -> Result<Option<TwitchUser>> {
             let data = [("login", name)];
@@ -991,4 +1027,91 @@ mod twitch_impls {
             Ok(videos.data.pop())
         }
     }
+
+    #[cfg(all(feature = "server", feature = "twitchtracking"))]
+    impl CustomClient {
+        /// Get all EventSub subscriptions of the bot
+        pub async fn get_twitch_subscriptions(&self) -> Result<Vec<TwitchSubscription>> {
+            let mut subscriptions = Vec::new();
+            let mut cursor: Option<String> = None;
+
+            loop {
+                let url = match cursor {
+                    Some(ref cursor) => format!("{TWITCH_EVENTSUB_ENDPOINT}?after={cursor}"),
+                    None => TWITCH_EVENTSUB_ENDPOINT.to_owned(),
+                };
+
+                let bytes = self
+                    .make_twitch_request(Method::GET, &url, Body::empty())
+                    .await?;
+
+                let list: TwitchSubscriptionList =
+                    serde_json::from_slice(&bytes).wrap_err_with(|| {
+                        let body = String::from_utf8_lossy(&bytes);
+
+                        format!("failed to deserialize twitch subscriptions: {body}")
+                    })?;
+
+                subscriptions.extend(list.data);
+
+                match list.pagination.cursor {
+                    Some(next) => cursor = Some(next),
+                    None => return Ok(subscriptions),
+                }
+            }
+        }
+
+        /// Subscribe to an event of a twitch user.
+        ///
+        /// Notifications will be sent to the callback url once twitch
+        /// verified the subscription through a challenge.
+        pub async fn create_twitch_subscription(
+            &self,
+            kind: &str,
+            user_id: u64,
+            callback: &str,
+            secret: &str,
+        ) -> Result<TwitchSubscription> {
+            let body = json!({
+                "type": kind,
+                "version": "1",
+                "condition": {
+                    "broadcaster_user_id": user_id.to_string(),
+                },
+                "transport": {
+                    "method": "webhook",
+                    "callback": callback,
+                    "secret": secret,
+                },
+            });
+
+            let bytes = self
+                .make_twitch_request(
+                    Method::POST,
+                    TWITCH_EVENTSUB_ENDPOINT,
+                    Body::from(body.to_string()),
+                )
+                .await?;
+
+            let mut list: TwitchDataList<TwitchSubscription> = serde_json::from_slice(&bytes)
+                .wrap_err_with(|| {
+                    let body = String::from_utf8_lossy(&bytes);
+
+                    format!("failed to deserialize twitch subscription: {body}")
+                })?;
+
+            list.data
+                .pop()
+                .wrap_err("missing subscription in twitch response")
+        }
+
+        pub async fn delete_twitch_subscription(&self, id: &str) -> Result<()> {
+            let url = format!("{TWITCH_EVENTSUB_ENDPOINT}?id={id}");
+
+            self.make_twitch_request(Method::DELETE, &url, Body::empty())
+                .await?;
+
+            Ok(())
+        }
+    }
 }
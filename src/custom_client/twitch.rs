@@ -57,6 +57,8 @@ pub struct TwitchStream {
     pub game_id: Option<u64>,
     #[serde(rename = "id", deserialize_with = "str_to_u64")]
     pub stream_id: u64,
+    #[serde(with = "datetime")]
+    pub started_at: OffsetDateTime,
     pub thumbnail_url: String,
     pub title: String,
     #[serde(deserialize_with = "str_to_u64")]
@@ -82,6 +84,40 @@ pub struct TwitchDataList<T> {
     pub data: Vec<T>,
 }
 
+#[derive(Deserialize)]
+pub struct TwitchPagination {
+    pub cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct TwitchSubscriptionList {
+    pub data: Vec<TwitchSubscription>,
+    pub pagination: TwitchPagination,
+}
+
+/// An EventSub subscription
+#[derive(Clone, Debug, Deserialize)]
+pub struct TwitchSubscription {
+    pub id: String,
+    pub status: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub condition: TwitchSubscriptionCondition,
+    pub transport: TwitchSubscriptionTransport,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct TwitchSubscriptionCondition {
+    #[serde(deserialize_with = "str_to_u64")]
+    pub broadcaster_user_id: u64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct TwitchSubscriptionTransport {
+    /// Only available for webhook transports
+    pub callback: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct TwitchVideo {
     #[serde(with = "datetime")]
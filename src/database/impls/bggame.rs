@@ -1,10 +1,14 @@
 use eyre::Result;
 use rosu_v2::model::GameMode;
 use tokio_stream::StreamExt;
+use twilight_model::id::{
+    marker::{ChannelMarker, GuildMarker, UserMarker},
+    Id,
+};
 
 use crate::{
-    database::{util::CustomSQL, MapsetTagWrapper, TagRow},
-    games::bg::MapsetTags,
+    database::{util::CustomSQL, BgGuildStats, MapsetTagWrapper, TagRow},
+    games::bg::{MapsetTags, Team},
     Database,
 };
 
@@ -39,6 +43,133 @@ impl Database {
         Ok(scores)
     }
 
+    /// The all-time score of the user and their rank among all users
+    pub async fn get_bggame_global_rank(
+        &self,
+        user: Id<UserMarker>,
+    ) -> Result<Option<(u32, usize)>> {
+        let row_opt = sqlx::query!(
+            "SELECT score,\
+            (SELECT COUNT(*) FROM bggame_scores WHERE score>user_score.score) AS \"higher!\" \
+            FROM bggame_scores AS user_score \
+            WHERE discord_id=$1",
+            user.get() as i64,
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row_opt.map(|row| (row.score as u32, row.higher as usize + 1)))
+    }
+
+    pub async fn increment_bggame_guild_stats(
+        &self,
+        guild: Id<GuildMarker>,
+        user_id: u64,
+        stats: &BgGuildStats,
+    ) -> Result<()> {
+        sqlx::query!(
+            "INSERT INTO bggame_guild_scores (guild_id,discord_id,score,guess_time_ms,hints) \
+            VALUES ($1,$2,$3,$4,$5) ON CONFLICT (guild_id,discord_id) DO \
+            UPDATE \
+            SET score=bggame_guild_scores.score+$3,\
+            guess_time_ms=bggame_guild_scores.guess_time_ms+$4,\
+            hints=bggame_guild_scores.hints+$5",
+            guild.get() as i64,
+            user_id as i64,
+            stats.score as i32,
+            stats.guess_time_ms as i64,
+            stats.hints as i32,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Scores of all users that guessed at least one mapset in the guild
+    pub async fn guild_bggame_scores(&self, guild: Id<GuildMarker>) -> Result<Vec<(u64, u32)>> {
+        let scores = sqlx::query!(
+            "SELECT discord_id,score FROM bggame_guild_scores WHERE guild_id=$1 AND score>0",
+            guild.get() as i64
+        )
+        .fetch(&self.pool)
+        .map(|res| res.map(|row| (row.discord_id as u64, row.score as u32)))
+        .collect::<Result<_, _>>()
+        .await?;
+
+        Ok(scores)
+    }
+
+    pub async fn get_bggame_guild_stats(
+        &self,
+        guild: Id<GuildMarker>,
+        user: Id<UserMarker>,
+    ) -> Result<Option<BgGuildStats>> {
+        let row_opt = sqlx::query!(
+            "SELECT score,guess_time_ms,hints FROM bggame_guild_scores \
+            WHERE guild_id=$1 AND discord_id=$2",
+            guild.get() as i64,
+            user.get() as i64,
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let stats = row_opt.map(|row| BgGuildStats {
+            score: row.score as u32,
+            guess_time_ms: row.guess_time_ms as u64,
+            hints: row.hints as u32,
+        });
+
+        Ok(stats)
+    }
+
+    /// Store the results of a bg game match.
+    ///
+    /// These are kept separately from the all-time scores of `bggame_scores`.
+    pub async fn insert_bggame_match(
+        &self,
+        channel: Id<ChannelMarker>,
+        rounds: usize,
+        teams: bool,
+        scores: &[(u64, Option<Team>, u32)],
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let match_id = sqlx::query!(
+            "INSERT INTO bggame_matches (channel_id,rounds,teams) \
+            VALUES ($1,$2,$3) RETURNING match_id",
+            channel.get() as i64,
+            rounds as i16,
+            teams,
+        )
+        .fetch_one(&mut tx)
+        .await?
+        .match_id;
+
+        let user_ids: Vec<_> = scores.iter().map(|(user, ..)| *user as i64).collect();
+        let user_teams: Vec<_> = scores
+            .iter()
+            .map(|(_, team, _)| team.map_or(-1, |team| team as i16))
+            .collect();
+        let user_scores: Vec<_> = scores.iter().map(|(.., score)| *score as i32).collect();
+
+        sqlx::query!(
+            "INSERT INTO bggame_match_scores (match_id,discord_id,team,score) \
+            SELECT $1,discord_id,NULLIF(team,-1),score \
+            FROM UNNEST($2::INT8[],$3::INT2[],$4::INT4[]) AS scores(discord_id,team,score)",
+            match_id,
+            &user_ids,
+            &user_teams,
+            &user_scores,
+        )
+        .execute(&mut tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
     pub async fn add_tag_mapset(
         &self,
         mapset_id: u32,
@@ -29,7 +29,11 @@ impl Database {
             while let Some(entry) = stream.next().await.transpose()? {
                 let config = GuildConfig {
                     authorities: serde_cbor::from_slice(&entry.authorities)?,
+                    digest_channel: entry.digest_channel_id.map(|id| Id::new(id as u64)),
+                    digest_day: entry.digest_day.map(|day| day as u8),
+                    digest_hour: entry.digest_hour.map(|hour| hour as u8),
                     embeds_size: entry.embeds_size.map(EmbedsSize::from),
+                    leaderboard_refresh: entry.leaderboard_refresh,
                     list_size: entry.list_size.map(ListSize::from),
                     minimized_pp: entry.minimized_pp.map(MinimizedPp::from),
                     prefixes: serde_cbor::from_slice(&entry.prefixes)?,
@@ -62,9 +66,13 @@ impl Database {
                 profile_size,\
                 show_retries,\
                 track_limit,\
-                with_lyrics\
+                with_lyrics,\
+                leaderboard_refresh,\
+                digest_channel_id,\
+                digest_day,\
+                digest_hour\
             )\
-            VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10) ON CONFLICT (guild_id) DO \
+            VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14) ON CONFLICT (guild_id) DO \
             UPDATE \
             SET authorities=$2,\
                 embeds_size=$3,\
@@ -74,7 +82,11 @@ impl Database {
                 profile_size=$7,\
                 show_retries=$8,\
                 track_limit=$9,\
-                with_lyrics=$10",
+                with_lyrics=$10,\
+                leaderboard_refresh=$11,\
+                digest_channel_id=$12,\
+                digest_day=$13,\
+                digest_hour=$14",
             guild_id.get() as i64,
             serde_cbor::to_vec(&config.authorities)?,
             config.embeds_size.map(|size| size as u8 as i16),
@@ -85,6 +97,10 @@ impl Database {
             config.show_retries,
             config.track_limit.map(|limit| limit as i16),
             config.with_lyrics,
+            config.leaderboard_refresh,
+            config.digest_channel.map(|channel| channel.get() as i64),
+            config.digest_day.map(|day| day as i16),
+            config.digest_hour.map(|hour| hour as i16),
         );
 
         query.execute(&self.pool).await?;
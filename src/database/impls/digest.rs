@@ -0,0 +1,110 @@
+use eyre::Result;
+use futures::stream::StreamExt;
+use time::OffsetDateTime;
+use twilight_model::id::{marker::GuildMarker, Id};
+
+use crate::{
+    database::{DigestMember, DigestValue},
+    Database,
+};
+
+impl Database {
+    /// osu!standard stats of all linked users among the given discord ids
+    pub async fn get_digest_members(&self, discord_ids: &[i64]) -> Result<Vec<DigestMember>> {
+        let query = sqlx::query!(
+            "SELECT stats.user_id,username,pp,global_rank,playtime \
+            FROM \
+                (SELECT osu_id \
+                FROM user_configs \
+                WHERE discord_id=ANY($1) \
+                  AND osu_id IS NOT NULL) AS configs \
+            JOIN osu_user_names AS names ON configs.osu_id=names.user_id \
+            JOIN osu_user_stats_mode AS stats ON names.user_id=stats.user_id \
+            WHERE stats.mode=0",
+            discord_ids,
+        );
+
+        let mut stream = query.fetch(&self.pool);
+        let mut members = Vec::new();
+
+        while let Some(row) = stream.next().await.transpose()? {
+            let member = DigestMember {
+                user_id: row.user_id as u32,
+                username: row.username.into(),
+                pp: row.pp,
+                global_rank: row.global_rank as u32,
+                playtime: row.playtime as u32,
+            };
+
+            members.push(member);
+        }
+
+        Ok(members)
+    }
+
+    pub async fn get_digest_values(&self, guild: Id<GuildMarker>) -> Result<Vec<DigestValue>> {
+        let query = sqlx::query!(
+            "SELECT user_id,pp,global_rank,playtime,taken_at \
+            FROM guild_digest_values \
+            WHERE guild_id=$1",
+            guild.get() as i64,
+        );
+
+        let mut stream = query.fetch(&self.pool);
+        let mut values = Vec::new();
+
+        while let Some(row) = stream.next().await.transpose()? {
+            let value = DigestValue {
+                user_id: row.user_id as u32,
+                pp: row.pp,
+                global_rank: row.global_rank as u32,
+                playtime: row.playtime as u32,
+                taken_at: row.taken_at,
+            };
+
+            values.push(value);
+        }
+
+        Ok(values)
+    }
+
+    /// Replace the stored values of the guild with the given members' values
+    pub async fn replace_digest_values(
+        &self,
+        guild: Id<GuildMarker>,
+        members: &[DigestMember],
+        taken_at: OffsetDateTime,
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            "DELETE FROM guild_digest_values WHERE guild_id=$1",
+            guild.get() as i64
+        )
+        .execute(&mut tx)
+        .await?;
+
+        let user_ids: Vec<_> = members.iter().map(|m| m.user_id as i32).collect();
+        let pps: Vec<_> = members.iter().map(|m| m.pp).collect();
+        let ranks: Vec<_> = members.iter().map(|m| m.global_rank as i32).collect();
+        let playtimes: Vec<_> = members.iter().map(|m| m.playtime as i32).collect();
+
+        sqlx::query!(
+            "INSERT INTO guild_digest_values (guild_id,user_id,pp,global_rank,playtime,taken_at) \
+            SELECT $1::INT8,*,$6::TIMESTAMPTZ \
+            FROM UNNEST($2::INT4[],$3::FLOAT4[],$4::INT4[],$5::INT4[])",
+            guild.get() as i64,
+            &user_ids,
+            &pps,
+            &ranks,
+            &playtimes,
+            taken_at,
+        )
+        .execute(&mut tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
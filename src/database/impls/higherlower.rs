@@ -1,7 +1,12 @@
 use eyre::Result;
+use rosu_v2::prelude::GameMods;
+use time::Date;
 use tokio_stream::StreamExt;
 
-use crate::{games::hl::HlVersion, Database};
+use crate::{
+    games::hl::{HlDailyEntry, HlVersion},
+    Database,
+};
 
 impl Database {
     pub async fn get_higherlower_scores(&self, version: HlVersion) -> Result<Vec<(u64, u32)>> {
@@ -61,4 +66,148 @@ impl Database {
 
         Ok(true)
     }
+
+    pub async fn get_higherlower_daily_scores(
+        &self,
+        version: HlVersion,
+        day: Date,
+    ) -> Result<Vec<(u64, u32)>> {
+        let query = sqlx::query!(
+            "SELECT discord_id,score \
+            FROM higherlower_daily_scores \
+            WHERE day=$1 AND version=$2",
+            day,
+            version as i16
+        );
+
+        let scores = query
+            .fetch(&self.pool)
+            .map(|res| res.map(|entry| (entry.discord_id as u64, entry.score as u32)))
+            .collect::<Result<_, _>>()
+            .await?;
+
+        Ok(scores)
+    }
+
+    /// Register the user's attempt at the daily challenge.
+    ///
+    /// Returns `false` if the user already attempted the challenge of that day.
+    pub async fn insert_higherlower_daily_attempt(
+        &self,
+        user_id: u64,
+        version: HlVersion,
+        day: Date,
+    ) -> Result<bool> {
+        let query = sqlx::query!(
+            "INSERT INTO higherlower_daily_scores \
+            VALUES ($1, $2, $3, 0) ON CONFLICT (day, version, discord_id) DO NOTHING",
+            day,
+            version as i16,
+            user_id as i64,
+        );
+
+        let res = query.execute(&self.pool).await?;
+
+        Ok(res.rows_affected() == 1)
+    }
+
+    pub async fn has_higherlower_daily_attempt(
+        &self,
+        user_id: u64,
+        version: HlVersion,
+        day: Date,
+    ) -> Result<bool> {
+        let query = sqlx::query!(
+            "SELECT EXISTS (\
+                SELECT 1 FROM higherlower_daily_scores \
+                WHERE day=$1 AND version=$2 AND discord_id=$3\
+            )",
+            day,
+            version as i16,
+            user_id as i64,
+        );
+
+        let exists = query.fetch_one(&self.pool).await?.exists;
+
+        Ok(exists.unwrap_or(false))
+    }
+
+    pub async fn update_higherlower_daily_score(
+        &self,
+        user_id: u64,
+        version: HlVersion,
+        day: Date,
+        score: u32,
+    ) -> Result<()> {
+        sqlx::query!(
+            "UPDATE higherlower_daily_scores \
+            SET score=$4 \
+            WHERE day=$1 AND version=$2 AND discord_id=$3",
+            day,
+            version as i16,
+            user_id as i64,
+            score as i32,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_higherlower_daily_entries(
+        &self,
+        version: HlVersion,
+        day: Date,
+    ) -> Result<Vec<HlDailyEntry>> {
+        let query = sqlx::query!(
+            "SELECT map_id,user_id,mods,farm \
+            FROM higherlower_daily_entries \
+            WHERE day=$1 AND version=$2 \
+            ORDER BY idx",
+            day,
+            version as i16
+        );
+
+        let entries = query
+            .fetch(&self.pool)
+            .map(|res| {
+                res.map(|entry| HlDailyEntry {
+                    map_id: entry.map_id as u32,
+                    user_id: entry.user_id.map(|user_id| user_id as u32),
+                    mods: entry
+                        .mods
+                        .map(|mods| GameMods::from_bits_truncate(mods as u32)),
+                    farm: entry.farm.map(|farm| farm as u32),
+                })
+            })
+            .collect::<Result<_, _>>()
+            .await?;
+
+        Ok(entries)
+    }
+
+    pub async fn insert_higherlower_daily_entry(
+        &self,
+        version: HlVersion,
+        day: Date,
+        idx: usize,
+        entry: &HlDailyEntry,
+    ) -> Result<()> {
+        sqlx::query!(
+            "INSERT INTO higherlower_daily_entries \
+            VALUES ($1, $2, $3, $4, $5, $6, $7) \
+            ON CONFLICT (day, version, idx) DO NOTHING",
+            day,
+            version as i16,
+            idx as i32,
+            entry.map_id as i32,
+            entry.user_id.map(|user_id| user_id as i32),
+            entry.mods.map(|mods| mods.bits() as i32),
+            entry.farm.map(|farm| farm as i32),
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
 }
@@ -61,6 +61,39 @@ impl Database {
         Ok(map)
     }
 
+    /// Look up a map by the md5 hash of its .osu file
+    pub async fn get_beatmap_by_checksum(&self, checksum: &str) -> Result<Option<Beatmap>> {
+        let mut conn = self.pool.acquire().await?;
+
+        let query = sqlx::query_as!(DBBeatmap, "SELECT * FROM maps WHERE checksum=$1", checksum);
+
+        let row = match query
+            .fetch_optional(&mut conn)
+            .await
+            .wrap_err("failed to get map")?
+        {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+
+        let mut map = Beatmap::from(row);
+
+        let query = sqlx::query_as!(
+            DBBeatmapset,
+            "SELECT * FROM mapsets WHERE mapset_id=$1",
+            map.mapset_id as i32
+        );
+
+        let mapset = query
+            .fetch_one(&mut conn)
+            .await
+            .wrap_err("failed to get mapset")?;
+
+        map.mapset.replace(mapset.into());
+
+        Ok(Some(map))
+    }
+
     pub async fn get_beatmapset<T: From<DBBeatmapset>>(&self, mapset_id: u32) -> Result<T> {
         let query = sqlx::query_as!(
             DBBeatmapset,
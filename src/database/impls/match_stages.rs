@@ -0,0 +1,81 @@
+use eyre::Result;
+use futures::stream::StreamExt;
+use twilight_model::id::{marker::GuildMarker, Id};
+
+use crate::{database::SavedMatchStage, Database};
+
+impl Database {
+    pub async fn get_match_stage(
+        &self,
+        guild: Id<GuildMarker>,
+        name: &str,
+    ) -> Result<Option<Vec<u32>>> {
+        let query = sqlx::query!(
+            "SELECT match_ids FROM match_stages WHERE guild_id=$1 AND name=$2",
+            guild.get() as i64,
+            name,
+        );
+
+        let match_ids = query
+            .fetch_optional(&self.pool)
+            .await?
+            .map(|row| row.match_ids.into_iter().map(|id| id as u32).collect());
+
+        Ok(match_ids)
+    }
+
+    pub async fn get_match_stages(&self, guild: Id<GuildMarker>) -> Result<Vec<SavedMatchStage>> {
+        let mut stream = sqlx::query!(
+            "SELECT name,match_ids FROM match_stages WHERE guild_id=$1 ORDER BY name",
+            guild.get() as i64,
+        )
+        .fetch(&self.pool);
+
+        let mut stages = Vec::new();
+
+        while let Some(entry) = stream.next().await.transpose()? {
+            let stage = SavedMatchStage {
+                name: entry.name,
+                match_ids: entry.match_ids.into_iter().map(|id| id as u32).collect(),
+            };
+
+            stages.push(stage);
+        }
+
+        Ok(stages)
+    }
+
+    pub async fn upsert_match_stage(
+        &self,
+        guild: Id<GuildMarker>,
+        name: &str,
+        match_ids: &[u32],
+    ) -> Result<()> {
+        let match_ids: Vec<_> = match_ids.iter().map(|&id| id as i32).collect();
+
+        sqlx::query!(
+            "INSERT INTO match_stages VALUES ($1,$2,$3) \
+            ON CONFLICT (guild_id,name) DO UPDATE SET match_ids=$3",
+            guild.get() as i64,
+            name,
+            &match_ids,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns whether a stage was removed
+    pub async fn remove_match_stage(&self, guild: Id<GuildMarker>, name: &str) -> Result<bool> {
+        let query = sqlx::query!(
+            "DELETE FROM match_stages WHERE guild_id=$1 AND name=$2",
+            guild.get() as i64,
+            name,
+        );
+
+        let res = query.execute(&self.pool).await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+}
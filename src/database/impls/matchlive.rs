@@ -0,0 +1,126 @@
+use eyre::Result;
+use futures::stream::StreamExt;
+use twilight_model::id::{
+    marker::{ChannelMarker, MessageMarker},
+    Id,
+};
+
+use crate::{database::MatchLiveTrack, Database};
+
+impl Database {
+    #[cold]
+    pub async fn get_match_live_tracks(&self) -> Result<Vec<MatchLiveTrack>> {
+        let mut stream = sqlx::query!("SELECT * FROM matchlive_tracks").fetch(&self.pool);
+        let mut tracks = Vec::new();
+
+        while let Some(entry) = stream.next().await.transpose()? {
+            let track = MatchLiveTrack {
+                match_id: entry.match_id as u32,
+                channel: Id::new(entry.channel_id as u64),
+                msg: Id::new(entry.msg_id as u64),
+                last_event_id: entry.last_event_id as u64,
+                warmups: entry.warmups as usize,
+                scoreboard: entry.scoreboard_msg_id.map(|id| Id::new(id as u64)),
+            };
+
+            tracks.push(track);
+        }
+
+        Ok(tracks)
+    }
+
+    pub async fn insert_match_live_track(&self, track: &MatchLiveTrack) -> Result<()> {
+        sqlx::query!(
+            "INSERT INTO matchlive_tracks \
+            VALUES ($1,$2,$3,$4,$5,$6) \
+            ON CONFLICT (match_id,channel_id) DO \
+            UPDATE \
+            SET msg_id=$3,last_event_id=$4,warmups=$5,scoreboard_msg_id=$6",
+            track.match_id as i32,
+            track.channel.get() as i64,
+            track.msg.get() as i64,
+            track.last_event_id as i64,
+            track.warmups as i16,
+            track.scoreboard.map(|id| id.get() as i64),
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn update_match_live_msg(
+        &self,
+        match_id: u32,
+        channel: Id<ChannelMarker>,
+        msg: Id<MessageMarker>,
+    ) -> Result<()> {
+        sqlx::query!(
+            "UPDATE matchlive_tracks SET msg_id=$3 WHERE match_id=$1 AND channel_id=$2",
+            match_id as i32,
+            channel.get() as i64,
+            msg.get() as i64,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn update_match_live_scoreboard(
+        &self,
+        match_id: u32,
+        channel: Id<ChannelMarker>,
+        msg: Id<MessageMarker>,
+    ) -> Result<()> {
+        sqlx::query!(
+            "UPDATE matchlive_tracks SET scoreboard_msg_id=$3 WHERE match_id=$1 AND channel_id=$2",
+            match_id as i32,
+            channel.get() as i64,
+            msg.get() as i64,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn update_match_live_event(&self, match_id: u32, last_event_id: u64) -> Result<()> {
+        sqlx::query!(
+            "UPDATE matchlive_tracks SET last_event_id=$2 WHERE match_id=$1",
+            match_id as i32,
+            last_event_id as i64,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn remove_match_live_track(
+        &self,
+        match_id: u32,
+        channel: Id<ChannelMarker>,
+    ) -> Result<()> {
+        sqlx::query!(
+            "DELETE FROM matchlive_tracks WHERE match_id=$1 AND channel_id=$2",
+            match_id as i32,
+            channel.get() as i64,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn remove_match_live_tracks(&self, match_id: u32) -> Result<()> {
+        sqlx::query!(
+            "DELETE FROM matchlive_tracks WHERE match_id=$1",
+            match_id as i32
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use eyre::Result;
+
+use crate::{games::minesweeper::Difficulty, Database};
+
+impl Database {
+    /// The user's fastest time in milliseconds
+    pub async fn get_minesweeper_best(
+        &self,
+        user_id: u64,
+        difficulty: Difficulty,
+    ) -> Result<Option<u32>> {
+        let query = sqlx::query!(
+            "SELECT time_ms FROM minesweeper_scores \
+            WHERE discord_id=$1 AND difficulty=$2",
+            user_id as i64,
+            difficulty as i16,
+        );
+
+        let best = query.fetch_optional(&self.pool).await?;
+
+        Ok(best.map(|row| row.time_ms as u32))
+    }
+
+    /// Caller must make sure the time is better than the one retrieved from
+    /// [`get_minesweeper_best`](Database::get_minesweeper_best)
+    pub async fn upsert_minesweeper_best(
+        &self,
+        user_id: u64,
+        difficulty: Difficulty,
+        time_ms: u32,
+    ) -> Result<()> {
+        sqlx::query!(
+            "INSERT INTO minesweeper_scores \
+                VALUES ($1, $2, $3) ON CONFLICT (discord_id, difficulty) DO \
+                UPDATE \
+                SET time_ms=$3",
+            user_id as i64,
+            difficulty as i16,
+            time_ms as i32
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
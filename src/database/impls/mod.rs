@@ -1,11 +1,20 @@
 mod bggame;
 mod configs;
+mod digest;
 mod higherlower;
 mod maps;
+mod match_stages;
+mod minesweeper;
 mod osu_users;
 mod role_assign;
 mod snipe_country;
 mod streams;
 
+#[cfg(feature = "matchlive")]
+mod matchlive;
+
 #[cfg(feature = "osutracking")]
 mod tracking;
+
+#[cfg(any(feature = "osutracking", feature = "twitchtracking"))]
+mod webhooks;
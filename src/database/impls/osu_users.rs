@@ -1,15 +1,16 @@
 use std::{cmp::Ordering, collections::BTreeMap};
 
 use eyre::{Result, WrapErr};
-use futures::stream::StreamExt;
+use futures::stream::{StreamExt, TryStreamExt};
 use hashbrown::HashMap;
 use rosu_v2::prelude::{GameMode, User, Username};
-use sqlx::Row;
-use time::OffsetDateTime;
+use sqlx::{Postgres, Row, Transaction};
+use time::{Duration, OffsetDateTime};
+use twilight_model::id::{marker::GuildMarker, Id};
 
 use crate::{
     commands::osu::UserValue,
-    database::{Database, UserStatsColumn, UserValueRaw},
+    database::{Database, RankedOsuUser, UserStatsColumn, UserStatsSnapshot, UserValueRaw},
     embeds::RankingEntry,
     util::hasher::IntHasher,
 };
@@ -291,6 +292,173 @@ LIMIT 1)) AS neighbors",
         Ok(map)
     }
 
+    /// Remember that the username was used in the guild so that
+    /// it will be suggested first when autocompleting usernames.
+    ///
+    /// Names that are not stored in `osu_user_names` are ignored.
+    pub async fn record_osu_name_usage(&self, guild: Id<GuildMarker>, name: &str) -> Result<()> {
+        let query = sqlx::query!(
+            "INSERT INTO osu_name_usage (guild_id,username) \
+            SELECT $1::INT8,LOWER(username) \
+            FROM osu_user_names \
+            WHERE LOWER(username)=$2 \
+            LIMIT 1 \
+            ON CONFLICT (guild_id,username) DO \
+            UPDATE \
+            SET last_used=NOW()",
+            guild.get() as i64,
+            name.to_ascii_lowercase(),
+        );
+
+        query
+            .execute(&self.pool)
+            .await
+            .wrap_err("failed to execute query")?;
+
+        Ok(())
+    }
+
+    /// Usernames that start with the given prefix.
+    ///
+    /// Names that were recently used in the guild come first, followed by names
+    /// of linked members, i.e. the given discord ids.
+    /// If the prefix is empty, only recently used names are returned.
+    pub async fn get_name_suggestions(
+        &self,
+        prefix: &str,
+        guild: Option<Id<GuildMarker>>,
+        discord_ids: &[i64],
+    ) -> Result<Vec<Username>> {
+        let guild = guild.map_or(0, |guild| guild.get() as i64);
+
+        let names = if prefix.is_empty() {
+            let query = sqlx::query!(
+                "SELECT names.username \
+                FROM osu_name_usage AS used \
+                JOIN osu_user_names AS names ON used.username=LOWER(names.username) \
+                WHERE used.guild_id=$1 \
+                ORDER BY used.last_used DESC \
+                LIMIT 25",
+                guild
+            );
+
+            query
+                .fetch(&self.pool)
+                .map_ok(|row| row.username.into())
+                .try_collect()
+                .await
+                .wrap_err("failed to fetch recent usernames")?
+        } else {
+            // Escape LIKE wildcards so only the appended `%` matches arbitrary characters
+            let pattern = prefix
+                .to_ascii_lowercase()
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+                + "%";
+
+            let query = sqlx::query!(
+                "SELECT names.username \
+                FROM osu_user_names AS names \
+                LEFT JOIN osu_name_usage AS used \
+                  ON used.guild_id=$2 AND used.username=LOWER(names.username) \
+                LEFT JOIN \
+                  (SELECT DISTINCT osu_id \
+                   FROM user_configs \
+                   WHERE discord_id=ANY($3)) AS configs ON names.user_id=configs.osu_id \
+                WHERE LOWER(names.username) LIKE $1 \
+                ORDER BY used.last_used DESC NULLS LAST, configs.osu_id IS NULL, names.username \
+                LIMIT 25",
+                pattern,
+                guild,
+                discord_ids
+            );
+
+            query
+                .fetch(&self.pool)
+                .map_ok(|row| row.username.into())
+                .try_collect()
+                .await
+                .wrap_err("failed to fetch usernames")?
+        };
+
+        Ok(names)
+    }
+
+    /// A random user with cached stats in the given mode whose
+    /// global rank is within the given bounds, excluding `skip_user`
+    pub async fn random_ranked_osu_user(
+        &self,
+        mode: GameMode,
+        min_rank: u32,
+        max_rank: u32,
+        skip_user: u32,
+    ) -> Result<Option<RankedOsuUser>> {
+        let query = sqlx::query!(
+            "SELECT stats.user_id,stats.global_rank,names.username,users.country_code \
+            FROM osu_user_stats_mode AS stats \
+            JOIN osu_user_names AS names ON stats.user_id=names.user_id \
+            JOIN osu_user_stats AS users ON stats.user_id=users.user_id \
+            WHERE stats.mode=$1 \
+              AND stats.global_rank BETWEEN $2 AND $3 \
+              AND stats.user_id<>$4 \
+            ORDER BY RANDOM() \
+            LIMIT 1",
+            mode as i16,
+            min_rank.max(1) as i32,
+            max_rank as i32,
+            skip_user as i32,
+        );
+
+        let user = query
+            .fetch_optional(&self.pool)
+            .await?
+            .map(|row| RankedOsuUser {
+                user_id: row.user_id as u32,
+                username: row.username.into(),
+                country_code: row.country_code.into(),
+                global_rank: row.global_rank as u32,
+            });
+
+        Ok(user)
+    }
+
+    /// Osu ids and modes of the linked users among the given discord ids
+    /// whose stats were not updated since the given point in time.
+    /// Users without any stats are included with mode osu!standard.
+    ///
+    /// Sorted so that the stalest stats come first.
+    pub async fn get_stale_osu_users(
+        &self,
+        discord_ids: &[i64],
+        updated_before: OffsetDateTime,
+    ) -> Result<Vec<(u32, GameMode)>> {
+        let query = sqlx::query!(
+            "SELECT DISTINCT configs.osu_id AS \"user_id!\",\
+                COALESCE(stats.mode, 0) AS \"mode!\",\
+                stats.last_update \
+            FROM \
+                (SELECT osu_id \
+                FROM user_configs \
+                WHERE discord_id=ANY($1) \
+                  AND osu_id IS NOT NULL) AS configs \
+            LEFT JOIN osu_user_stats_mode AS stats ON configs.osu_id=stats.user_id \
+            WHERE stats.last_update IS NULL OR stats.last_update < $2 \
+            ORDER BY stats.last_update NULLS FIRST",
+            discord_ids,
+            updated_before,
+        );
+
+        let mut stream = query.fetch(&self.pool);
+        let mut users = Vec::new();
+
+        while let Some(row) = stream.next().await.transpose()? {
+            users.push((row.user_id as u32, GameMode::from(row.mode as u8)));
+        }
+
+        Ok(users)
+    }
+
     pub async fn upsert_osu_user(&self, user: &User, mode: GameMode) -> Result<()> {
         let mut tx = self.pool.begin().await?;
 
@@ -435,11 +603,136 @@ LIMIT 1)) AS neighbors",
                 .execute(&mut tx)
                 .await
                 .wrap_err("failed to insert mode stats entry")?;
+
+            let snapshot = UserStatsSnapshot {
+                taken_at: OffsetDateTime::now_utc(),
+                pp: stats.pp,
+                global_rank: stats.global_rank.unwrap_or(0),
+                accuracy: stats.accuracy,
+                playcount: stats.playcount,
+                ranked_score: stats.ranked_score,
+            };
+
+            Self::insert_osu_user_snapshot(&mut tx, user.user_id, mode, &snapshot)
+                .await
+                .wrap_err("failed to insert stats snapshot")?;
         }
 
         Ok(tx.commit().await?)
     }
 
+    /// Store a snapshot of the user's stats unless they did not change since
+    /// the latest snapshot. Snapshots within the same hour are merged.
+    async fn insert_osu_user_snapshot(
+        tx: &mut Transaction<'_, Postgres>,
+        user_id: u32,
+        mode: GameMode,
+        snapshot: &UserStatsSnapshot,
+    ) -> Result<()> {
+        let latest_query = sqlx::query!(
+            "SELECT taken_at,pp,global_rank,accuracy,playcount,ranked_score \
+            FROM osu_user_mode_snapshots \
+            WHERE user_id=$1 AND mode=$2 \
+            ORDER BY taken_at DESC LIMIT 1",
+            user_id as i32,
+            mode as i16,
+        );
+
+        let latest = latest_query.fetch_optional(&mut *tx).await?;
+
+        if let Some(ref latest) = latest {
+            let unchanged = latest.pp == snapshot.pp
+                && latest.global_rank == snapshot.global_rank as i32
+                && latest.accuracy == snapshot.accuracy
+                && latest.playcount == snapshot.playcount as i32
+                && latest.ranked_score == snapshot.ranked_score as i64;
+
+            if unchanged {
+                return Ok(());
+            }
+        }
+
+        match latest {
+            Some(latest) if snapshot.taken_at - latest.taken_at < Duration::HOUR => {
+                sqlx::query!(
+                    "UPDATE osu_user_mode_snapshots \
+                    SET pp=$4,global_rank=$5,accuracy=$6,playcount=$7,ranked_score=$8 \
+                    WHERE user_id=$1 AND mode=$2 AND taken_at=$3",
+                    user_id as i32,
+                    mode as i16,
+                    latest.taken_at,
+                    snapshot.pp,
+                    snapshot.global_rank as i32,
+                    snapshot.accuracy,
+                    snapshot.playcount as i32,
+                    snapshot.ranked_score as i64,
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+            _ => {
+                sqlx::query!(
+                    "INSERT INTO osu_user_mode_snapshots \
+                    (user_id,mode,taken_at,pp,global_rank,accuracy,playcount,ranked_score) \
+                    VALUES ($1,$2,$3,$4,$5,$6,$7,$8)",
+                    user_id as i32,
+                    mode as i16,
+                    snapshot.taken_at,
+                    snapshot.pp,
+                    snapshot.global_rank as i32,
+                    snapshot.accuracy,
+                    snapshot.playcount as i32,
+                    snapshot.ranked_score as i64,
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Snapshots of the user's stats since the given point in time,
+    /// including the latest snapshot before it if available.
+    pub async fn get_osu_user_snapshots(
+        &self,
+        user_id: u32,
+        mode: GameMode,
+        since: OffsetDateTime,
+    ) -> Result<Vec<UserStatsSnapshot>> {
+        let query = sqlx::query!(
+            "SELECT taken_at,pp,global_rank,accuracy,playcount,ranked_score \
+            FROM osu_user_mode_snapshots \
+            WHERE user_id=$1 AND mode=$2 AND taken_at >= (\
+                SELECT COALESCE(MAX(taken_at), $3) \
+                FROM osu_user_mode_snapshots \
+                WHERE user_id=$1 AND mode=$2 AND taken_at <= $3\
+            ) \
+            ORDER BY taken_at",
+            user_id as i32,
+            mode as i16,
+            since,
+        );
+
+        let mut stream = query.fetch(&self.pool);
+        let mut snapshots = Vec::new();
+
+        while let Some(row) = stream.next().await.transpose()? {
+            let snapshot = UserStatsSnapshot {
+                taken_at: row.taken_at,
+                pp: row.pp,
+                global_rank: row.global_rank as u32,
+                accuracy: row.accuracy,
+                playcount: row.playcount as u32,
+                ranked_score: row.ranked_score as u64,
+            };
+
+            snapshots.push(snapshot);
+        }
+
+        Ok(snapshots)
+    }
+
     pub async fn get_osu_users_stats(
         &self,
         column: UserStatsColumn,
@@ -1,52 +1,80 @@
-use std::iter;
-
 use eyre::Result;
-use flurry::HashMap as FlurryMap;
 use futures::stream::StreamExt;
+use twilight_model::id::Id;
 
-use crate::{util::hasher::IntHasher, Database};
-
-type TrackedStreams = FlurryMap<u64, Vec<u64>, IntHasher>;
+use crate::{
+    database::{StreamTrack, StreamTrackSettings, TrackedStreams},
+    util::hasher::IntHasher,
+    Database,
+};
 
 impl Database {
-    pub async fn add_stream_track(&self, channel: u64, user: u64) -> Result<bool> {
-        let done = sqlx::query!(
-            "INSERT INTO stream_tracks VALUES ($1,$2) ON CONFLICT DO NOTHING",
+    /// Insert the track or overwrite the settings of an existing one
+    pub async fn upsert_stream_track(
+        &self,
+        channel: u64,
+        user: u64,
+        settings: &StreamTrackSettings,
+    ) -> Result<()> {
+        sqlx::query!(
+            "INSERT INTO stream_tracks (channel_id,user_id,osu_only,title_filter,vod_edit,role_id) \
+            VALUES ($1,$2,$3,$4,$5,$6) \
+            ON CONFLICT (channel_id,user_id) DO \
+            UPDATE \
+            SET osu_only=$3,title_filter=$4,vod_edit=$5,role_id=$6",
             channel as i64,
-            user as i64
+            user as i64,
+            settings.osu_only,
+            settings.title_filter.as_deref(),
+            settings.vod_edit,
+            settings.role.map(|role| role.get() as i64),
         )
         .execute(&self.pool)
         .await?;
 
-        Ok(done.rows_affected() > 0)
+        Ok(())
     }
 
     #[cold]
     pub async fn get_stream_tracks(&self) -> Result<TrackedStreams> {
-        let mut stream = sqlx::query!("SELECT * FROM stream_tracks").fetch(&self.pool);
+        let mut stream = sqlx::query!(
+            "SELECT channel_id,user_id,osu_only,title_filter,vod_edit,role_id FROM stream_tracks"
+        )
+        .fetch(&self.pool);
+
         let tracks = TrackedStreams::with_capacity_and_hasher(1000, IntHasher);
 
         {
             let guard = tracks.guard();
 
             while let Some(entry) = stream.next().await.transpose()? {
-                let channel_id = entry.channel_id as u64;
                 let user_id = entry.user_id as u64;
 
-                let missing = tracks
-                    .compute_if_present(
-                        &user_id,
-                        |_, channels| {
-                            let channels = channels.iter().copied().chain(iter::once(channel_id));
-
-                            Some(channels.collect())
-                        },
-                        &guard,
-                    )
-                    .is_none();
-
-                if missing {
-                    tracks.insert(user_id, vec![channel_id], &guard);
+                let track = StreamTrack {
+                    channel: Id::new(entry.channel_id as u64),
+                    settings: StreamTrackSettings {
+                        osu_only: entry.osu_only,
+                        title_filter: entry.title_filter,
+                        vod_edit: entry.vod_edit,
+                        role: entry.role_id.map(|role| Id::new(role as u64)),
+                    },
+                };
+
+                let mut track = Some(track);
+
+                tracks.compute_if_present(
+                    &user_id,
+                    |_, channels| {
+                        let mut channels = channels.clone();
+                        channels.extend(track.take());
+
+                        Some(channels)
+                    },
+                    &guard,
+                );
+
+                if let Some(track) = track {
+                    tracks.insert(user_id, vec![track], &guard);
                 }
             }
         }
@@ -1,14 +1,19 @@
 #![cfg(feature = "osutracking")]
 
-use eyre::{Result, WrapErr};
+use eyre::{Report, Result, WrapErr};
 use futures::stream::StreamExt;
 use hashbrown::HashMap;
 use rosu_v2::model::GameMode;
 use serde_json::Value;
-use time::OffsetDateTime;
+use time::{Duration, OffsetDateTime};
 use twilight_model::id::{marker::ChannelMarker, Id};
 
-use crate::{database::TrackingUser, tracking::TrackingEntry, util::hasher::IntHasher, Database};
+use crate::{
+    database::{TrackingFilters, TrackingNotification, TrackingUser},
+    tracking::{TrackingEntry, TrackingKind},
+    util::hasher::IntHasher,
+    Database,
+};
 
 impl Database {
     #[cold]
@@ -19,17 +24,32 @@ impl Database {
         while let Some(entry) = stream.next().await.transpose()? {
             let user_id = entry.user_id as u32;
             let mode = GameMode::from(entry.mode as u8);
+            let kind = TrackingKind::from(entry.kind);
             let last_top_score = entry.last_top_score;
             let channels: Value = entry.channels;
 
+            // Skip users with invalid filters instead of tracking them without the filters
+            let channels = match serde_json::from_value(channels) {
+                Ok(channels) => channels,
+                Err(err) => {
+                    let wrap =
+                        format!("Failed to deserialize tracking channels of ({user_id},{mode})");
+                    warn!("{:?}", Report::new(err).wrap_err(wrap));
+
+                    continue;
+                }
+            };
+
             let user = TrackingUser {
                 user_id,
                 mode,
+                kind,
                 last_top_score,
-                channels: serde_json::from_value(channels)?,
+                channels,
+                interval: Duration::seconds(entry.check_interval as i64),
             };
 
-            tracks.push((TrackingEntry { user_id, mode }, user));
+            tracks.push((TrackingEntry::from(&user), user));
         }
 
         Ok(tracks)
@@ -41,9 +61,10 @@ impl Database {
         last_top_score: OffsetDateTime,
     ) -> Result<()> {
         sqlx::query!(
-            "UPDATE osu_trackings SET last_top_score=$3 WHERE user_id=$1 AND mode=$2",
+            "UPDATE osu_trackings SET last_top_score=$4 WHERE user_id=$1 AND mode=$2 AND kind=$3",
             entry.user_id as i32,
             entry.mode as i16,
+            entry.kind as i16,
             last_top_score,
         )
         .execute(&self.pool)
@@ -52,19 +73,37 @@ impl Database {
         Ok(())
     }
 
+    pub async fn update_osu_tracking_interval(
+        &self,
+        entry: &TrackingEntry,
+        interval: Duration,
+    ) -> Result<()> {
+        sqlx::query!(
+            "UPDATE osu_trackings SET check_interval=$4 WHERE user_id=$1 AND mode=$2 AND kind=$3",
+            entry.user_id as i32,
+            entry.mode as i16,
+            entry.kind as i16,
+            interval.whole_seconds() as i32,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn update_osu_tracking(
         &self,
-        user_id: u32,
-        mode: GameMode,
+        entry: &TrackingEntry,
         last_top_score: OffsetDateTime,
-        channels: &HashMap<Id<ChannelMarker>, usize, IntHasher>,
+        channels: &HashMap<Id<ChannelMarker>, TrackingFilters, IntHasher>,
     ) -> Result<()> {
         sqlx::query!(
             "UPDATE osu_trackings \
-            SET last_top_score=$3,channels=$4 \
-            WHERE user_id=$1 AND mode=$2",
-            user_id as i32,
-            mode as i16,
+            SET last_top_score=$4,channels=$5 \
+            WHERE user_id=$1 AND mode=$2 AND kind=$3",
+            entry.user_id as i32,
+            entry.mode as i16,
+            entry.kind as i16,
             last_top_score,
             serde_json::to_value(&channels)?
         )
@@ -74,11 +113,17 @@ impl Database {
         Ok(())
     }
 
-    pub async fn remove_osu_tracking(&self, user_id: u32, mode: GameMode) -> Result<()> {
+    pub async fn remove_osu_tracking(
+        &self,
+        user_id: u32,
+        mode: GameMode,
+        kind: TrackingKind,
+    ) -> Result<()> {
         sqlx::query!(
-            "DELETE FROM osu_trackings WHERE user_id=$1 AND mode=$2",
+            "DELETE FROM osu_trackings WHERE user_id=$1 AND mode=$2 AND kind=$3",
             user_id as i32,
-            mode as i16
+            mode as i16,
+            kind as i16,
         )
         .execute(&self.pool)
         .await?;
@@ -88,39 +133,43 @@ impl Database {
 
     pub async fn insert_osu_tracking(
         &self,
-        user_id: u32,
-        mode: GameMode,
+        entry: &TrackingEntry,
         last_top_score: OffsetDateTime,
         channel: Id<ChannelMarker>,
-        limit: usize,
+        filters: TrackingFilters,
+        interval: Duration,
     ) -> Result<()> {
         let mut set = HashMap::with_hasher(IntHasher);
-        set.insert(channel, limit);
+        set.insert(channel, filters);
 
         let row = sqlx::query!(
             "INSERT INTO osu_trackings \
-            VALUES ($1,$2,$3,$4)\
-            ON CONFLICT (user_id,mode) DO \
+            (user_id,mode,kind,last_top_score,channels,check_interval) \
+            VALUES ($1,$2,$3,$4,$5,$6)\
+            ON CONFLICT (user_id,mode,kind) DO \
             UPDATE \
-            SET last_top_score=$3 \
+            SET last_top_score=$4 \
             RETURNING channels",
-            user_id as i32,
-            mode as i16,
+            entry.user_id as i32,
+            entry.mode as i16,
+            entry.kind as i16,
             last_top_score,
             serde_json::to_value(&set)?,
+            interval.whole_seconds() as i32,
         )
         .fetch_one(&self.pool)
         .await
         .wrap_err("failed to insert entry")?;
 
-        let mut channels: HashMap<Id<ChannelMarker>, usize, IntHasher> =
+        let mut channels: HashMap<Id<ChannelMarker>, TrackingFilters, IntHasher> =
             serde_json::from_value(row.channels)?;
 
-        if channels.insert(channel, limit).is_none() {
+        if channels.insert(channel, filters) != Some(filters) {
             sqlx::query!(
-                "UPDATE osu_trackings SET channels=$3 WHERE user_id=$1 AND mode=$2",
-                user_id as i32,
-                mode as i16,
+                "UPDATE osu_trackings SET channels=$4 WHERE user_id=$1 AND mode=$2 AND kind=$3",
+                entry.user_id as i32,
+                entry.mode as i16,
+                entry.kind as i16,
                 serde_json::to_value(&channels)?
             )
             .execute(&self.pool)
@@ -130,4 +179,92 @@ impl Database {
 
         Ok(())
     }
+
+    pub async fn insert_osu_tracking_notification(
+        &self,
+        notif: &TrackingNotification,
+        channels: &[Id<ChannelMarker>],
+    ) -> Result<()> {
+        let channels: Vec<_> = channels
+            .iter()
+            .map(|channel| channel.get() as i64)
+            .collect();
+
+        sqlx::query!(
+            "INSERT INTO osu_tracking_notifications \
+            (user_id,mode,score_id,username,map_id,mapset_id,map_title,pp,top_idx,channels,ended_at,kind) \
+            VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12) \
+            ON CONFLICT (user_id,mode,score_id,kind) DO \
+            UPDATE \
+            SET top_idx=$9,\
+            channels=ARRAY(SELECT DISTINCT UNNEST(osu_tracking_notifications.channels || $10))",
+            notif.user_id as i32,
+            notif.mode as i16,
+            notif.score_id as i64,
+            notif.username.as_str(),
+            notif.map_id as i32,
+            notif.mapset_id as i32,
+            notif.map_title,
+            notif.pp,
+            notif.top_idx as i16,
+            &channels,
+            notif.ended_at,
+            notif.kind as i16,
+        )
+        .execute(&self.pool)
+        .await
+        .wrap_err("failed to insert notification")?;
+
+        Ok(())
+    }
+
+    /// Returns all notifications that were sent to the channel within the given
+    /// time frame, the latest one first
+    pub async fn get_osu_tracking_notifications(
+        &self,
+        channel: Id<ChannelMarker>,
+        user_id: Option<u32>,
+        mode: Option<GameMode>,
+        since: OffsetDateTime,
+        until: OffsetDateTime,
+    ) -> Result<Vec<TrackingNotification>> {
+        let query = sqlx::query!(
+            "SELECT user_id,mode,score_id,username,map_id,mapset_id,map_title,pp,kind,top_idx,ended_at,notified_at \
+            FROM osu_tracking_notifications \
+            WHERE $1=ANY(channels) \
+            AND ($2::INT4 IS NULL OR user_id=$2) \
+            AND ($3::INT2 IS NULL OR mode=$3) \
+            AND notified_at BETWEEN $4 AND $5 \
+            ORDER BY notified_at DESC",
+            channel.get() as i64,
+            user_id.map(|id| id as i32),
+            mode.map(|mode| mode as i16),
+            since,
+            until,
+        );
+
+        let mut stream = query.fetch(&self.pool);
+        let mut notifs = Vec::new();
+
+        while let Some(row) = stream.next().await.transpose()? {
+            let notif = TrackingNotification {
+                user_id: row.user_id as u32,
+                username: row.username.into(),
+                mode: GameMode::from(row.mode as u8),
+                score_id: row.score_id as u64,
+                map_id: row.map_id as u32,
+                mapset_id: row.mapset_id as u32,
+                map_title: row.map_title,
+                pp: row.pp,
+                kind: TrackingKind::from(row.kind),
+                top_idx: row.top_idx as usize,
+                ended_at: row.ended_at,
+                notified_at: row.notified_at,
+            };
+
+            notifs.push(notif);
+        }
+
+        Ok(notifs)
+    }
 }
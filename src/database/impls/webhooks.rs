@@ -0,0 +1,140 @@
+use eyre::{Result, WrapErr};
+use futures::stream::StreamExt;
+use ring::{
+    aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN},
+    rand::{SecureRandom, SystemRandom},
+};
+use twilight_model::id::{marker::ChannelMarker, Id};
+
+use crate::{
+    core::BotConfig,
+    database::{TrackingWebhook, TrackingWebhooks},
+    util::hasher::IntHasher,
+    Database,
+};
+
+impl Database {
+    #[cold]
+    pub async fn get_tracking_webhooks(&self) -> Result<TrackingWebhooks> {
+        if BotConfig::get().tokens.webhook_key.is_none() {
+            info!("No webhook key specified, tracking webhooks are disabled");
+
+            return Ok(TrackingWebhooks::with_hasher(IntHasher));
+        }
+
+        let mut stream = sqlx::query!("SELECT * FROM tracking_webhooks").fetch(&self.pool);
+        let webhooks = TrackingWebhooks::with_capacity_and_hasher(64, IntHasher);
+
+        {
+            let guard = webhooks.guard();
+
+            while let Some(entry) = stream.next().await.transpose()? {
+                let channel = Id::new(entry.channel_id as u64);
+
+                // Skip the entry instead of failing entirely e.g. if the key changed
+                let token = match decrypt_token(entry.token, &entry.nonce) {
+                    Ok(token) => token,
+                    Err(err) => {
+                        let wrap = format!("failed to decrypt webhook token of channel {channel}");
+                        warn!("{:?}", err.wrap_err(wrap));
+
+                        continue;
+                    }
+                };
+
+                let webhook = TrackingWebhook {
+                    webhook_id: Id::new(entry.webhook_id as u64),
+                    token,
+                    username: entry.username,
+                    avatar_url: entry.avatar_url,
+                };
+
+                webhooks.insert(channel, webhook, &guard);
+            }
+        }
+
+        Ok(webhooks)
+    }
+
+    pub async fn upsert_tracking_webhook(
+        &self,
+        channel: Id<ChannelMarker>,
+        webhook: &TrackingWebhook,
+    ) -> Result<()> {
+        let (token, nonce) = encrypt_token(&webhook.token)?;
+
+        sqlx::query!(
+            "INSERT INTO tracking_webhooks \
+            VALUES ($1,$2,$3,$4,$5,$6) \
+            ON CONFLICT (channel_id) DO \
+            UPDATE \
+            SET webhook_id=$2,token=$3,nonce=$4,username=$5,avatar_url=$6",
+            channel.get() as i64,
+            webhook.webhook_id.get() as i64,
+            token,
+            &nonce[..],
+            webhook.username,
+            webhook.avatar_url,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn remove_tracking_webhook(&self, channel: Id<ChannelMarker>) -> Result<bool> {
+        let done = sqlx::query!(
+            "DELETE FROM tracking_webhooks WHERE channel_id=$1",
+            channel.get() as i64
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(done.rows_affected() > 0)
+    }
+}
+
+fn webhook_key() -> Result<LessSafeKey> {
+    let key = BotConfig::get()
+        .tokens
+        .webhook_key
+        .as_ref()
+        .ok_or_else(|| eyre!("missing webhook key"))?;
+
+    UnboundKey::new(&CHACHA20_POLY1305, key)
+        .map(LessSafeKey::new)
+        .map_err(|_| eyre!("invalid webhook key"))
+}
+
+fn encrypt_token(token: &str) -> Result<(Vec<u8>, [u8; NONCE_LEN])> {
+    let mut nonce = [0; NONCE_LEN];
+
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| eyre!("failed to generate nonce"))?;
+
+    let mut in_out = token.as_bytes().to_vec();
+
+    webhook_key()?
+        .seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::empty(),
+            &mut in_out,
+        )
+        .map_err(|_| eyre!("failed to encrypt webhook token"))?;
+
+    Ok((in_out, nonce))
+}
+
+fn decrypt_token(mut token: Vec<u8>, nonce: &[u8]) -> Result<String> {
+    let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| eyre!("invalid nonce"))?;
+
+    let len = webhook_key()?
+        .open_in_place(nonce, Aad::empty(), &mut token)
+        .map_err(|_| eyre!("failed to decrypt webhook token"))?
+        .len();
+
+    token.truncate(len);
+
+    String::from_utf8(token).wrap_err("webhook token is not valid utf-8")
+}
@@ -0,0 +1,15 @@
+/// Background game stats of a user within a guild
+#[derive(Copy, Clone, Default)]
+pub struct BgGuildStats {
+    /// Amount of guessed mapsets
+    pub score: u32,
+    /// Sum of the time it took to guess each mapset
+    pub guess_time_ms: u64,
+    pub hints: u32,
+}
+
+impl BgGuildStats {
+    pub fn avg_guess_time_ms(&self) -> Option<u64> {
+        (self.score > 0).then(|| self.guess_time_ms / self.score as u64)
+    }
+}
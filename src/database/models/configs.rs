@@ -2,6 +2,7 @@ use rosu_v2::prelude::{GameMode, Username};
 use smallstr::SmallString;
 use smallvec::SmallVec;
 use twilight_interactions::command::{CommandOption, CreateOption};
+use twilight_model::id::{marker::ChannelMarker, Id};
 
 use crate::commands::osu::ProfileSize;
 
@@ -85,7 +86,13 @@ impl Default for ListSize {
 #[derive(Debug, Clone)]
 pub struct GuildConfig {
     pub authorities: Authorities,
+    pub digest_channel: Option<Id<ChannelMarker>>,
+    /// Days from monday
+    pub digest_day: Option<u8>,
+    /// Hour in UTC
+    pub digest_hour: Option<u8>,
     pub embeds_size: Option<EmbedsSize>,
+    pub leaderboard_refresh: Option<bool>,
     pub list_size: Option<ListSize>,
     pub minimized_pp: Option<MinimizedPp>,
     pub prefixes: Prefixes,
@@ -100,10 +107,25 @@ impl GuildConfig {
         self.with_lyrics.unwrap_or(true)
     }
 
+    /// Channel, days from monday, and hour in UTC of the weekly digest
+    pub fn digest(&self) -> Option<(Id<ChannelMarker>, u8, u8)> {
+        let channel = self.digest_channel?;
+
+        Some((
+            channel,
+            self.digest_day.unwrap_or(0),
+            self.digest_hour.unwrap_or(12),
+        ))
+    }
+
     pub fn embeds_size(&self) -> EmbedsSize {
         self.embeds_size.unwrap_or_default()
     }
 
+    pub fn leaderboard_refresh(&self) -> bool {
+        self.leaderboard_refresh.unwrap_or(false)
+    }
+
     pub fn list_size(&self) -> ListSize {
         self.list_size.unwrap_or_default()
     }
@@ -129,7 +151,11 @@ impl Default for GuildConfig {
     fn default() -> Self {
         GuildConfig {
             authorities: SmallVec::new(),
+            digest_channel: None,
+            digest_day: None,
+            digest_hour: None,
             embeds_size: None,
+            leaderboard_refresh: None,
             list_size: None,
             minimized_pp: None,
             prefixes: smallvec::smallvec!["<".into()],
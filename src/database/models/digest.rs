@@ -0,0 +1,22 @@
+use rosu_v2::prelude::Username;
+use time::OffsetDateTime;
+
+/// osu!standard stats of a linked guild member
+pub struct DigestMember {
+    pub user_id: u32,
+    pub username: Username,
+    pub pp: f32,
+    pub global_rank: u32,
+    /// In seconds
+    pub playtime: u32,
+}
+
+/// Stats of a guild member at the time of the previous digest
+pub struct DigestValue {
+    pub user_id: u32,
+    pub pp: f32,
+    pub global_rank: u32,
+    /// In seconds
+    pub playtime: u32,
+    pub taken_at: OffsetDateTime,
+}
@@ -0,0 +1,5 @@
+/// A named list of multiplayer matches, e.g. all matches of a tournament stage
+pub struct SavedMatchStage {
+    pub name: String,
+    pub match_ids: Vec<u32>,
+}
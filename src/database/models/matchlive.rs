@@ -0,0 +1,18 @@
+use twilight_model::id::{
+    marker::{ChannelMarker, MessageMarker},
+    Id,
+};
+
+/// A match that is live-tracked in a channel
+pub struct MatchLiveTrack {
+    pub match_id: u32,
+    pub channel: Id<ChannelMarker>,
+    /// Last message of the match in the channel
+    pub msg: Id<MessageMarker>,
+    /// Id of the latest match event that was already processed
+    pub last_event_id: u64,
+    /// Amount of games to ignore for the scoreboard
+    pub warmups: usize,
+    /// Msg containing the scoreboard
+    pub scoreboard: Option<Id<MessageMarker>>,
+}
@@ -1,20 +1,40 @@
 pub use self::{
     beatmap::{DBBeatmap, DBBeatmapset},
+    bggame::BgGuildStats,
     configs::{
         Authorities, EmbedsSize, GuildConfig, ListSize, MinimizedPp, OsuData, Prefix, Prefixes,
         UserConfig,
     },
+    digest::{DigestMember, DigestValue},
     map_tags::{MapsetTagWrapper, TagRow},
-    osu_users::{UserStatsColumn, UserValueRaw},
+    match_stage::SavedMatchStage,
+    osu_users::{RankedOsuUser, UserStatsColumn, UserStatsSnapshot, UserValueRaw},
+    streams::{StreamTrack, StreamTrackSettings, TrackedStreams},
 };
 
+#[cfg(feature = "matchlive")]
+pub use self::matchlive::MatchLiveTrack;
+
 #[cfg(feature = "osutracking")]
-pub use self::tracking::TrackingUser;
+pub use self::tracking::{TrackingFilters, TrackingNotification, TrackingUser};
+
+#[cfg(any(feature = "osutracking", feature = "twitchtracking"))]
+pub use self::webhooks::{TrackingWebhook, TrackingWebhooks};
 
 mod beatmap;
+mod bggame;
 mod configs;
+mod digest;
 mod map_tags;
+mod match_stage;
 mod osu_users;
+mod streams;
+
+#[cfg(feature = "matchlive")]
+mod matchlive;
 
 #[cfg(feature = "osutracking")]
 mod tracking;
+
+#[cfg(any(feature = "osutracking", feature = "twitchtracking"))]
+mod webhooks;
@@ -1,4 +1,5 @@
 use rosu_v2::prelude::{GameMode, Username};
+use time::OffsetDateTime;
 
 use crate::util::CountryCode;
 
@@ -128,8 +129,28 @@ impl UserStatsColumn {
     }
 }
 
+/// A user whose cached stats include a global rank
+pub struct RankedOsuUser {
+    pub user_id: u32,
+    pub username: Username,
+    pub country_code: CountryCode,
+    pub global_rank: u32,
+}
+
 pub struct UserValueRaw<T> {
     pub username: Username,
     pub country_code: CountryCode,
     pub value: T,
 }
+
+/// Stats of a user in a mode at some point in time
+#[derive(Copy, Clone)]
+pub struct UserStatsSnapshot {
+    pub taken_at: OffsetDateTime,
+    pub pp: f32,
+    /// 0 if the user had no rank
+    pub global_rank: u32,
+    pub accuracy: f32,
+    pub playcount: u32,
+    pub ranked_score: u64,
+}
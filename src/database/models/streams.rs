@@ -0,0 +1,80 @@
+use std::fmt;
+
+use flurry::HashMap as FlurryMap;
+use twilight_model::id::{
+    marker::{ChannelMarker, RoleMarker},
+    Id,
+};
+
+use crate::util::{constants::TWITCH_OSU_GAME_ID, hasher::IntHasher};
+
+/// Twitch user ids mapped to all channels tracking them
+pub type TrackedStreams = FlurryMap<u64, Vec<StreamTrack>, IntHasher>;
+
+#[derive(Clone, Debug)]
+pub struct StreamTrack {
+    pub channel: Id<ChannelMarker>,
+    pub settings: StreamTrackSettings,
+}
+
+/// Determines when and how a channel is notified about a stream
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StreamTrackSettings {
+    /// Only notify if the stream's category is osu!
+    pub osu_only: bool,
+    /// Only notify if the stream's title contains this keyword, stored in lowercase
+    pub title_filter: Option<String>,
+    /// Edit the notification once the stream ends to show its duration and VOD
+    pub vod_edit: bool,
+    /// Role to ping alongside the notification
+    pub role: Option<Id<RoleMarker>>,
+}
+
+impl StreamTrackSettings {
+    /// Check whether a stream with the given game and title passes all filters
+    pub fn matches(&self, game_id: Option<u64>, title: &str) -> bool {
+        if self.osu_only && game_id != Some(TWITCH_OSU_GAME_ID) {
+            return false;
+        }
+
+        match self.title_filter {
+            Some(ref keyword) => title.to_lowercase().contains(keyword.as_str()),
+            None => true,
+        }
+    }
+}
+
+impl Default for StreamTrackSettings {
+    fn default() -> Self {
+        Self {
+            osu_only: false,
+            title_filter: None,
+            vod_edit: true,
+            role: None,
+        }
+    }
+}
+
+impl fmt::Display for StreamTrackSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut options = Vec::with_capacity(4);
+
+        if self.osu_only {
+            options.push("osu! only".to_owned());
+        }
+
+        if let Some(ref keyword) = self.title_filter {
+            options.push(format!("title contains `{keyword}`"));
+        }
+
+        if !self.vod_edit {
+            options.push("no VOD".to_owned());
+        }
+
+        if let Some(role) = self.role {
+            options.push(format!("pings <@&{role}>"));
+        }
+
+        f.write_str(&options.join(", "))
+    }
+}
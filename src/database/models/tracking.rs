@@ -1,41 +1,51 @@
 #![cfg(feature = "osutracking")]
 
-use std::collections::HashMap as StdHashMap;
+use std::{collections::HashMap as StdHashMap, fmt};
 
 use eyre::Report;
 use hashbrown::HashMap;
-use rosu_v2::model::GameMode;
+use rosu_v2::prelude::{GameMode, GameMods, Score, Username};
+use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 use sqlx::{types::Json, ColumnIndex, Decode, Error, FromRow, Row, Type};
-use time::OffsetDateTime;
+use time::{Duration, OffsetDateTime};
 use twilight_model::id::{marker::ChannelMarker, Id};
 
-use crate::util::hasher::IntHasher;
+use crate::{
+    tracking::{TrackingEntry, TrackingKind},
+    util::{hasher::IntHasher, matcher, osu::ModSelection},
+};
 
 #[derive(Debug)]
 pub struct TrackingUser {
     pub user_id: u32,
     pub mode: GameMode,
+    pub kind: TrackingKind,
+    /// Date of the latest score that was tracked, depending on `kind`
     pub last_top_score: OffsetDateTime,
-    pub channels: HashMap<Id<ChannelMarker>, usize, IntHasher>,
+    pub channels: HashMap<Id<ChannelMarker>, TrackingFilters, IntHasher>,
+    /// Time between two checks of the user, adjusted based on their activity
+    pub interval: Duration,
 }
 
 impl TrackingUser {
     pub fn new(
-        user_id: u32,
-        mode: GameMode,
+        entry: TrackingEntry,
         last_top_score: OffsetDateTime,
         channel: Id<ChannelMarker>,
-        limit: usize,
+        filters: TrackingFilters,
+        interval: Duration,
     ) -> Self {
         let mut channels = HashMap::default();
-        channels.insert(channel, limit);
+        channels.insert(channel, filters);
 
         Self {
-            user_id,
-            mode,
+            user_id: entry.user_id,
+            mode: entry.mode,
+            kind: entry.kind,
             last_top_score,
             channels,
+            interval,
         }
     }
 
@@ -44,12 +54,191 @@ impl TrackingUser {
     }
 }
 
+/// Determines which tracked scores of a user will be notified in a channel
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TrackingFilters {
+    /// Maximum index within the top scores or recent scores
+    pub limit: usize,
+    pub min_pp: Option<f32>,
+    pub min_stars: Option<f32>,
+    pub mods: Option<ModSelection>,
+}
+
+impl TrackingFilters {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            min_pp: None,
+            min_stars: None,
+            mods: None,
+        }
+    }
+
+    /// Check whether the score at the given index of the top scores passes
+    /// all filters except for the star rating, see [`matches_stars`].
+    ///
+    /// [`matches_stars`]: TrackingFilters::matches_stars
+    pub fn matches(&self, score: &Score, idx: usize) -> bool {
+        if idx > self.limit {
+            return false;
+        }
+
+        if let Some(min_pp) = self.min_pp {
+            if score.pp.unwrap_or(0.0) < min_pp {
+                return false;
+            }
+        }
+
+        match self.mods {
+            None => true,
+            Some(ModSelection::Include(mods @ GameMods::NoMod) | ModSelection::Exact(mods)) => {
+                score.mods == mods
+            }
+            Some(ModSelection::Include(mods)) => score.mods.contains(mods),
+            Some(ModSelection::Exclude(GameMods::NoMod)) => !score.mods.is_empty(),
+            Some(ModSelection::Exclude(mods)) => score.mods.intersection(mods).is_empty(),
+        }
+    }
+
+    /// Check whether the score passes the star rating filter.
+    ///
+    /// `stars` should contain the mod-adjusted star rating if available,
+    /// otherwise the map's nomod star rating will be used.
+    pub fn matches_stars(&self, score: &Score, stars: Option<f32>) -> bool {
+        match self.min_stars {
+            Some(min_stars) => {
+                let stars = stars.or_else(|| score.map.as_ref().map(|map| map.stars));
+
+                stars.unwrap_or(0.0) >= min_stars
+            }
+            None => true,
+        }
+    }
+}
+
+impl fmt::Display for TrackingFilters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.limit)?;
+
+        if let Some(min_pp) = self.min_pp {
+            write!(f, " | ≥{min_pp}pp")?;
+        }
+
+        if let Some(min_stars) = self.min_stars {
+            write!(f, " | ≥{min_stars}★")?;
+        }
+
+        if let Some(mods) = self.mods {
+            write!(f, " | {mods}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Filters are stored as plain limit if no other filter is set,
+/// otherwise as object with mods in their `+mods` / `+mods!` / `-mods!` form.
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum TrackingFiltersRaw {
+    Limit(usize),
+    Filters {
+        limit: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_pp: Option<f32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_stars: Option<f32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        mods: Option<String>,
+    },
+}
+
+impl Serialize for TrackingFilters {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let raw = match self {
+            Self {
+                limit,
+                min_pp: None,
+                min_stars: None,
+                mods: None,
+            } => TrackingFiltersRaw::Limit(*limit),
+            Self {
+                limit,
+                min_pp,
+                min_stars,
+                mods,
+            } => TrackingFiltersRaw::Filters {
+                limit: *limit,
+                min_pp: *min_pp,
+                min_stars: *min_stars,
+                mods: mods.map(|mods| mods.to_string()),
+            },
+        };
+
+        raw.serialize(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for TrackingFilters {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let filters = match TrackingFiltersRaw::deserialize(d)? {
+            TrackingFiltersRaw::Limit(limit) => Self::new(limit),
+            TrackingFiltersRaw::Filters {
+                limit,
+                min_pp,
+                min_stars,
+                mods,
+            } => {
+                // Dropping an invalid mods filter would send notifications
+                // that the channel opted out of so it's an error instead
+                let mods = match mods {
+                    Some(mods) => match matcher::get_mods(&mods) {
+                        Some(mods) => Some(mods),
+                        None => return Err(DeError::custom(format!("invalid mods `{mods}`"))),
+                    },
+                    None => None,
+                };
+
+                Self {
+                    limit,
+                    min_pp,
+                    min_stars,
+                    mods,
+                }
+            }
+        };
+
+        Ok(filters)
+    }
+}
+
+/// A score notification that was sent to tracking channels
+#[derive(Debug)]
+pub struct TrackingNotification {
+    pub user_id: u32,
+    pub username: Username,
+    pub mode: GameMode,
+    pub score_id: u64,
+    pub map_id: u32,
+    pub mapset_id: u32,
+    pub map_title: String,
+    pub pp: Option<f32>,
+    pub kind: TrackingKind,
+    /// Index within the top scores or within the recent scores, depending on `kind`
+    pub top_idx: usize,
+    pub ended_at: OffsetDateTime,
+    /// When the notification was first sent
+    pub notified_at: OffsetDateTime,
+}
+
 impl<'r, R> FromRow<'r, R> for TrackingUser
 where
     R: Row,
     usize: ColumnIndex<R>,
     i8: Type<<R as Row>::Database>,
     i8: Decode<'r, <R as Row>::Database>,
+    i32: Type<<R as Row>::Database>,
+    i32: Decode<'r, <R as Row>::Database>,
     u32: Type<<R as Row>::Database>,
     u32: Decode<'r, <R as Row>::Database>,
     OffsetDateTime: Type<<R as Row>::Database>,
@@ -62,26 +251,34 @@ where
         let mode: i8 = row.try_get(1)?;
         let mode = GameMode::from(mode as u8);
         let last_top_score: OffsetDateTime = row.try_get(2)?;
+        let kind: i8 = row.try_get(4)?;
+        let kind = TrackingKind::from(kind as i16);
+        let interval: i32 = row.try_get(5)?;
+        let interval = Duration::seconds(interval as i64);
 
-        let channels = match serde_json::from_value::<StdHashMap<String, usize>>(row.try_get(3)?) {
-            Ok(channels) => channels
-                .into_iter()
-                .map(|(id, limit)| (Id::new(id.parse().unwrap()), limit))
-                .collect(),
-            Err(err) => {
-                let wrap =
-                    format!("failed to deserialize tracking channels value for ({user_id},{mode})");
-                warn!("{:?}", Report::new(err).wrap_err(wrap));
-
-                HashMap::default()
-            }
-        };
+        let channels =
+            match serde_json::from_value::<StdHashMap<String, TrackingFilters>>(row.try_get(3)?) {
+                Ok(channels) => channels
+                    .into_iter()
+                    .map(|(id, filters)| (Id::new(id.parse().unwrap()), filters))
+                    .collect(),
+                Err(err) => {
+                    let wrap = format!(
+                        "failed to deserialize tracking channels value for ({user_id},{mode})"
+                    );
+                    warn!("{:?}", Report::new(err).wrap_err(wrap));
+
+                    HashMap::default()
+                }
+            };
 
         Ok(Self {
             user_id,
             mode,
+            kind,
             last_top_score,
             channels,
+            interval,
         })
     }
 }
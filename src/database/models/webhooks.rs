@@ -0,0 +1,18 @@
+use flurry::HashMap as FlurryMap;
+use twilight_model::id::{
+    marker::{ChannelMarker, WebhookMarker},
+    Id,
+};
+
+use crate::util::hasher::IntHasher;
+
+pub type TrackingWebhooks = FlurryMap<Id<ChannelMarker>, TrackingWebhook, IntHasher>;
+
+/// A webhook through which tracking notifications of a channel are delivered
+#[derive(Clone, Debug)]
+pub struct TrackingWebhook {
+    pub webhook_id: Id<WebhookMarker>,
+    pub token: String,
+    pub username: Option<String>,
+    pub avatar_url: Option<String>,
+}
@@ -0,0 +1,113 @@
+use std::{cmp::Ordering, fmt::Write};
+
+use command_macros::EmbedData;
+use twilight_model::channel::embed::EmbedField;
+
+use crate::{
+    games::bg::{Scoreboard, Team},
+    util::builder::FooterBuilder,
+};
+
+const MAX_PLAYERS: usize = 15;
+
+#[derive(EmbedData)]
+pub struct BgScoreboardEmbed {
+    description: String,
+    fields: Vec<EmbedField>,
+    footer: FooterBuilder,
+    title: String,
+}
+
+impl BgScoreboardEmbed {
+    pub fn new(scoreboard: &Scoreboard, finished: bool) -> Self {
+        let rounds = scoreboard.settings.rounds;
+
+        let title = if finished {
+            format!(
+                "Background game results ({}/{rounds} rounds)",
+                scoreboard.round
+            )
+        } else {
+            format!("Background game: Round {}/{rounds}", scoreboard.round + 1)
+        };
+
+        let mut description = String::with_capacity(256);
+        let mut fields = Vec::new();
+
+        if scoreboard.settings.teams {
+            let red = scoreboard.team_score(Team::Red);
+            let blue = scoreboard.team_score(Team::Blue);
+
+            if finished {
+                let _ = match red.cmp(&blue) {
+                    Ordering::Greater => writeln!(description, "**Team Red wins!**"),
+                    Ordering::Less => writeln!(description, "**Team Blue wins!**"),
+                    Ordering::Equal => writeln!(description, "**It's a draw!**"),
+                };
+            }
+
+            let _ = write!(description, "🔴 Red: **{red}** | 🔵 Blue: **{blue}**");
+
+            for team in [Team::Red, Team::Blue] {
+                let members = scoreboard.team_members(team);
+                let mut value = String::with_capacity(members.len() * 32);
+
+                for user in members.iter().take(MAX_PLAYERS) {
+                    let _ = writeln!(value, "<@{user}>: {}", scoreboard.score(*user));
+                }
+
+                if members.len() > MAX_PLAYERS {
+                    let _ = write!(value, "and {} more...", members.len() - MAX_PLAYERS);
+                } else if members.is_empty() {
+                    value.push_str("No members yet");
+                }
+
+                fields.push(EmbedField {
+                    inline: true,
+                    name: format!("Team {}", team.name()),
+                    value,
+                });
+            }
+        } else {
+            let ranking = scoreboard.ranking();
+
+            if ranking.is_empty() {
+                description.push_str("Nobody scored a point yet");
+            }
+
+            for (i, (user, score)) in ranking.iter().take(MAX_PLAYERS).enumerate() {
+                let _ = writeln!(description, "**{}.** <@{user}>: {score}", i + 1);
+            }
+
+            if ranking.len() > MAX_PLAYERS {
+                let _ = write!(description, "and {} more...", ranking.len() - MAX_PLAYERS);
+            }
+
+            if finished {
+                if let Some((user, _)) = ranking.first() {
+                    description.insert_str(0, &format!("**<@{user}> wins!**\n"));
+                }
+            }
+        }
+
+        let footer = if finished {
+            "Thanks for playing o/".to_owned()
+        } else {
+            let secs = scoreboard.settings.round_time.as_secs();
+            let mut footer = format!("{secs} seconds per round");
+
+            if scoreboard.settings.teams {
+                footer.push_str(" • Join a team with the buttons below");
+            }
+
+            footer
+        };
+
+        Self {
+            description,
+            fields,
+            footer: FooterBuilder::new(footer),
+            title,
+        }
+    }
+}
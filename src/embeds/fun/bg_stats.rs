@@ -0,0 +1,55 @@
+use command_macros::EmbedData;
+use twilight_model::{
+    channel::embed::EmbedField,
+    id::{marker::UserMarker, Id},
+};
+
+use crate::{database::BgGuildStats, util::numbers::with_comma_int};
+
+#[derive(EmbedData)]
+pub struct BgStatsEmbed {
+    description: String,
+    fields: Vec<EmbedField>,
+    title: String,
+}
+
+impl BgStatsEmbed {
+    /// Ranks are given as `(rank, total)`
+    pub fn new(
+        user: Id<UserMarker>,
+        guild_name: &str,
+        stats: BgGuildStats,
+        guild_rank: Option<(usize, usize)>,
+        global: Option<(u32, usize)>,
+    ) -> Self {
+        let avg_guess_time = match stats.avg_guess_time_ms() {
+            Some(ms) => format!("{:.2}s", ms as f64 / 1000.0),
+            None => "-".to_owned(),
+        };
+
+        let guild_rank = match guild_rank {
+            Some((rank, total)) => format!("#{rank} / {total}"),
+            None => "-".to_owned(),
+        };
+
+        let (global_score, global_rank) = match global {
+            Some((score, rank)) => (with_comma_int(score).to_string(), format!("#{rank}")),
+            None => ("0".to_owned(), "-".to_owned()),
+        };
+
+        let fields = fields![
+            "Mapsets guessed", with_comma_int(stats.score).to_string(), true;
+            "Average guess time", avg_guess_time, true;
+            "Hints used", with_comma_int(stats.hints).to_string(), true;
+            "Server rank", guild_rank, true;
+            "All-time score", global_score, true;
+            "Global rank", global_rank, true;
+        ];
+
+        Self {
+            description: format!("<@{user}>"),
+            fields,
+            title: format!("Background game stats in {guild_name}"),
+        }
+    }
+}
@@ -53,7 +53,7 @@ impl BGTagsEmbed {
             .then_some("No stored backgrounds match these tags, try different ones")
             .unwrap_or_default();
 
-        let footer = FooterBuilder::new(format!("Difficulty: {difficulty:?}"));
+        let footer = FooterBuilder::new(format!("Difficulty: {difficulty}"));
 
         Self {
             description,
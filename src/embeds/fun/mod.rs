@@ -1,3 +1,5 @@
+mod bg_scoreboard;
+mod bg_stats;
 mod bg_tags;
 
-pub use bg_tags::BGTagsEmbed;
+pub use self::{bg_scoreboard::BgScoreboardEmbed, bg_stats::BgStatsEmbed, bg_tags::BGTagsEmbed};
@@ -1,7 +1,7 @@
-use std::{fmt::Write, mem};
+use std::{collections::HashMap, fmt::Write};
 
 use command_macros::EmbedData;
-use rosu_v2::model::matches::OsuMatch;
+use rosu_v2::{model::matches::OsuMatch, prelude::UserCompact};
 
 use crate::{
     commands::osu::MatchResult,
@@ -23,7 +23,25 @@ pub struct MatchCostEmbed {
 
 impl MatchCostEmbed {
     pub fn new(
-        osu_match: &mut OsuMatch,
+        osu_match: &OsuMatch,
+        description: Option<String>,
+        match_result: Option<MatchResult>,
+    ) -> Option<Self> {
+        Self::from_parts(
+            osu_match.match_id,
+            &osu_match.name,
+            osu_match.end_time.is_some(),
+            &osu_match.users,
+            description,
+            match_result,
+        )
+    }
+
+    pub fn from_parts(
+        match_id: u32,
+        title: &str,
+        finished: bool,
+        users: &HashMap<u32, UserCompact>,
         description: Option<String>,
         match_result: Option<MatchResult>,
     ) -> Option<Self> {
@@ -47,11 +65,7 @@ impl MatchCostEmbed {
                         description,
                         "**{word} score:** :blue_circle: {blue_stars}{blue_score}{blue_stars} \
                         - {red_stars}{red_score}{red_stars} :red_circle:\n",
-                        word = if osu_match.end_time.is_some() {
-                            "Final"
-                        } else {
-                            "Current"
-                        },
+                        word = if finished { "Final" } else { "Current" },
                         blue_score = match_scores.blue(),
                         red_score = match_scores.red(),
                         blue_stars = if match_scores.blue() > match_scores.red() {
@@ -70,7 +84,7 @@ impl MatchCostEmbed {
                     let _ = writeln!(description, ":blue_circle: **Blue Team** :blue_circle:");
 
                     for ((id, cost), i) in blue.into_iter().zip(1..) {
-                        let name = match osu_match.users.get(&id) {
+                        let name = match users.get(&id) {
                             Some(user) => user.username.cow_escape_markdown(),
                             None => format!("User id {id}").into(),
                         };
@@ -105,7 +119,7 @@ impl MatchCostEmbed {
                     let _ = writeln!(description, "\n:red_circle: **Red Team** :red_circle:");
 
                     for ((id, cost), i) in red.into_iter().zip(1..) {
-                        let name = match osu_match.users.get(&id) {
+                        let name = match users.get(&id) {
                             Some(user) => user.username.cow_escape_markdown(),
                             None => format!("User id {id}").into(),
                         };
@@ -129,7 +143,7 @@ impl MatchCostEmbed {
                     mvp_avatar_url,
                 }) => {
                     for ((id, cost), i) in players.into_iter().zip(1..) {
-                        let name = match osu_match.users.get(&id) {
+                        let name = match users.get(&id) {
                             Some(user) => user.username.cow_escape_markdown(),
                             None => format!("User id {id}").into(),
                         };
@@ -153,11 +167,7 @@ impl MatchCostEmbed {
             description
         };
 
-        let match_id = osu_match.match_id;
-
-        let mut title = mem::take(&mut osu_match.name)
-            .cow_escape_markdown()
-            .into_owned();
+        let mut title = title.cow_escape_markdown().into_owned();
 
         title.retain(|c| c != '(' && c != ')');
         let footer = FooterBuilder::new("Note: Formula is subject to change; values are volatile");
@@ -0,0 +1,102 @@
+use std::fmt::Write;
+
+use command_macros::EmbedData;
+
+use crate::{
+    commands::osu::{MatchStageView, StageStats},
+    pagination::Pages,
+    util::{
+        builder::FooterBuilder,
+        constants::OSU_BASE,
+        numbers::{round, with_comma_int},
+        CowUtils,
+    },
+};
+
+#[derive(EmbedData)]
+pub struct MatchStageEmbed {
+    description: String,
+    footer: FooterBuilder,
+    title: String,
+}
+
+impl MatchStageEmbed {
+    pub fn new(
+        stats: &StageStats,
+        view: MatchStageView,
+        title: &str,
+        formula: &str,
+        pages: &Pages,
+    ) -> Self {
+        let mut description = String::with_capacity(1024);
+        let idx = pages.index;
+
+        match view {
+            MatchStageView::Players => {
+                let end_idx = stats.players.len().min(idx + pages.per_page);
+
+                for (player, i) in stats.players[idx..end_idx].iter().zip(idx + 1..) {
+                    let _ = writeln!(
+                        description,
+                        "**#{i} [{name}]({OSU_BASE}users/{user_id})**: **{rating:.2}**\n\
+                        {matches} match{plural} • {played} maps • {won} won ({win_rate}%)",
+                        name = player.username.cow_escape_markdown(),
+                        user_id = player.user_id,
+                        rating = player.avg_rating,
+                        matches = player.matches,
+                        plural = if player.matches == 1 { "" } else { "es" },
+                        played = player.maps_played,
+                        won = player.maps_won,
+                        win_rate = round(player.win_rate()),
+                    );
+                }
+            }
+            MatchStageView::Maps => {
+                let end_idx = stats.maps.len().min(idx + pages.per_page);
+
+                for (map, i) in stats.maps[idx..end_idx].iter().zip(idx + 1..) {
+                    let _ = write!(
+                        description,
+                        "**#{i} [{title}]({OSU_BASE}b/{map_id})**\n\
+                        Played {picks} time{plural} • Avg score: {score} • Avg acc: {acc}%",
+                        title = map
+                            .title
+                            .as_deref()
+                            .unwrap_or("Unknown map")
+                            .cow_escape_markdown(),
+                        map_id = map.map_id,
+                        picks = map.picks,
+                        plural = if map.picks == 1 { "" } else { "s" },
+                        score = with_comma_int(map.avg_score.round() as u32),
+                        acc = round(map.avg_acc),
+                    );
+
+                    if let Some((ref name, score)) = map.best {
+                        let _ = write!(
+                            description,
+                            " • Best: {name} ({score})",
+                            name = name.cow_escape_markdown(),
+                            score = with_comma_int(score),
+                        );
+                    }
+
+                    description.push('\n');
+                }
+            }
+        }
+
+        let footer_text = format!(
+            "Page {page}/{pages} • {matches} match{plural} • Formula: {formula}",
+            page = pages.curr_page(),
+            pages = pages.last_page(),
+            matches = stats.matches,
+            plural = if stats.matches == 1 { "" } else { "es" },
+        );
+
+        Self {
+            description,
+            footer: FooterBuilder::new(footer_text),
+            title: title.to_owned(),
+        }
+    }
+}
@@ -15,6 +15,7 @@ mod map_search;
 mod match_compare;
 mod match_costs;
 mod match_live;
+mod match_stage;
 mod medal;
 mod medal_stats;
 mod medals_common;
@@ -38,6 +39,7 @@ mod player_snipe_stats;
 mod pp_missing;
 mod profile;
 mod profile_compare;
+mod progress;
 mod rank;
 mod rank_score;
 mod ranking;
@@ -46,6 +48,7 @@ mod ratio;
 mod recent;
 mod recent_list;
 mod scores;
+mod server_digest;
 mod simulate;
 mod sniped;
 mod sniped_difference;
@@ -64,14 +67,15 @@ use crate::util::{datetime::sec_to_minsec, numbers::round, BeatmapExt, ScoreExt}
 pub use self::{
     attributes::*, badge::*, bws::*, card::*, claim_name::*, common::*, compare::*,
     country_snipe_list::*, country_snipe_stats::*, fix_score::*, graph::*, leaderboard::*, map::*,
-    map_search::*, match_compare::*, match_costs::*, medal::*, medal_stats::*, medals_common::*,
-    medals_list::*, medals_missing::*, most_played::*, most_played_common::*, nochoke::*,
-    osekai_medal_count::*, osekai_medal_rarity::*, osustats_counts::*, osustats_globals::*,
-    osustats_list::*, osutracker_countrytop::*, osutracker_mappers::*, osutracker_maps::*,
-    osutracker_mapsets::*, osutracker_mods::*, player_snipe_list::*, player_snipe_stats::*,
-    pp_missing::*, profile::*, profile_compare::*, rank::*, rank_score::*, ranking::*,
-    ranking_countries::*, ratio::*, recent::*, recent_list::*, scores::*, simulate::*, sniped::*,
-    sniped_difference::*, top::*, top_if::*, top_single::*, whatif::*,
+    map_search::*, match_compare::*, match_costs::*, match_stage::*, medal::*, medal_stats::*,
+    medals_common::*, medals_list::*, medals_missing::*, most_played::*, most_played_common::*,
+    nochoke::*, osekai_medal_count::*, osekai_medal_rarity::*, osustats_counts::*,
+    osustats_globals::*, osustats_list::*, osutracker_countrytop::*, osutracker_mappers::*,
+    osutracker_maps::*, osutracker_mapsets::*, osutracker_mods::*, player_snipe_list::*,
+    player_snipe_stats::*, pp_missing::*, profile::*, profile_compare::*, progress::*, rank::*,
+    rank_score::*, ranking::*, ranking_countries::*, ratio::*, recent::*, recent_list::*,
+    scores::*, server_digest::*, simulate::*, sniped::*, sniped_difference::*, top::*, top_if::*,
+    top_single::*, whatif::*,
 };
 
 #[cfg(feature = "matchlive")]
@@ -0,0 +1,141 @@
+use std::fmt::{Display, Write};
+
+use command_macros::EmbedData;
+use rosu_v2::prelude::User;
+use time::OffsetDateTime;
+
+use crate::{
+    commands::osu::ProgressPeriod,
+    database::UserStatsSnapshot,
+    embeds::attachment,
+    util::{
+        builder::{AuthorBuilder, FooterBuilder},
+        datetime::how_long_ago_text,
+        numbers::{round, with_comma_float, with_comma_int},
+    },
+};
+
+#[derive(EmbedData)]
+pub struct ProgressEmbed {
+    author: AuthorBuilder,
+    description: String,
+    footer: FooterBuilder,
+    image: String,
+    thumbnail: String,
+}
+
+impl ProgressEmbed {
+    pub fn new(
+        user: &User,
+        first: &UserStatsSnapshot,
+        last: &UserStatsSnapshot,
+        since: OffsetDateTime,
+        period: ProgressPeriod,
+        graph: bool,
+    ) -> Self {
+        let mut description = String::with_capacity(256);
+
+        let _ = writeln!(description, "**Progress over the last {}:**", period.name());
+
+        line(
+            &mut description,
+            "PP",
+            with_comma_float(first.pp),
+            with_comma_float(last.pp),
+            signed_float(last.pp - first.pp),
+        );
+
+        let rank_gain = match (first.global_rank, last.global_rank) {
+            (0, _) | (_, 0) => "-".to_owned(),
+            (first, last) => signed_int(first as i64 - last as i64),
+        };
+
+        line(
+            &mut description,
+            "Rank",
+            rank(first.global_rank),
+            rank(last.global_rank),
+            rank_gain,
+        );
+
+        line(
+            &mut description,
+            "Accuracy",
+            format_args!("{}%", round(first.accuracy)),
+            format_args!("{}%", round(last.accuracy)),
+            format_args!("{}%", signed_float(last.accuracy - first.accuracy)),
+        );
+
+        line(
+            &mut description,
+            "Playcount",
+            with_comma_int(first.playcount),
+            with_comma_int(last.playcount),
+            signed_int(last.playcount as i64 - first.playcount as i64),
+        );
+
+        line(
+            &mut description,
+            "Ranked score",
+            with_comma_int(first.ranked_score),
+            with_comma_int(last.ranked_score),
+            signed_int(last.ranked_score as i64 - first.ranked_score as i64),
+        );
+
+        if first.taken_at > since {
+            let _ = write!(
+                description,
+                "\n*The oldest available data is from {}*",
+                how_long_ago_text(&first.taken_at),
+            );
+        }
+
+        let image = if graph {
+            attachment("progress.png")
+        } else {
+            String::new()
+        };
+
+        Self {
+            author: author!(user),
+            description,
+            footer: FooterBuilder::new(format!("Mode: {}", user.mode)),
+            image,
+            thumbnail: user.avatar_url.to_owned(),
+        }
+    }
+}
+
+fn line(
+    description: &mut String,
+    name: &str,
+    first: impl Display,
+    last: impl Display,
+    gain: impl Display,
+) {
+    let _ = writeln!(description, "**{name}:** {first} → {last} (**{gain}**)");
+}
+
+fn rank(rank: u32) -> String {
+    if rank == 0 {
+        "-".to_owned()
+    } else {
+        format!("#{}", with_comma_int(rank))
+    }
+}
+
+fn signed_float(n: f32) -> String {
+    if n < 0.0 {
+        format!("-{}", with_comma_float(-n))
+    } else {
+        format!("+{}", with_comma_float(n))
+    }
+}
+
+fn signed_int(n: i64) -> String {
+    if n < 0 {
+        with_comma_int(n).to_string()
+    } else {
+        format!("+{}", with_comma_int(n))
+    }
+}
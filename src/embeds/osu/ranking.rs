@@ -49,6 +49,7 @@ pub enum RankingKindData {
     HlScores {
         scores: Vec<(u64, u32)>,
         version: HlVersion,
+        daily: bool,
     },
     OsekaiRarity,
     OsekaiMedalCount,
@@ -88,10 +89,19 @@ impl RankingKindData {
 
                 EmbedHeader::Author(AuthorBuilder::new(text))
             }
-            Self::HlScores { version, .. } => {
-                let text = match version {
-                    HlVersion::ScorePp => "Server leaderboard for Higherlower (Score PP)",
-                    HlVersion::FarmMaps => "Server leaderboard for Higherlower (Farm)",
+            Self::HlScores { version, daily, .. } => {
+                let version = match version {
+                    HlVersion::ScorePp => "Score PP",
+                    HlVersion::FarmMaps => "Farm",
+                    HlVersion::RankPlayers => "Rank",
+                    HlVersion::MapPlaycount => "Playcount",
+                    HlVersion::MapStars => "Stars",
+                };
+
+                let text = if *daily {
+                    format!("Leaderboard for today's daily Higherlower challenge ({version})")
+                } else {
+                    format!("Server leaderboard for Higherlower ({version})")
                 };
 
                 EmbedHeader::Author(AuthorBuilder::new(text))
@@ -0,0 +1,179 @@
+use std::{cmp::Ordering, fmt::Write};
+
+use command_macros::EmbedData;
+use hashbrown::HashMap;
+use twilight_model::channel::embed::EmbedField;
+
+use crate::{
+    database::{DigestMember, DigestValue},
+    util::{
+        builder::{AuthorBuilder, FooterBuilder},
+        constants::OSU_BASE,
+        datetime::how_long_ago_text,
+        hasher::IntHasher,
+        numbers::{with_comma_float, with_comma_int},
+        CowUtils,
+    },
+};
+
+const TOP_COUNT: usize = 5;
+const NEW_MEMBERS_COUNT: usize = 15;
+
+#[derive(EmbedData)]
+pub struct ServerDigestEmbed {
+    author: AuthorBuilder,
+    description: String,
+    fields: Vec<EmbedField>,
+    footer: FooterBuilder,
+    title: &'static str,
+}
+
+impl ServerDigestEmbed {
+    pub fn new(
+        guild_name: String,
+        guild_icon: Option<String>,
+        members: &[DigestMember],
+        previous: &[DigestValue],
+    ) -> Self {
+        let mut author = AuthorBuilder::new(guild_name);
+
+        if let Some(icon) = guild_icon {
+            author = author.icon_url(icon);
+        }
+
+        let previous: HashMap<_, _, IntHasher> = previous
+            .iter()
+            .map(|value| (value.user_id, value))
+            .collect();
+
+        let since = previous.values().map(|value| value.taken_at).min();
+
+        let mut pp_gains = Vec::new();
+        let mut rank_gains = Vec::new();
+        let mut playtime_gains = Vec::new();
+        let mut new_members = Vec::new();
+
+        for member in members {
+            match previous.get(&member.user_id) {
+                Some(prev) => {
+                    let pp = member.pp - prev.pp;
+
+                    if pp > 0.0 {
+                        pp_gains.push((member, pp));
+                    }
+
+                    if prev.global_rank > 0 && member.global_rank > 0 {
+                        let ranks = prev.global_rank as i64 - member.global_rank as i64;
+
+                        if ranks > 0 {
+                            rank_gains.push((member, ranks));
+                        }
+                    }
+
+                    let playtime = member.playtime.saturating_sub(prev.playtime);
+
+                    if playtime > 0 {
+                        playtime_gains.push((member, playtime));
+                    }
+                }
+                None => new_members.push(member),
+            }
+        }
+
+        pp_gains.sort_unstable_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+        rank_gains.sort_unstable_by(|(_, a), (_, b)| b.cmp(a));
+        playtime_gains.sort_unstable_by(|(_, a), (_, b)| b.cmp(a));
+        new_members.sort_unstable_by(|a, b| b.pp.partial_cmp(&a.pp).unwrap_or(Ordering::Equal));
+
+        let description = match since {
+            Some(since) => format!(
+                "Changes of linked members since the last digest {}",
+                how_long_ago_text(&since)
+            ),
+            None => "First digest of this server, from now on changes will be tracked".to_owned(),
+        };
+
+        let mut fields = Vec::with_capacity(4);
+
+        if since.is_some() {
+            let value = list(&pp_gains, |pp| format!("+{}pp", with_comma_float(*pp)));
+            fields.push(field("Biggest pp gainers", value));
+
+            let value = list(&rank_gains, |ranks| {
+                format!("+{} ranks", with_comma_int(*ranks))
+            });
+            fields.push(field("Rank climbers", value));
+
+            let value = list(&playtime_gains, |secs| {
+                format!("{} hrs", with_comma_float(*secs as f32 / 3600.0))
+            });
+            fields.push(field("Most playtime", value));
+        }
+
+        let value = if new_members.is_empty() {
+            "None".to_owned()
+        } else {
+            let mut value = String::with_capacity(new_members.len().min(NEW_MEMBERS_COUNT) * 32);
+
+            for member in new_members.iter().take(NEW_MEMBERS_COUNT) {
+                let _ = writeln!(
+                    value,
+                    "[{name}]({OSU_BASE}u/{user_id}): {pp}pp",
+                    name = member.username.cow_escape_markdown(),
+                    user_id = member.user_id,
+                    pp = with_comma_float(member.pp),
+                );
+            }
+
+            if new_members.len() > NEW_MEMBERS_COUNT {
+                let _ = write!(value, "and {} more", new_members.len() - NEW_MEMBERS_COUNT);
+            }
+
+            value
+        };
+
+        let name = if since.is_some() {
+            "New linked members"
+        } else {
+            "Linked members"
+        };
+
+        fields.push(field(name, value));
+
+        Self {
+            author,
+            description,
+            fields,
+            footer: FooterBuilder::new("osu!standard stats of linked server members"),
+            title: "Weekly server digest",
+        }
+    }
+}
+
+fn list<T>(entries: &[(&DigestMember, T)], f: impl Fn(&T) -> String) -> String {
+    if entries.is_empty() {
+        return "None".to_owned();
+    }
+
+    let mut value = String::with_capacity(TOP_COUNT * 48);
+
+    for ((member, gain), i) in entries.iter().take(TOP_COUNT).zip(1..) {
+        let _ = writeln!(
+            value,
+            "**{i}.** [{name}]({OSU_BASE}u/{user_id}): **{gain}**",
+            name = member.username.cow_escape_markdown(),
+            user_id = member.user_id,
+            gain = f(gain),
+        );
+    }
+
+    value
+}
+
+fn field(name: &str, value: String) -> EmbedField {
+    EmbedField {
+        inline: false,
+        name: name.to_owned(),
+        value,
+    }
+}
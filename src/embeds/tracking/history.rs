@@ -0,0 +1,63 @@
+use std::fmt::Write;
+
+use command_macros::EmbedData;
+
+use crate::{
+    database::TrackingNotification,
+    pagination::Pages,
+    tracking::TrackingKind,
+    util::{
+        builder::FooterBuilder, constants::OSU_BASE, datetime::how_long_ago_dynamic,
+        numbers::with_comma_float, osu::mode_emote, CowUtils,
+    },
+};
+
+#[derive(EmbedData)]
+pub struct TrackHistoryEmbed {
+    description: String,
+    footer: FooterBuilder,
+    title: &'static str,
+}
+
+impl TrackHistoryEmbed {
+    pub fn new(notifs: &[TrackingNotification], pages: &Pages) -> Self {
+        let mut description = String::with_capacity(notifs.len() * 160);
+
+        for notif in notifs {
+            let _ = write!(
+                description,
+                "{mode} **{name}**: ",
+                mode = mode_emote(notif.mode),
+                name = notif.username.cow_escape_markdown(),
+            );
+
+            let _ = match notif.kind {
+                TrackingKind::Top => writeln!(description, "Personal Best #{}", notif.top_idx),
+                TrackingKind::Recent => writeln!(description, "New pass"),
+            };
+
+            // e.g. loved maps don't give pp
+            let pp = match notif.pp {
+                Some(pp) => format!("{}pp", with_comma_float(pp)),
+                None => "-".to_owned(),
+            };
+
+            let _ = writeln!(
+                description,
+                "[{title}]({OSU_BASE}b/{map_id}) • **{pp}** • {ago}",
+                title = notif.map_title.cow_escape_markdown(),
+                map_id = notif.map_id,
+                ago = how_long_ago_dynamic(&notif.notified_at),
+            );
+        }
+
+        let page = pages.curr_page();
+        let pages = pages.last_page();
+
+        Self {
+            description,
+            footer: FooterBuilder::new(format!("Page {page}/{pages}")),
+            title: "Score notifications in this channel:",
+        }
+    }
+}
@@ -4,7 +4,10 @@ use command_macros::EmbedData;
 use itertools::Itertools;
 use rosu_v2::model::GameMode;
 
-use crate::{commands::tracking::TracklistUserEntry, util::constants::DESCRIPTION_SIZE};
+use crate::{
+    commands::tracking::TracklistUserEntry, tracking::TrackingKind,
+    util::constants::DESCRIPTION_SIZE,
+};
 
 #[derive(EmbedData)]
 pub struct TrackListEmbed {
@@ -15,7 +18,7 @@ pub struct TrackListEmbed {
 impl TrackListEmbed {
     pub fn new(users: Vec<TracklistUserEntry>) -> Vec<Self> {
         let mut embeds = Vec::new();
-        let title = "Tracked osu! users in this channel (limit)";
+        let title = "Tracked osu! users in this channel (filters)";
         let mut description = String::with_capacity(256);
 
         users
@@ -31,9 +34,21 @@ impl TrackListEmbed {
                 };
 
                 description.reserve(256);
-                let mut names = group.map(|entry| (entry.name, entry.limit));
-                let (first_name, first_limit) = names.next().unwrap();
-                let len = description.chars().count() + mode.len() + first_name.chars().count() + 7;
+                let mut names = group.map(|entry| {
+                    let filters = match entry.kind {
+                        TrackingKind::Top => entry.filters.to_string(),
+                        TrackingKind::Recent => format!("recent | {}", entry.filters),
+                    };
+
+                    (entry.name, filters)
+                });
+                let (first_name, first_filters) = names.next().unwrap();
+
+                let len = description.chars().count()
+                    + mode.len()
+                    + first_name.chars().count()
+                    + first_filters.chars().count()
+                    + 7;
 
                 if len > DESCRIPTION_SIZE {
                     embeds.push(Self {
@@ -45,11 +60,14 @@ impl TrackListEmbed {
                 }
 
                 let _ = writeln!(description, "__**{mode}**__");
-                let _ = write!(description, "`{first_name}` ({first_limit})");
+                let _ = write!(description, "`{first_name}` ({first_filters})");
                 let mut with_comma = true;
 
-                for (name, limit) in names {
-                    let len = description.chars().count() + name.chars().count() + 9;
+                for (name, filters) in names {
+                    let len = description.chars().count()
+                        + name.chars().count()
+                        + filters.chars().count()
+                        + 9;
 
                     if len > DESCRIPTION_SIZE {
                         embeds.push(Self {
@@ -64,7 +82,7 @@ impl TrackListEmbed {
 
                     let _ = write!(
                         description,
-                        "{}`{name}` ({limit})",
+                        "{}`{name}` ({filters})",
                         if with_comma { ", " } else { "" },
                     );
 
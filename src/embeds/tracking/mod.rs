@@ -1,10 +1,12 @@
 #![cfg(feature = "osutracking")]
 
+mod history;
 mod list;
 mod notification;
 mod track;
 mod untrack;
 
+pub use history::TrackHistoryEmbed;
 pub use list::TrackListEmbed;
 pub use notification::TrackNotificationEmbed;
 pub use track::TrackEmbed;
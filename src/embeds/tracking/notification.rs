@@ -7,6 +7,7 @@ use crate::{
     core::Context,
     embeds::osu,
     pp::PpCalculator,
+    tracking::TrackingKind,
     util::{
         builder::{AuthorBuilder, FooterBuilder},
         constants::{AVATAR_URL, MAP_THUMB_URL, OSU_BASE},
@@ -30,11 +31,20 @@ pub struct TrackNotificationEmbed {
 }
 
 impl TrackNotificationEmbed {
-    pub async fn new(user: &User, score: &Score, idx: usize, ctx: &Context) -> Self {
+    pub async fn new(
+        user: &User,
+        score: &Score,
+        kind: TrackingKind,
+        idx: usize,
+        ctx: &Context,
+    ) -> Self {
         let map = score.map.as_ref().unwrap();
         let mapset = score.mapset.as_ref().unwrap();
 
-        let description = format!("{} __**Personal Best #{idx}**__", mode_emote(map.mode));
+        let description = match kind {
+            TrackingKind::Top => format!("{} __**Personal Best #{idx}**__", mode_emote(map.mode)),
+            TrackingKind::Recent => format!("{} __**New pass**__", mode_emote(map.mode)),
+        };
 
         let (max_pp, stars) = match PpCalculator::new(ctx, map.map_id).await {
             Ok(base_calc) => {
@@ -4,6 +4,8 @@ use command_macros::EmbedData;
 use rosu_v2::{model::GameMode, prelude::Username};
 use twilight_model::channel::embed::EmbedField;
 
+use crate::{database::TrackingFilters, tracking::TrackingKind};
+
 #[derive(EmbedData)]
 pub struct TrackEmbed {
     fields: Vec<EmbedField>,
@@ -13,12 +15,18 @@ pub struct TrackEmbed {
 impl TrackEmbed {
     pub fn new(
         mode: GameMode,
+        kind: TrackingKind,
         success: Vec<Username>,
         failure: Vec<Username>,
         failed: Option<Username>,
-        limit: usize,
+        filters: TrackingFilters,
     ) -> Self {
-        let title = format!("Top score tracking | mode={} | limit={}", mode, limit);
+        let title = match kind {
+            TrackingKind::Top => format!("Top score tracking | mode={mode} | filters={filters}"),
+            TrackingKind::Recent => {
+                format!("Recent pass tracking | mode={mode} | filters={filters}")
+            }
+        };
         let mut fields = Vec::with_capacity(3);
         let mut iter = success.iter();
 
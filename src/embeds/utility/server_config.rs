@@ -66,12 +66,36 @@ impl ServerConfigEmbed {
         let track_limit = config.track_limit();
         let _ = writeln!(description, "\nDefault track limit: {track_limit}\n```");
 
+        if let Some((channel, day, hour)) = config.digest() {
+            let day = match day {
+                0 => "monday",
+                1 => "tuesday",
+                2 => "wednesday",
+                3 => "thursday",
+                4 => "friday",
+                5 => "saturday",
+                _ => "sunday",
+            };
+
+            let _ = writeln!(
+                description,
+                "Weekly digest: <#{channel}> every {day} at {hour:0>2}:00 UTC"
+            );
+        } else {
+            description.push_str("Weekly digest: disabled\n");
+        }
+
         let fields = vec![
             create_field(
                 "Song commands",
                 config.with_lyrics(),
                 &[(true, "enabled"), (false, "disabled")],
             ),
+            create_field(
+                "Leaderboard refresh",
+                config.leaderboard_refresh(),
+                &[(true, "enabled"), (false, "disabled")],
+            ),
             create_field(
                 "Retries*",
                 config.show_retries(),
@@ -6,6 +6,7 @@ use rosu_v2::prelude::GameMode;
 use twilight_model::channel::embed::{Embed, EmbedField};
 
 use crate::{
+    commands::fun::BgGameKind,
     core::Context,
     embeds::{BGTagsEmbed, EmbedData},
     games::bg::GameWrapper,
@@ -17,7 +18,7 @@ use crate::{
     },
 };
 
-use super::{Effects, GameState, MapsetTags};
+use super::{retain_previews, BgPreset, Effects, GameState, JoinResult, MapsetTags, Team};
 
 pub async fn handle_bg_start_include(
     ctx: &Context,
@@ -85,6 +86,8 @@ pub async fn handle_bg_start_button(
                 effects,
                 excluded,
                 included,
+                kind,
+                settings,
             } => {
                 if *author != component.user_id()? {
                     return Ok(());
@@ -94,7 +97,7 @@ pub async fn handle_bg_start_button(
                     ctx.psql()
                         .get_specific_tags_mapset(GameMode::Osu, *included, *excluded);
 
-                let mapsets = match mapset_fut.await {
+                let mut mapsets = match mapset_fut.await {
                     Ok(mapsets) => mapsets,
                     Err(err) => {
                         let embed = EmbedBuilder::new()
@@ -110,6 +113,10 @@ pub async fn handle_bg_start_button(
                     }
                 };
 
+                if matches!(kind, BgGameKind::Audio) {
+                    retain_previews(&mut mapsets).await;
+                }
+
                 let embed =
                     BGTagsEmbed::new(*included, *excluded, mapsets.len(), *effects, *difficulty)
                         .build();
@@ -131,9 +138,19 @@ pub async fn handle_bg_start_button(
                 );
 
                 let ctx = Arc::clone(&ctx);
-                let game = GameWrapper::new(ctx, channel, mapsets, *effects, *difficulty).await;
+                let game_fut = GameWrapper::new(
+                    ctx,
+                    channel,
+                    mapsets,
+                    *effects,
+                    *difficulty,
+                    *kind,
+                    *settings,
+                );
 
-                entry.insert(GameState::Running { game });
+                entry.insert(GameState::Running {
+                    game: game_fut.await,
+                });
             }
             GameState::Running { .. } => {
                 if let Err(err) = remove_components(&ctx, &component, None).await {
@@ -209,6 +226,11 @@ pub async fn handle_bg_start_effects(
                         "flip_v" => Effects::FlipVertical,
                         "grayscale" => Effects::Grayscale,
                         "invert" => Effects::Invert,
+                        "pixelate" => Effects::Pixelate,
+                        "rotate" => Effects::Rotate,
+                        "channel_shuffle" => Effects::ChannelShuffle,
+                        "scramble" => Effects::Scramble,
+                        "edge_detect" => Effects::EdgeDetect,
                         _ => {
                             warn!("unknown effects `{value}`");
 
@@ -218,24 +240,61 @@ pub async fn handle_bg_start_effects(
             });
 
         let mut embed = component.message.embeds.pop().wrap_err("missing embed")?;
+        set_field(&mut embed, "Effects", effects.join(", "));
 
-        let field_opt = embed
-            .fields
-            .iter_mut()
-            .find(|field| field.name == "Effects");
+        // Effects were picked by hand so they no longer match a preset
+        embed.fields.retain(|field| field.name != "Preset");
 
-        if let Some(field) = field_opt {
-            field.value = effects.join(", ");
-        } else {
-            let field = EmbedField {
-                inline: false,
-                name: "Effects".to_owned(),
-                value: effects.join(", "),
-            };
+        let builder = MessageBuilder::new().embed(embed);
+        component
+            .callback(ctx, builder)
+            .await
+            .wrap_err("failed to callback")?;
+    } else if let Err(err) = remove_components(ctx, &component, None).await {
+        warn!("{err:?}");
+    }
 
-            embed.fields.push(field);
+    Ok(())
+}
+
+pub async fn handle_bg_start_preset(
+    ctx: &Context,
+    mut component: InteractionComponent,
+) -> Result<()> {
+    if let Some(GameState::Setup {
+        author,
+        difficulty,
+        effects,
+        ..
+    }) = ctx.bg_games().write(&component.channel_id).await.get_mut()
+    {
+        if *author != component.user_id()? {
+            return Ok(());
         }
 
+        let preset = component
+            .data
+            .values
+            .first()
+            .and_then(|value| BgPreset::from_value(value));
+
+        let preset = match preset {
+            Some(preset) => preset,
+            None => {
+                warn!("unknown preset `{:?}`", component.data.values);
+
+                return Ok(());
+            }
+        };
+
+        *effects = preset.effects();
+        *difficulty = preset.difficulty();
+
+        let mut embed = component.message.embeds.pop().wrap_err("missing embed")?;
+        set_field(&mut embed, "Preset", preset.name().to_owned());
+        set_field(&mut embed, "Effects", effects.join(", "));
+        set_field(&mut embed, "Difficulty", difficulty.to_string());
+
         let builder = MessageBuilder::new().embed(embed);
         component
             .callback(ctx, builder)
@@ -248,6 +307,48 @@ pub async fn handle_bg_start_effects(
     Ok(())
 }
 
+pub async fn handle_bg_team_red(ctx: &Context, component: InteractionComponent) -> Result<()> {
+    handle_bg_team(ctx, component, Team::Red).await
+}
+
+pub async fn handle_bg_team_blue(ctx: &Context, component: InteractionComponent) -> Result<()> {
+    handle_bg_team(ctx, component, Team::Blue).await
+}
+
+async fn handle_bg_team(ctx: &Context, component: InteractionComponent, team: Team) -> Result<()> {
+    let user = component.user_id()?;
+
+    let (result, embed_opt) = match ctx.bg_games().read(&component.channel_id).await.get() {
+        Some(GameState::Running { game }) => game.join_team(user.get(), team),
+        _ => (JoinResult::Finished, None),
+    };
+
+    match (result, embed_opt) {
+        (_, Some(embed)) => {
+            let builder = MessageBuilder::new().embed(embed);
+
+            component
+                .callback(ctx, builder)
+                .await
+                .wrap_err("failed to callback")?;
+        }
+        (JoinResult::Locked(current), _) => {
+            let content = format!(
+                "You can't switch teams after the match started, you're staying in team {}",
+                current.name()
+            );
+
+            component
+                .error_callback(ctx, content)
+                .await
+                .wrap_err("failed to callback")?;
+        }
+        _ => remove_components(ctx, &component, None).await?,
+    }
+
+    Ok(())
+}
+
 async fn update_field(
     ctx: &Context,
     component: &mut InteractionComponent,
@@ -255,20 +356,7 @@ async fn update_field(
     name: &str,
 ) -> Result<()> {
     let mut embed = component.message.embeds.pop().wrap_err("missing embed")?;
-
-    let field_opt = embed.fields.iter_mut().find(|field| field.name == name);
-
-    if let Some(field) = field_opt {
-        field.value = tags.join(", ");
-    } else {
-        let field = EmbedField {
-            inline: false,
-            name: name.to_owned(),
-            value: tags.join(", "),
-        };
-
-        embed.fields.push(field);
-    }
+    set_field(&mut embed, name, tags.join(", "));
 
     let builder = MessageBuilder::new().embed(embed);
     component
@@ -279,6 +367,22 @@ async fn update_field(
     Ok(())
 }
 
+/// Overwrite the value of the field with the given name or add it if it's missing
+fn set_field(embed: &mut Embed, name: &str, value: String) {
+    match embed.fields.iter_mut().find(|field| field.name == name) {
+        Some(field) => field.value = value,
+        None => {
+            let field = EmbedField {
+                inline: false,
+                name: name.to_owned(),
+                value,
+            };
+
+            embed.fields.push(field);
+        }
+    }
+}
+
 async fn remove_components(
     ctx: &Context,
     component: &InteractionComponent,
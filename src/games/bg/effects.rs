@@ -0,0 +1,204 @@
+use image::{
+    imageops::{self, colorops, FilterType},
+    DynamicImage, GenericImageView, RgbaImage,
+};
+use rand::{prelude::SliceRandom, Rng};
+
+use crate::commands::fun::GameDifficulty;
+
+use super::Effects;
+
+/// Side length of a pixelated block
+const PIXEL_SIZE: u32 = 12;
+
+/// Amount of tiles per row and column when scrambling
+const SCRAMBLE_TILES: u32 = 4;
+
+const LAPLACIAN: [f32; 9] = [-1.0, -1.0, -1.0, -1.0, 8.0, -1.0, -1.0, -1.0, -1.0];
+
+impl Effects {
+    /// Apply all effects onto the image
+    pub fn apply(self, mut img: DynamicImage) -> DynamicImage {
+        // Edges must be detected on the original image
+        if self.contains(Effects::EdgeDetect) {
+            img = img.grayscale().filter3x3(&LAPLACIAN);
+        }
+
+        if self.contains(Effects::Invert) {
+            img.invert();
+        }
+
+        if self.contains(Effects::Contrast) {
+            colorops::contrast_in_place(&mut img, 18.0);
+        }
+
+        if self.contains(Effects::ChannelShuffle) {
+            img = shuffle_channels(img);
+        }
+
+        if self.contains(Effects::FlipHorizontal) {
+            imageops::flip_horizontal_in_place(&mut img);
+        }
+
+        if self.contains(Effects::FlipVertical) {
+            imageops::flip_vertical_in_place(&mut img);
+        }
+
+        if self.contains(Effects::Rotate) {
+            img = match rand::thread_rng().gen_range(0..3) {
+                0 => img.rotate90(),
+                1 => img.rotate180(),
+                _ => img.rotate270(),
+            };
+        }
+
+        if self.contains(Effects::Scramble) {
+            img = scramble_tiles(&img);
+        }
+
+        if self.contains(Effects::Grayscale) {
+            img = img.grayscale();
+        }
+
+        if self.contains(Effects::Pixelate) {
+            let (w, h) = img.dimensions();
+
+            img = img
+                .resize_exact(
+                    (w / PIXEL_SIZE).max(1),
+                    (h / PIXEL_SIZE).max(1),
+                    FilterType::Nearest,
+                )
+                .resize_exact(w, h, FilterType::Nearest);
+        }
+
+        if self.contains(Effects::Blur) {
+            img = img.blur(4.0);
+        }
+
+        img
+    }
+}
+
+/// Permute the color channels of the image, never keeping the original order
+fn shuffle_channels(img: DynamicImage) -> DynamicImage {
+    const PERMUTATIONS: [[usize; 3]; 5] = [[0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+
+    let perm = *PERMUTATIONS.choose(&mut rand::thread_rng()).unwrap();
+    let mut rgba = img.into_rgba8();
+
+    for pixel in rgba.pixels_mut() {
+        let [r, g, b, a] = pixel.0;
+        let channels = [r, g, b];
+        pixel.0 = [channels[perm[0]], channels[perm[1]], channels[perm[2]], a];
+    }
+
+    DynamicImage::ImageRgba8(rgba)
+}
+
+/// Split the image into tiles and put them back together in a random order
+fn scramble_tiles(img: &DynamicImage) -> DynamicImage {
+    let (w, h) = img.dimensions();
+    let tile_w = w / SCRAMBLE_TILES;
+    let tile_h = h / SCRAMBLE_TILES;
+
+    let mut tiles: Vec<_> = (0..SCRAMBLE_TILES * SCRAMBLE_TILES)
+        .map(|i| {
+            let x = (i % SCRAMBLE_TILES) * tile_w;
+            let y = (i / SCRAMBLE_TILES) * tile_h;
+
+            img.crop_imm(x, y, tile_w, tile_h)
+        })
+        .collect();
+
+    tiles.shuffle(&mut rand::thread_rng());
+
+    let mut scrambled = RgbaImage::new(tile_w * SCRAMBLE_TILES, tile_h * SCRAMBLE_TILES);
+
+    for (i, tile) in tiles.iter().enumerate() {
+        let x = (i as u32 % SCRAMBLE_TILES) * tile_w;
+        let y = (i as u32 / SCRAMBLE_TILES) * tile_h;
+        imageops::replace(&mut scrambled, &tile.to_rgba8(), x as i64, y as i64);
+    }
+
+    DynamicImage::ImageRgba8(scrambled)
+}
+
+/// Named combinations of effects and difficulty
+#[derive(Copy, Clone)]
+pub enum BgPreset {
+    Casual,
+    Mirrored,
+    Retro,
+    Sketch,
+    Chaos,
+    Nightmare,
+}
+
+impl BgPreset {
+    pub const ALL: [Self; 6] = [
+        Self::Casual,
+        Self::Mirrored,
+        Self::Retro,
+        Self::Sketch,
+        Self::Chaos,
+        Self::Nightmare,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Casual => "Casual",
+            Self::Mirrored => "Mirrored",
+            Self::Retro => "Retro",
+            Self::Sketch => "Sketch",
+            Self::Chaos => "Chaos",
+            Self::Nightmare => "Nightmare",
+        }
+    }
+
+    /// Value of the preset's select menu option
+    pub fn value(self) -> &'static str {
+        match self {
+            Self::Casual => "casual",
+            Self::Mirrored => "mirrored",
+            Self::Retro => "retro",
+            Self::Sketch => "sketch",
+            Self::Chaos => "chaos",
+            Self::Nightmare => "nightmare",
+        }
+    }
+
+    pub fn from_value(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|preset| preset.value() == value)
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Casual => "No effects, normal difficulty",
+            Self::Mirrored => "Flipped and rotated, normal difficulty",
+            Self::Retro => "Pixelated and grayscaled, normal difficulty",
+            Self::Sketch => "Only the edges in inverted colors, hard difficulty",
+            Self::Chaos => "Scrambled tiles with shuffled colors, hard difficulty",
+            Self::Nightmare => "Pixelated, scrambled, and blurred, impossible difficulty",
+        }
+    }
+
+    pub fn effects(self) -> Effects {
+        match self {
+            Self::Casual => Effects::empty(),
+            Self::Mirrored => Effects::FlipHorizontal | Effects::Rotate,
+            Self::Retro => Effects::Pixelate | Effects::Grayscale,
+            Self::Sketch => Effects::EdgeDetect | Effects::Invert,
+            Self::Chaos => Effects::Scramble | Effects::ChannelShuffle,
+            Self::Nightmare => Effects::Pixelate | Effects::Scramble | Effects::Blur,
+        }
+    }
+
+    pub fn difficulty(self) -> GameDifficulty {
+        match self {
+            Self::Casual | Self::Mirrored | Self::Retro => GameDifficulty::Normal,
+            Self::Sketch | Self::Chaos => GameDifficulty::Hard,
+            Self::Nightmare => GameDifficulty::Impossible,
+        }
+    }
+}
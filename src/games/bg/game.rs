@@ -1,10 +1,7 @@
 use std::{collections::VecDeque, sync::Arc};
 
-use eyre::{Report, Result, WrapErr};
-use image::{
-    imageops::{self, colorops},
-    GenericImageView,
-};
+use eyre::{ContextCompat, Report, Result, WrapErr};
+use image::{DynamicImage, GenericImageView};
 use parking_lot::RwLock;
 use rosu_v2::model::GameMode;
 use tokio::{fs, sync::RwLock as TokioRwLock};
@@ -13,7 +10,7 @@ use twilight_model::id::{marker::ChannelMarker, Id};
 use twilight_standby::future::WaitForMessageStream;
 
 use crate::{
-    commands::fun::GameDifficulty,
+    commands::fun::{BgGameKind, GameDifficulty},
     core::BotConfig,
     database::MapsetTagWrapper,
     games::bg::{hints::Hints, img_reveal::ImageReveal, GameMapset},
@@ -27,33 +24,24 @@ pub struct Game {
     pub mapset: GameMapset,
     difficulty: f32,
     hints: Arc<RwLock<Hints>>,
-    reveal: Arc<RwLock<ImageReveal>>,
+    /// `None` for audio games
+    reveal: Option<Arc<RwLock<ImageReveal>>>,
 }
 
 impl Game {
+    /// Returns the game and the initial attachment i.e. either
+    /// the first part of the image or the audio preview
     pub async fn new(
         ctx: &Context,
         mapsets: &[MapsetTagWrapper],
         previous_ids: &mut VecDeque<u32>,
         effects: Effects,
         difficulty: GameDifficulty,
+        kind: BgGameKind,
     ) -> (Self, Vec<u8>) {
         loop {
-            match Game::new_(ctx, mapsets, previous_ids, effects, difficulty).await {
-                Ok(game) => {
-                    let sub_image_result = { game.reveal.read().sub_image() };
-
-                    match sub_image_result {
-                        Ok(img) => return (game, img),
-                        Err(err) => {
-                            let wrap = format!(
-                                "failed to create initial bg image for id {}",
-                                game.mapset.mapset_id
-                            );
-                            warn!("{:?}", err.wrap_err(wrap));
-                        }
-                    }
-                }
+            match Game::new_(ctx, mapsets, previous_ids, effects, difficulty, kind).await {
+                Ok(game) => return game,
                 Err(err) => {
                     warn!("{:?}", err.wrap_err("error while creating bg game"));
                 }
@@ -67,74 +55,97 @@ impl Game {
         previous_ids: &mut VecDeque<u32>,
         effects: Effects,
         difficulty: GameDifficulty,
-    ) -> Result<Self> {
-        let mut path = BotConfig::get().paths.backgrounds.clone();
-
-        match mapsets[0].mode {
-            GameMode::Osu => path.push("osu"),
-            GameMode::Mania => path.push("mania"),
-            _ => bail!("background game not available for {}", mapsets[0].mode),
-        }
-
+        kind: BgGameKind,
+    ) -> Result<(Self, Vec<u8>)> {
         let mapset = util::get_random_mapset(mapsets, previous_ids);
         let mapset_id = mapset.mapset_id;
         debug!("Next BG mapset id: {mapset_id}");
-        path.push(&mapset.filename);
 
-        let img_fut = async {
-            let bytes = fs::read(path)
-                .await
-                .wrap_err_with(|| format!("failed to read bg image for mapset {mapset_id}"))?;
+        let (mapset_, reveal, bytes) = match kind {
+            BgGameKind::Background => {
+                let img_fut =
+                    Self::load_image(mapsets[0].mode, &mapset.filename, mapset_id, effects);
+                let (mapset_, img) = tokio::try_join!(GameMapset::new(ctx, mapset_id), img_fut)?;
 
-            let mut img =
-                image::load_from_memory(&bytes).wrap_err("failed to load image from memory")?;
+                let reveal = ImageReveal::new(img);
 
-            let (w, h) = img.dimensions();
+                let bytes = reveal.sub_image().wrap_err_with(|| {
+                    format!("failed to create initial bg image for id {mapset_id}")
+                })?;
 
-            // 800*600 (4:3)
-            if w * h > 480_000 {
-                img = img.thumbnail(800, 600);
+                (mapset_, Some(Arc::new(RwLock::new(reveal))), bytes)
             }
+            BgGameKind::Audio => {
+                let mut path = BotConfig::get()
+                    .paths
+                    .previews
+                    .clone()
+                    .wrap_err("missing preview path")?;
+
+                path.push(format!("{mapset_id}.mp3"));
+
+                let audio_fut = async {
+                    fs::read(path).await.wrap_err_with(|| {
+                        format!("failed to read audio preview for mapset {mapset_id}")
+                    })
+                };
 
-            if effects.contains(Effects::Invert) {
-                img.invert();
-            }
+                let (mapset_, bytes) =
+                    tokio::try_join!(GameMapset::new(ctx, mapset_id), audio_fut)?;
 
-            if effects.contains(Effects::Contrast) {
-                colorops::contrast_in_place(&mut img, 18.0);
+                (mapset_, None, bytes)
             }
+        };
 
-            if effects.contains(Effects::FlipHorizontal) {
-                imageops::flip_horizontal_in_place(&mut img);
-            }
+        let game = Self {
+            hints: Arc::new(RwLock::new(Hints::new(mapset_.title(), mapset.tags))),
+            difficulty: difficulty.factor(),
+            mapset: mapset_,
+            reveal,
+        };
 
-            if effects.contains(Effects::FlipVertical) {
-                imageops::flip_vertical_in_place(&mut img);
-            }
+        Ok((game, bytes))
+    }
 
-            if effects.contains(Effects::Grayscale) {
-                img = img.grayscale();
-            }
+    async fn load_image(
+        mode: GameMode,
+        filename: &str,
+        mapset_id: u32,
+        effects: Effects,
+    ) -> Result<DynamicImage> {
+        let mut path = BotConfig::get().paths.backgrounds.clone();
 
-            if effects.contains(Effects::Blur) {
-                img = img.blur(4.0);
-            }
+        match mode {
+            GameMode::Osu => path.push("osu"),
+            GameMode::Mania => path.push("mania"),
+            _ => bail!("background game not available for {mode}"),
+        }
 
-            Ok(img)
-        };
+        path.push(filename);
 
-        let (mapset_, img) = tokio::try_join!(GameMapset::new(ctx, mapset.mapset_id), img_fut)?;
+        let bytes = fs::read(path)
+            .await
+            .wrap_err_with(|| format!("failed to read bg image for mapset {mapset_id}"))?;
 
-        Ok(Self {
-            hints: Arc::new(RwLock::new(Hints::new(mapset_.title(), mapset.tags))),
-            difficulty: difficulty.factor(),
-            mapset: mapset_,
-            reveal: Arc::new(RwLock::new(ImageReveal::new(img))),
-        })
+        let mut img =
+            image::load_from_memory(&bytes).wrap_err("failed to load image from memory")?;
+
+        let (w, h) = img.dimensions();
+
+        // 800*600 (4:3)
+        if w * h > 480_000 {
+            img = img.thumbnail(800, 600);
+        }
+
+        Ok(effects.apply(img))
     }
 
     pub fn sub_image(&self) -> Result<Vec<u8>> {
-        let mut reveal = self.reveal.write();
+        let mut reveal = self
+            .reveal
+            .as_ref()
+            .wrap_err("audio games have no image")?
+            .write();
         reveal.increase_radius();
 
         reveal.sub_image()
@@ -174,6 +185,8 @@ pub enum LoopResult {
     Winner(u64),
     Restart,
     Stop,
+    /// The time limit of a match round ran out
+    Timeout,
 }
 
 pub async fn game_loop(
@@ -1,35 +1,48 @@
 use std::{collections::VecDeque, mem, sync::Arc};
 
-use eyre::{Report, Result};
+use eyre::{Report, Result, WrapErr};
 use hashbrown::HashMap;
+use parking_lot::Mutex;
 use tokio::sync::RwLock;
 use tokio::{
     sync::mpsc::{self, UnboundedSender},
-    time::{sleep, timeout, Duration},
+    time::{sleep, timeout, Duration, Instant},
 };
 use twilight_model::{
+    application::component::{button::ButtonStyle, ActionRow, Button, Component},
+    channel::embed::Embed,
     gateway::payload::incoming::MessageCreate,
-    id::{marker::ChannelMarker, Id},
+    id::{
+        marker::{ChannelMarker, MessageMarker},
+        Id,
+    },
 };
 
 use crate::util::hasher::IntHasher;
 use crate::{
-    commands::fun::GameDifficulty,
-    database::MapsetTagWrapper,
-    util::{builder::MessageBuilder, constants::OSU_BASE, ChannelExt},
+    commands::fun::{BgGameKind, GameDifficulty},
+    database::{BgGuildStats, MapsetTagWrapper},
+    embeds::{BgScoreboardEmbed, EmbedData},
+    util::{builder::MessageBuilder, constants::OSU_BASE, ChannelExt, MessageExt},
     Context,
 };
 
 use super::{
     game::{game_loop, Game, LoopResult},
-    Effects,
+    Effects, JoinResult, MatchSettings, Scoreboard, Team,
 };
 
 const GAME_LEN: Duration = Duration::from_secs(180);
 
+type GuildStats = HashMap<u64, BgGuildStats, IntHasher>;
+
 #[derive(Clone)]
 pub struct GameWrapper {
     game: Arc<RwLock<Game>>,
+    kind: BgGameKind,
+    scoreboard: Option<Arc<Mutex<Scoreboard>>>,
+    /// `None` if too few mapsets are available for the stats to be tracked
+    stats: Option<Arc<Mutex<GuildStats>>>,
     tx: UnboundedSender<LoopResult>,
 }
 
@@ -40,6 +53,8 @@ impl GameWrapper {
         mapsets: Vec<MapsetTagWrapper>,
         effects: Effects,
         difficulty: GameDifficulty,
+        kind: BgGameKind,
+        settings: Option<MatchSettings>,
     ) -> Self {
         let (tx, mut rx) = mpsc::unbounded_channel();
 
@@ -50,41 +65,69 @@ impl GameWrapper {
         let mut previous_ids = VecDeque::with_capacity(50);
         let mut scores = HashMap::with_hasher(IntHasher);
 
+        let scoreboard = settings.map(|settings| Arc::new(Mutex::new(Scoreboard::new(settings))));
+        let scoreboard_clone = scoreboard.clone();
+
+        let stats = (mapsets.len() >= 20).then(|| Arc::new(Mutex::new(GuildStats::default())));
+        let stats_clone = stats.clone();
+
         // Initialize game
         let (game, mut img) =
-            Game::new(&ctx, &mapsets, &mut previous_ids, effects, difficulty).await;
+            Game::new(&ctx, &mapsets, &mut previous_ids, effects, difficulty, kind).await;
         let game = Arc::new(RwLock::new(game));
         let game_clone = Arc::clone(&game);
 
         tokio::spawn(async move {
+            // The message containing the live scoreboard
+            let mut scoreboard_msg = None;
+
+            if let Some(ref scoreboard) = scoreboard_clone {
+                match send_scoreboard(&ctx, channel, scoreboard).await {
+                    Ok(msg) => scoreboard_msg = Some(msg),
+                    Err(err) => warn!("{:?}", err.wrap_err("Failed to send bg game scoreboard")),
+                }
+            }
+
+            let round_len = scoreboard_clone
+                .as_ref()
+                .map_or(GAME_LEN, |scoreboard| scoreboard.lock().settings.round_time);
+
             loop {
                 let builder = MessageBuilder::new()
                     .content("Here's the next one:")
-                    .attachment("bg_img.png", mem::take(&mut img));
+                    .attachment(kind.attachment_name(), mem::take(&mut img));
 
                 if let Err(err) = channel.create_message(&ctx, &builder).await {
                     let report = Report::new(err).wrap_err("Failed to send initial bg game msg");
                     warn!("{report:?}");
                 }
 
+                let round_start = Instant::now();
+
                 let result = tokio::select! {
                     // Listen for stop or restart invokes
                     option = rx.recv() => option.unwrap_or(LoopResult::Stop),
                     // Let the game run
                     result = game_loop(&mut msg_stream, &ctx, &game_clone, channel) => result,
-                    // Timeout after 3 minutes
-                    _ = sleep(GAME_LEN) => LoopResult::Stop,
+                    // Rounds of a match are limited individually,
+                    // otherwise timeout after 3 minutes
+                    _ = sleep(round_len) => if scoreboard_clone.is_some() {
+                        LoopResult::Timeout
+                    } else {
+                        LoopResult::Stop
+                    },
                 };
 
                 // Process the result
                 match result {
-                    LoopResult::Restart => {
+                    LoopResult::Restart | LoopResult::Timeout => {
                         let mapset_id = game_clone.read().await.mapset_id();
 
                         // Send message
                         let content = format!(
-                            "Mapset: {OSU_BASE}beatmapsets/{mapset_id}\n\
-                            Full background: https://assets.ppy.sh/beatmaps/{mapset_id}/covers/raw.jpg"
+                            "{}Mapset: {OSU_BASE}beatmapsets/{mapset_id}\n\
+                            Full background: https://assets.ppy.sh/beatmaps/{mapset_id}/covers/raw.jpg",
+                            if let LoopResult::Timeout = result { "Time's up!\n" } else { "" },
                         );
 
                         if let Err(err) = channel.plain_message(&ctx, &content).await {
@@ -109,10 +152,19 @@ impl GameWrapper {
                             warn!("{report:?}");
                         }
 
-                        // Store score for winners
-                        for (user, score) in scores {
-                            if let Err(err) = ctx.psql().increment_bggame_score(user, score).await {
-                                warn!("{:?}", err.wrap_err("Failed to increment bg game score"));
+                        if let Some(ref scoreboard) = scoreboard_clone {
+                            finish_match(&ctx, channel, scoreboard_msg, scoreboard).await;
+                        } else {
+                            // Store score for winners
+                            for (user, score) in scores {
+                                if let Err(err) =
+                                    ctx.psql().increment_bggame_score(user, score).await
+                                {
+                                    warn!(
+                                        "{:?}",
+                                        err.wrap_err("Failed to increment bg game score")
+                                    );
+                                }
                             }
                         }
 
@@ -121,23 +173,75 @@ impl GameWrapper {
                         break;
                     }
                     LoopResult::Winner(user_id) => {
-                        if mapsets.len() >= 20 {
+                        if let Some(ref stats) = stats_clone {
+                            let mut stats = stats.lock();
+                            let entry = stats.entry(user_id).or_default();
+                            entry.score += 1;
+                            entry.guess_time_ms += round_start.elapsed().as_millis() as u64;
+                        }
+
+                        if let Some(ref scoreboard) = scoreboard_clone {
+                            scoreboard.lock().add_point(user_id);
+                        } else if mapsets.len() >= 20 {
                             *scores.entry(user_id).or_insert(0) += 1;
                         }
                     }
                 }
 
+                // Advance to the next round of the match
+                if let Some(ref scoreboard) = scoreboard_clone {
+                    let finished = {
+                        let mut scoreboard = scoreboard.lock();
+                        scoreboard.round += 1;
+
+                        scoreboard.is_finished()
+                    };
+
+                    if finished {
+                        finish_match(&ctx, channel, scoreboard_msg, scoreboard).await;
+                        info!("Match finished in channel {channel}");
+
+                        break;
+                    }
+
+                    if let Some(msg) = scoreboard_msg {
+                        let embed = BgScoreboardEmbed::new(&scoreboard.lock(), false).build();
+                        let builder = MessageBuilder::new().embed(embed);
+
+                        if let Err(err) = (msg, channel).update(&ctx, &builder).await {
+                            let report =
+                                Report::new(err).wrap_err("Failed to update bg game scoreboard");
+                            warn!("{report:?}");
+                        }
+                    }
+                }
+
                 // Initialize next game
                 let (game, img_) =
-                    Game::new(&ctx, &mapsets, &mut previous_ids, effects, difficulty).await;
+                    Game::new(&ctx, &mapsets, &mut previous_ids, effects, difficulty, kind).await;
                 img = img_;
                 *game_clone.write().await = game;
             }
 
+            if let Some(stats) = stats_clone {
+                let stats = mem::take(&mut *stats.lock());
+                store_guild_stats(&ctx, channel, stats).await;
+            }
+
             ctx.bg_games().write(&channel).await.remove();
         });
 
-        Self { game, tx }
+        Self {
+            game,
+            kind,
+            scoreboard,
+            stats,
+            tx,
+        }
+    }
+
+    pub fn kind(&self) -> BgGameKind {
+        self.kind
     }
 
     pub fn stop(&self) -> Result<()> {
@@ -158,11 +262,174 @@ impl GameWrapper {
             .sub_image()
     }
 
-    pub async fn hint(&self) -> Result<String> {
+    pub async fn hint(&self, user: u64) -> Result<String> {
         let game = timeout(Duration::from_secs(1), self.game.read())
             .await
             .map_err(|_| eyre!("timeout while waiting for write"))?;
 
+        if let Some(ref stats) = self.stats {
+            stats.lock().entry(user).or_default().hints += 1;
+        }
+
         Ok(game.hint())
     }
+
+    /// Let the user join a team and return the updated scoreboard
+    /// if they joined successfully.
+    pub fn join_team(&self, user: u64, team: Team) -> (JoinResult, Option<Embed>) {
+        let mut scoreboard = match self.scoreboard {
+            Some(ref scoreboard) => scoreboard.lock(),
+            None => return (JoinResult::NoTeams, None),
+        };
+
+        match scoreboard.join(user, team) {
+            JoinResult::Joined => {
+                let embed = BgScoreboardEmbed::new(&scoreboard, false).build();
+
+                (JoinResult::Joined, Some(embed))
+            }
+            result => (result, None),
+        }
+    }
+}
+
+async fn store_guild_stats(ctx: &Context, channel: Id<ChannelMarker>, stats: GuildStats) {
+    let guild = match ctx.cache.channel(channel, |channel| channel.guild_id) {
+        Ok(Some(guild)) => guild,
+        Ok(None) => return,
+        Err(err) => {
+            let report = Report::new(err).wrap_err("Failed to get channel for bg game stats");
+
+            return warn!("{report:?}");
+        }
+    };
+
+    for (user, stats) in stats {
+        if let Err(err) = ctx
+            .psql()
+            .increment_bggame_guild_stats(guild, user, &stats)
+            .await
+        {
+            warn!(
+                "{:?}",
+                err.wrap_err("Failed to increment bg game guild stats")
+            );
+        }
+    }
+}
+
+async fn send_scoreboard(
+    ctx: &Context,
+    channel: Id<ChannelMarker>,
+    scoreboard: &Mutex<Scoreboard>,
+) -> Result<Id<MessageMarker>> {
+    let (embed, teams) = {
+        let scoreboard = scoreboard.lock();
+
+        (
+            BgScoreboardEmbed::new(&scoreboard, false).build(),
+            scoreboard.settings.teams,
+        )
+    };
+
+    let mut builder = MessageBuilder::new().embed(embed);
+
+    if teams {
+        builder = builder.components(team_components());
+    }
+
+    let msg = channel
+        .create_message(ctx, &builder)
+        .await
+        .wrap_err("failed to create message")?
+        .model()
+        .await
+        .wrap_err("failed to deserialize message")?;
+
+    Ok(msg.id)
+}
+
+/// Finalize the scoreboard message, send the results, and store them
+async fn finish_match(
+    ctx: &Context,
+    channel: Id<ChannelMarker>,
+    scoreboard_msg: Option<Id<MessageMarker>>,
+    scoreboard: &Mutex<Scoreboard>,
+) {
+    let (embed, rounds, teams, scores) = {
+        let scoreboard = scoreboard.lock();
+
+        let scores: Vec<_> = scoreboard
+            .ranking()
+            .into_iter()
+            .map(|(user, score)| (user, scoreboard.team(user), score))
+            .collect();
+
+        (
+            BgScoreboardEmbed::new(&scoreboard, true).build(),
+            scoreboard.round,
+            scoreboard.settings.teams,
+            scores,
+        )
+    };
+
+    // Remove the team buttons
+    if let Some(msg) = scoreboard_msg {
+        let builder = MessageBuilder::new()
+            .embed(embed.clone())
+            .components(Vec::new());
+
+        if let Err(err) = (msg, channel).update(ctx, &builder).await {
+            let report = Report::new(err).wrap_err("Failed to update bg game scoreboard");
+            warn!("{report:?}");
+        }
+    }
+
+    let builder = MessageBuilder::new().embed(embed);
+
+    if let Err(err) = channel.create_message(ctx, &builder).await {
+        let report = Report::new(err).wrap_err("Failed to send bg game results");
+        warn!("{report:?}");
+    }
+
+    if rounds == 0 {
+        return;
+    }
+
+    if let Err(err) = ctx
+        .psql()
+        .insert_bggame_match(channel, rounds, teams, &scores)
+        .await
+    {
+        warn!("{:?}", err.wrap_err("Failed to store bg game match"));
+    }
+}
+
+fn team_components() -> Vec<Component> {
+    let red_button = Button {
+        custom_id: Some("bg_team_red".to_owned()),
+        disabled: false,
+        emoji: None,
+        label: Some("Join team red".to_owned()),
+        style: ButtonStyle::Danger,
+        url: None,
+    };
+
+    let blue_button = Button {
+        custom_id: Some("bg_team_blue".to_owned()),
+        disabled: false,
+        emoji: None,
+        label: Some("Join team blue".to_owned()),
+        style: ButtonStyle::Primary,
+        url: None,
+    };
+
+    let button_row = ActionRow {
+        components: vec![
+            Component::Button(red_button),
+            Component::Button(blue_button),
+        ],
+    };
+
+    vec![Component::ActionRow(button_row)]
 }
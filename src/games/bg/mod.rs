@@ -2,28 +2,42 @@
 
 use twilight_model::id::{marker::UserMarker, Id};
 
-use crate::commands::fun::GameDifficulty;
+use crate::commands::fun::{BgGameKind, GameDifficulty};
 
-pub use self::{game_wrapper::GameWrapper, mapset::GameMapset, tags::MapsetTags};
+pub use self::{
+    effects::BgPreset,
+    game_wrapper::GameWrapper,
+    mapset::GameMapset,
+    scoreboard::{JoinResult, MatchSettings, Scoreboard, Team},
+    tags::MapsetTags,
+    util::retain_previews,
+};
 
+mod effects;
 mod game;
 mod game_wrapper;
 mod hints;
 mod img_reveal;
 mod mapset;
+mod scoreboard;
 mod tags;
 mod util;
 
 pub mod components;
 
 bitflags::bitflags! {
-    pub struct Effects: u8 {
+    pub struct Effects: u16 {
         const Blur           = 1 << 0;
         const Contrast       = 1 << 1;
         const FlipHorizontal = 1 << 2;
         const FlipVertical   = 1 << 3;
         const Grayscale      = 1 << 4;
         const Invert         = 1 << 5;
+        const Pixelate       = 1 << 6;
+        const Rotate         = 1 << 7;
+        const ChannelShuffle = 1 << 8;
+        const Scramble       = 1 << 9;
+        const EdgeDetect     = 1 << 10;
     }
 }
 
@@ -38,5 +52,7 @@ pub enum GameState {
         effects: Effects,
         excluded: MapsetTags,
         included: MapsetTags,
+        kind: BgGameKind,
+        settings: Option<MatchSettings>,
     },
 }
@@ -0,0 +1,264 @@
+use std::cmp::Reverse;
+
+use hashbrown::HashMap;
+use tokio::time::Duration;
+
+use crate::util::hasher::IntHasher;
+
+/// Settings of a background game with a fixed amount of rounds
+#[derive(Copy, Clone)]
+pub struct MatchSettings {
+    pub rounds: usize,
+    pub round_time: Duration,
+    pub teams: bool,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Team {
+    Red = 0,
+    Blue = 1,
+}
+
+impl Team {
+    pub fn name(self) -> &'static str {
+        match self {
+            Team::Red => "Red",
+            Team::Blue => "Blue",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum JoinResult {
+    Joined,
+    /// The match is not played in teams
+    NoTeams,
+    Finished,
+    /// The user is already in the given team and can no longer switch
+    /// because the match already started
+    Locked(Team),
+}
+
+pub struct Scoreboard {
+    pub settings: MatchSettings,
+    /// Amount of rounds that have been finished
+    pub round: usize,
+    scores: HashMap<u64, u32, IntHasher>,
+    teams: HashMap<u64, Team, IntHasher>,
+}
+
+impl Scoreboard {
+    pub fn new(settings: MatchSettings) -> Self {
+        Self {
+            settings,
+            round: 0,
+            scores: HashMap::default(),
+            teams: HashMap::default(),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.round >= self.settings.rounds
+    }
+
+    /// Assign a user to a team.
+    ///
+    /// Users can only switch teams until the first point of the match was awarded,
+    /// otherwise they could carry their points over to the other team.
+    pub fn join(&mut self, user: u64, team: Team) -> JoinResult {
+        if !self.settings.teams {
+            return JoinResult::NoTeams;
+        } else if self.is_finished() {
+            return JoinResult::Finished;
+        }
+
+        match self.team(user) {
+            Some(current) if current == team => return JoinResult::Joined,
+            Some(current) if self.round > 0 || !self.scores.is_empty() => {
+                return JoinResult::Locked(current)
+            }
+            _ => {}
+        }
+
+        self.teams.insert(user, team);
+
+        JoinResult::Joined
+    }
+
+    /// Award a point to the user.
+    ///
+    /// If the match is played in teams and the user did not join a team yet,
+    /// they will be assigned to the smaller one.
+    pub fn add_point(&mut self, user: u64) {
+        *self.scores.entry(user).or_insert(0) += 1;
+
+        if self.settings.teams && !self.teams.contains_key(&user) {
+            let red = self.team_size(Team::Red);
+            let blue = self.team_size(Team::Blue);
+            let team = if blue < red { Team::Blue } else { Team::Red };
+            self.teams.insert(user, team);
+        }
+    }
+
+    pub fn team(&self, user: u64) -> Option<Team> {
+        self.teams.get(&user).copied()
+    }
+
+    fn team_size(&self, team: Team) -> usize {
+        self.teams.values().filter(|t| **t == team).count()
+    }
+
+    pub fn team_score(&self, team: Team) -> u32 {
+        self.scores
+            .iter()
+            .filter(|(user, _)| self.team(**user) == Some(team))
+            .map(|(_, score)| *score)
+            .sum()
+    }
+
+    /// Members of the team, including those without points
+    pub fn team_members(&self, team: Team) -> Vec<u64> {
+        let mut members: Vec<_> = self
+            .teams
+            .iter()
+            .filter(|(_, t)| **t == team)
+            .map(|(user, _)| *user)
+            .collect();
+
+        members.sort_unstable_by_key(|user| Reverse(self.score(*user)));
+
+        members
+    }
+
+    pub fn score(&self, user: u64) -> u32 {
+        self.scores.get(&user).copied().unwrap_or(0)
+    }
+
+    /// All users with points, sorted by their score
+    pub fn ranking(&self) -> Vec<(u64, u32)> {
+        let mut ranking: Vec<_> = self
+            .scores
+            .iter()
+            .map(|(user, score)| (*user, *score))
+            .collect();
+
+        ranking.sort_unstable_by(|(_, a), (_, b)| b.cmp(a));
+
+        ranking
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scoreboard(teams: bool) -> Scoreboard {
+        Scoreboard::new(MatchSettings {
+            rounds: 3,
+            round_time: Duration::from_secs(60),
+            teams,
+        })
+    }
+
+    #[test]
+    fn join_without_teams() {
+        let mut scoreboard = scoreboard(false);
+
+        assert_eq!(scoreboard.join(1, Team::Red), JoinResult::NoTeams);
+        assert_eq!(scoreboard.team(1), None);
+    }
+
+    #[test]
+    fn switch_before_start() {
+        let mut scoreboard = scoreboard(true);
+
+        assert_eq!(scoreboard.join(1, Team::Red), JoinResult::Joined);
+        assert_eq!(scoreboard.join(1, Team::Blue), JoinResult::Joined);
+        assert_eq!(scoreboard.team(1), Some(Team::Blue));
+    }
+
+    #[test]
+    fn no_switch_after_points() {
+        let mut scoreboard = scoreboard(true);
+
+        scoreboard.join(1, Team::Red);
+        scoreboard.add_point(1);
+
+        assert_eq!(
+            scoreboard.join(1, Team::Blue),
+            JoinResult::Locked(Team::Red)
+        );
+        assert_eq!(scoreboard.join(1, Team::Red), JoinResult::Joined);
+        assert_eq!(scoreboard.team_score(Team::Red), 1);
+        assert_eq!(scoreboard.team_score(Team::Blue), 0);
+
+        // New players can still join a team
+        assert_eq!(scoreboard.join(2, Team::Blue), JoinResult::Joined);
+        assert_eq!(
+            scoreboard.join(2, Team::Red),
+            JoinResult::Locked(Team::Blue)
+        );
+    }
+
+    #[test]
+    fn no_switch_after_first_round() {
+        let mut scoreboard = scoreboard(true);
+
+        scoreboard.join(1, Team::Red);
+        scoreboard.round += 1;
+
+        assert_eq!(
+            scoreboard.join(1, Team::Blue),
+            JoinResult::Locked(Team::Red)
+        );
+    }
+
+    #[test]
+    fn no_join_when_finished() {
+        let mut scoreboard = scoreboard(true);
+        scoreboard.round = 3;
+
+        assert_eq!(scoreboard.join(1, Team::Red), JoinResult::Finished);
+    }
+
+    #[test]
+    fn team_scores() {
+        let mut scoreboard = scoreboard(true);
+
+        scoreboard.join(1, Team::Red);
+        scoreboard.join(2, Team::Blue);
+        scoreboard.join(3, Team::Blue);
+
+        for user in [1, 1, 2, 3, 3, 3] {
+            scoreboard.add_point(user);
+        }
+
+        assert_eq!(scoreboard.team_score(Team::Red), 2);
+        assert_eq!(scoreboard.team_score(Team::Blue), 4);
+        assert_eq!(scoreboard.team_members(Team::Blue), vec![3, 2]);
+    }
+
+    #[test]
+    fn scorer_without_team_joins_smaller_team() {
+        let mut scoreboard = scoreboard(true);
+
+        scoreboard.join(1, Team::Red);
+        scoreboard.add_point(2);
+
+        assert_eq!(scoreboard.team(2), Some(Team::Blue));
+    }
+
+    #[test]
+    fn final_standings() {
+        let mut scoreboard = scoreboard(false);
+
+        for user in [1, 2, 2, 3, 3, 3] {
+            scoreboard.add_point(user);
+        }
+
+        scoreboard.round = 3;
+
+        assert!(scoreboard.is_finished());
+        assert_eq!(scoreboard.ranking(), vec![(3, 3), (2, 2), (1, 1)]);
+    }
+}
@@ -1,8 +1,11 @@
 use std::collections::VecDeque;
 
+use eyre::{Report, WrapErr};
+use hashbrown::HashSet;
 use rand::Rng;
+use tokio::fs;
 
-use crate::database::MapsetTagWrapper;
+use crate::{core::BotConfig, database::MapsetTagWrapper, util::hasher::IntHasher};
 
 #[allow(clippy::needless_lifetimes)]
 pub fn get_random_mapset<'m>(
@@ -27,3 +30,53 @@ pub fn get_random_mapset<'m>(
         }
     }
 }
+
+/// Only keep mapsets for which an audio preview is available.
+///
+/// If no preview path is configured or it can't be read, no mapset is kept.
+pub async fn retain_previews(mapsets: &mut Vec<MapsetTagWrapper>) {
+    let path = match BotConfig::get().paths.previews {
+        Some(ref path) => path,
+        None => return mapsets.clear(),
+    };
+
+    let mut dir = match fs::read_dir(path).await {
+        Ok(dir) => dir,
+        Err(err) => {
+            let report = Report::new(err).wrap_err("Failed to read preview directory");
+            warn!("{report:?}");
+
+            return mapsets.clear();
+        }
+    };
+
+    let mut available = HashSet::with_hasher(IntHasher);
+
+    loop {
+        match dir
+            .next_entry()
+            .await
+            .wrap_err("Failed to read preview entry")
+        {
+            Ok(Some(entry)) => {
+                let mapset_id = entry
+                    .file_name()
+                    .to_str()
+                    .and_then(|name| name.strip_suffix(".mp3"))
+                    .and_then(|id| id.parse::<u32>().ok());
+
+                if let Some(mapset_id) = mapset_id {
+                    available.insert(mapset_id);
+                }
+            }
+            Ok(None) => break,
+            Err(err) => {
+                warn!("{err:?}");
+
+                break;
+            }
+        }
+    }
+
+    mapsets.retain(|mapset| available.contains(&mapset.mapset_id));
+}
@@ -185,6 +185,11 @@ async fn correct_guess(
         let mut embed = game.reveal(&mut component).wrap_err("failed to reveal")?;
 
         game.current_score += 1;
+
+        if let Err(err) = game.update_daily(&ctx, user).await {
+            warn!("{err:?}");
+        }
+
         let mut footer = game.footer();
         let _ = write!(footer, " • {guess} was correct, press Next to continue");
 
@@ -233,10 +238,31 @@ async fn game_over(
     let GameState {
         current_score,
         highscore,
+        daily,
         ..
     } = &game;
 
-    let value = if game.new_highscore(&ctx, user).await? {
+    let value = if let Some(day) = daily {
+        let mut scores = ctx
+            .psql()
+            .get_higherlower_daily_scores(game.version(), *day)
+            .await
+            .wrap_err("failed to get daily scores")?;
+
+        scores.sort_unstable_by(|(_, a), (_, b)| b.cmp(a));
+        let total = scores.len();
+
+        let rank = scores
+            .iter()
+            .position(|(_, score)| score == current_score)
+            .map_or(total, |idx| idx + 1);
+
+        format!(
+            "You achieved a total score of {current_score} in today's daily challenge, \
+            placing you #{rank} out of {total} player{plural}.",
+            plural = if total == 1 { "" } else { "s" }
+        )
+    } else if game.new_highscore(&ctx, user).await? {
         format!("You achieved a total score of {current_score}, your new personal best :tada:")
     } else {
         format!("You achieved a total score of {current_score}, your personal best is {highscore}.")
@@ -253,7 +279,14 @@ async fn game_over(
     };
 
     embed.fields.push(field);
-    let components = HlComponents::restart();
+
+    // Daily challenges can only be attempted once
+    let components = if game.daily.is_some() {
+        HlComponents::disabled()
+    } else {
+        HlComponents::restart()
+    };
+
     let builder = MessageBuilder::new().embed(embed).components(components);
 
     component
@@ -261,6 +294,10 @@ async fn game_over(
         .await
         .wrap_err("failed to callback")?;
 
+    if game.daily.is_some() {
+        return Ok(());
+    }
+
     let (tx, rx) = oneshot::channel();
     let msg = game.msg;
     let channel = game.channel;
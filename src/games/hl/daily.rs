@@ -0,0 +1,269 @@
+use std::sync::Arc;
+
+use eyre::{Result, WrapErr};
+use parking_lot::Mutex;
+use rand::{rngs::StdRng, SeedableRng};
+use rosu_v2::prelude::{GameMode, GameMods};
+use time::Date;
+use tokio::sync::Mutex as TokioMutex;
+
+use crate::core::Context;
+
+use super::{
+    farm_map::{FarmEntries, FarmMap},
+    score_pp::ScorePp,
+    HlVersion,
+};
+
+/// Entries of the current daily challenges.
+///
+/// Entries are generated once when the first player reaches them and then
+/// shared with everyone else so that all players of a day get the same sequence,
+/// no matter how the underlying data changes throughout the day.
+///
+/// Generated entries are also stored in the database so that the sequence
+/// survives restarts.
+#[derive(Default)]
+pub struct HlDaily {
+    score_pp: Mutex<Option<Arc<DailyEntries<ScorePp>>>>,
+    farm_maps: Mutex<Option<Arc<DailyEntries<FarmMap, FarmEntries>>>>,
+}
+
+impl HlDaily {
+    pub(super) fn score_pp(&self, day: Date) -> Arc<DailyEntries<ScorePp>> {
+        let mut guard = self.score_pp.lock();
+
+        match *guard {
+            Some(ref daily) if daily.day == day => Arc::clone(daily),
+            _ => {
+                let daily = Arc::new(DailyEntries::new(day, HlVersion::ScorePp, ()));
+                *guard = Some(Arc::clone(&daily));
+
+                daily
+            }
+        }
+    }
+
+    pub(super) async fn farm_maps(
+        &self,
+        ctx: &Context,
+        day: Date,
+    ) -> Result<Arc<DailyEntries<FarmMap, FarmEntries>>> {
+        if let Some(ref daily) = *self.farm_maps.lock() {
+            if daily.day == day {
+                return Ok(Arc::clone(daily));
+            }
+        }
+
+        // The farm counts are fetched only once per day so they don't change between players
+        let entries = ctx
+            .redis()
+            .osutracker_counts()
+            .await
+            .wrap_err("failed to get osutracker counts")?;
+
+        let mut guard = self.farm_maps.lock();
+
+        // Another player might have been faster
+        match *guard {
+            Some(ref daily) if daily.day == day => Ok(Arc::clone(daily)),
+            _ => {
+                let daily = Arc::new(DailyEntries::new(day, HlVersion::FarmMaps, entries));
+                *guard = Some(Arc::clone(&daily));
+
+                Ok(daily)
+            }
+        }
+    }
+}
+
+/// Where the entries of a game come from
+pub(super) enum EntrySource<T, D = ()> {
+    /// Entries are picked randomly for this game only
+    Random { data: D, rng: StdRng },
+    /// Entries of the daily challenge, shared by all players of the day
+    Daily(Arc<DailyEntries<T, D>>),
+}
+
+impl<T, D> EntrySource<T, D> {
+    pub(super) fn random(data: D) -> Self {
+        Self::Random {
+            data,
+            rng: StdRng::from_entropy(),
+        }
+    }
+}
+
+/// The ids of a daily entry by which it is stored in the database
+pub struct HlDailyEntry {
+    pub map_id: u32,
+    pub user_id: Option<u32>,
+    pub mods: Option<GameMods>,
+    pub farm: Option<u32>,
+}
+
+/// The sequence of entries of a daily challenge on a given day
+pub(super) struct DailyEntries<T, D = ()> {
+    day: Date,
+    version: HlVersion,
+    /// Data that is fixed for the day
+    data: D,
+    inner: TokioMutex<DailyInner<T>>,
+}
+
+struct DailyInner<T> {
+    rng: StdRng,
+    entries: Vec<T>,
+    /// Entries stored in the database, `None` if not loaded yet
+    stored: Option<Vec<HlDailyEntry>>,
+}
+
+impl<T, D> DailyEntries<T, D> {
+    fn new(day: Date, version: HlVersion, data: D) -> Self {
+        let inner = DailyInner {
+            rng: StdRng::seed_from_u64(Self::seed(day, version)),
+            entries: Vec::new(),
+            stored: None,
+        };
+
+        Self {
+            day,
+            version,
+            data,
+            inner: TokioMutex::new(inner),
+        }
+    }
+}
+
+impl<T, D> DailyEntries<T, D> {
+    fn seed(day: Date, version: HlVersion) -> u64 {
+        // Mix the version into the seed so that each version has its own sequence
+        ((day.to_julian_day() as u64) << 8) | version as u64
+    }
+
+    /// Load the entries that were stored for this day.
+    ///
+    /// If entries were already stored, the rng is reseeded so that newly generated
+    /// entries don't just repeat the choices of the first ones.
+    async fn load_stored(&self, ctx: &Context, inner: &mut DailyInner<T>) -> Result<()> {
+        if inner.stored.is_some() {
+            return Ok(());
+        }
+
+        let stored = ctx
+            .psql()
+            .get_higherlower_daily_entries(self.version, self.day)
+            .await
+            .wrap_err("failed to get stored daily entries")?;
+
+        if !stored.is_empty() {
+            let seed = Self::seed(self.day, self.version) ^ ((stored.len() as u64) << 32);
+            inner.rng = StdRng::seed_from_u64(seed);
+        }
+
+        inner.stored = Some(stored);
+
+        Ok(())
+    }
+
+    async fn store(&self, ctx: &Context, idx: usize, entry: &HlDailyEntry) {
+        let store_fut =
+            ctx.psql()
+                .insert_higherlower_daily_entry(self.version, self.day, idx, entry);
+
+        if let Err(err) = store_fut.await {
+            warn!("{:?}", err.wrap_err("Failed to store daily entry"));
+        }
+    }
+}
+
+impl DailyEntries<ScorePp> {
+    /// Get the entry at the given index, generating it and all
+    /// previous ones if no player got that far yet.
+    pub(super) async fn get(&self, ctx: &Context, idx: usize) -> Result<ScorePp> {
+        let mut inner = self.inner.lock().await;
+        self.load_stored(ctx, &mut inner).await?;
+
+        while inner.entries.len() <= idx {
+            let DailyInner {
+                rng,
+                entries,
+                stored,
+            } = &mut *inner;
+
+            let len = entries.len();
+
+            if let Some(entry) = stored.as_ref().and_then(|stored| stored.get(len)) {
+                let entry = ScorePp::from_daily(ctx, entry)
+                    .await
+                    .wrap_err("failed to recreate score pp entry")?;
+
+                entries.push(entry);
+
+                continue;
+            }
+
+            // The first two entries are not compared against any previous pp value
+            let (prev_pp, curr_score) = match len {
+                0 | 1 => (0.0, 0),
+                len => (entries[len - 1].pp, len as u32 - 1),
+            };
+
+            let entry = loop {
+                let entry = ScorePp::random(ctx, GameMode::Osu, prev_pp, curr_score, rng)
+                    .await
+                    .wrap_err("failed to create score pp entry")?;
+
+                if entries.last() != Some(&entry) {
+                    break entry;
+                }
+            };
+
+            self.store(ctx, len, &entry.daily_entry()).await;
+            entries.push(entry);
+        }
+
+        Ok(inner.entries[idx].clone())
+    }
+}
+
+impl DailyEntries<FarmMap, FarmEntries> {
+    /// Get the entry at the given index, generating it and all
+    /// previous ones if no player got that far yet.
+    pub(super) async fn get(&self, ctx: &Context, idx: usize) -> Result<FarmMap> {
+        let mut inner = self.inner.lock().await;
+        self.load_stored(ctx, &mut inner).await?;
+
+        while inner.entries.len() <= idx {
+            let DailyInner {
+                rng,
+                entries,
+                stored,
+            } = &mut *inner;
+
+            let len = entries.len();
+
+            if let Some(entry) = stored.as_ref().and_then(|stored| stored.get(len)) {
+                let entry = FarmMap::from_daily(ctx, entry)
+                    .await
+                    .wrap_err("failed to recreate farm map entry")?;
+
+                entries.push(entry);
+
+                continue;
+            }
+
+            let prev_farm = entries.last().map(|entry| entry.farm);
+            let curr_score = len.saturating_sub(1) as u32;
+
+            let entry = FarmMap::random(ctx, &self.data, prev_farm, curr_score, rng)
+                .await
+                .wrap_err("failed to create farm map entry")?;
+
+            self.store(ctx, len, &entry.daily_entry()).await;
+            entries.push(entry);
+        }
+
+        Ok(inner.entries[idx].clone())
+    }
+}
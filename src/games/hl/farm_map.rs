@@ -1,8 +1,7 @@
 use std::f32::consts::SQRT_2;
 
 use eyre::{Report, Result, WrapErr};
-use image::{GenericImageView, ImageBuffer};
-use rand::{prelude::SliceRandom, Rng};
+use rand::{prelude::SliceRandom, rngs::StdRng, Rng};
 use time::OffsetDateTime;
 
 use crate::{
@@ -10,11 +9,13 @@ use crate::{
     custom_client::OsuTrackerIdCount,
 };
 
-use super::{kind::GameStateKind, mapset_cover, H, W};
+use super::{kind::GameStateKind, split_covers, HlDailyEntry};
 
 pub type FarmEntries = ArchivedBytes<Vec<OsuTrackerIdCount>>;
 
+#[derive(Clone)]
 pub(super) struct FarmMap {
+    map_id: u32,
     pub map_string: String,
     pub map_url: String,
     pub mapset_id: u32,
@@ -60,25 +61,19 @@ impl FarmMap {
         entries: &FarmEntries,
         prev_farm: Option<u32>,
         curr_score: u32,
+        rng: &mut StdRng,
     ) -> Result<Self> {
         let archived = entries.get();
+        let max = archived[0].count as f32;
 
-        let (prev_farm, rng_res) = {
-            let mut rng = rand::thread_rng();
-            let max = archived[0].count as f32;
-
-            let prev_farm = match prev_farm {
-                Some(farm) => farm,
-                None => rng.gen_range(1..=archived[0].count),
-            };
-
-            let rng_res = archived.choose_weighted(&mut rng, |entry| {
-                weight(prev_farm, entry.count, max, curr_score)
-            });
-
-            (prev_farm, rng_res)
+        let prev_farm = match prev_farm {
+            Some(farm) => farm,
+            None => rng.gen_range(1..=archived[0].count),
         };
 
+        let rng_res =
+            archived.choose_weighted(rng, |entry| weight(prev_farm, entry.count, max, curr_score));
+
         let (map_id, count) = match rng_res {
             Ok(id_count) => (id_count.map_id, id_count.count),
             Err(err) => {
@@ -99,39 +94,26 @@ impl FarmMap {
     }
 
     pub async fn image(ctx: &Context, mapset1: u32, mapset2: u32) -> Result<String> {
-        let cover1 = mapset_cover(mapset1);
-        let cover2 = mapset_cover(mapset2);
+        let blipped = split_covers(ctx, mapset1, mapset2).await?;
 
-        // Gather the map covers
-        let client = ctx.client();
-
-        let (bg_left, bg_right) = tokio::try_join!(
-            client.get_mapset_cover(&cover1),
-            client.get_mapset_cover(&cover2),
-        )
-        .wrap_err("failed to get mapset cover")?;
-
-        let bg_left =
-            image::load_from_memory(&bg_left).wrap_err("failed to load left bg from memory")?;
-
-        let bg_right =
-            image::load_from_memory(&bg_right).wrap_err("failed to load right bg from memory")?;
+        let content = format!("{mapset1} ~ {mapset2}");
 
-        // Combine the images
-        let mut blipped = ImageBuffer::new(W, H);
+        GameStateKind::upload_image(ctx, blipped.as_raw(), content).await
+    }
 
-        let iter = blipped
-            .enumerate_pixels_mut()
-            .zip(bg_left.pixels())
-            .zip(bg_right.pixels());
+    /// Recreate a stored entry of a daily challenge
+    pub async fn from_daily(ctx: &Context, entry: &HlDailyEntry) -> Result<Self> {
+        Self::new(ctx, entry.map_id, entry.farm.unwrap_or(0)).await
+    }
 
-        for (((x, _, pixel), (.., left)), (.., right)) in iter {
-            *pixel = if x <= W / 2 { left } else { right };
+    /// The ids by which the entry can be stored for a daily challenge
+    pub fn daily_entry(&self) -> HlDailyEntry {
+        HlDailyEntry {
+            map_id: self.map_id,
+            user_id: None,
+            mods: None,
+            farm: Some(self.farm),
         }
-
-        let content = format!("{mapset1} ~ {mapset2}");
-
-        GameStateKind::upload_image(ctx, blipped.as_raw(), content).await
     }
 
     async fn new(ctx: &Context, map_id: u32, farm: u32) -> Result<Self> {
@@ -156,6 +138,7 @@ impl FarmMap {
         let mapset = map.mapset.as_ref().unwrap();
 
         Ok(Self {
+            map_id,
             map_string: format!(
                 "{artist} - {title} [{version}]",
                 artist = mapset.artist,
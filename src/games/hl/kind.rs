@@ -1,4 +1,4 @@
-use std::{fmt::Write, mem, sync::Arc};
+use std::{fmt::Write, future::Future, mem, sync::Arc};
 
 use eyre::{ContextCompat, Result, WrapErr};
 use image::{codecs::png::PngEncoder, ColorType, ImageEncoder};
@@ -12,27 +12,47 @@ use crate::{
     util::{
         builder::{EmbedBuilder, MessageBuilder},
         datetime::sec_to_minsec,
-        numbers::round,
+        numbers::{round, with_comma_int},
         ChannelExt,
     },
 };
 
 use super::{
+    daily::EntrySource,
     farm_map::{FarmEntries, FarmMap},
+    map_playcount::PlaycountMap,
+    map_stars::StarsMap,
+    rank_player::RankPlayer,
     HlGuess, HlVersion, H, W,
 };
 
 pub(super) enum GameStateKind {
     ScorePp {
         mode: GameMode,
+        source: EntrySource<ScorePp>,
         previous: ScorePp,
         next: ScorePp,
     },
     FarmMaps {
-        entries: FarmEntries,
+        source: EntrySource<FarmMap, FarmEntries>,
         previous: FarmMap,
         next: FarmMap,
     },
+    RankPlayers {
+        mode: GameMode,
+        previous: RankPlayer,
+        next: RankPlayer,
+    },
+    MapPlaycount {
+        entries: FarmEntries,
+        previous: PlaycountMap,
+        next: PlaycountMap,
+    },
+    MapStars {
+        entries: FarmEntries,
+        previous: StarsMap,
+        next: StarsMap,
+    },
 }
 
 impl GameStateKind {
@@ -46,13 +66,39 @@ impl GameStateKind {
                 HlGuess::Higher => next.farm >= previous.farm,
                 HlGuess::Lower => next.farm <= previous.farm,
             },
+            Self::RankPlayers { previous, next, .. } => match guess {
+                HlGuess::Higher => next.rank >= previous.rank,
+                HlGuess::Lower => next.rank <= previous.rank,
+            },
+            Self::MapPlaycount { previous, next, .. } => match guess {
+                HlGuess::Higher => next.playcount >= previous.playcount,
+                HlGuess::Lower => next.playcount <= previous.playcount,
+            },
+            Self::MapStars { previous, next, .. } => match guess {
+                HlGuess::Higher => next.stars >= previous.stars,
+                HlGuess::Lower => next.stars <= previous.stars,
+            },
         }
     }
 
     pub async fn restart(self, ctx: &Context) -> Result<(Self, Receiver<String>)> {
         match self {
-            Self::ScorePp { mode, .. } => Self::score_pp(ctx, mode).await,
-            Self::FarmMaps { entries, .. } => Self::farm_maps(ctx, entries).await,
+            Self::ScorePp { mode, .. } => Self::score_pp(ctx, mode, EntrySource::random(())).await,
+            Self::FarmMaps { source, .. } => {
+                let entries = match source {
+                    EntrySource::Random { data, .. } => data,
+                    EntrySource::Daily(_) => ctx
+                        .redis()
+                        .osutracker_counts()
+                        .await
+                        .wrap_err("failed to get osutracker counts")?,
+                };
+
+                Self::farm_maps(ctx, EntrySource::random(entries)).await
+            }
+            Self::RankPlayers { mode, .. } => Self::rank_players(ctx, mode).await,
+            Self::MapPlaycount { entries, .. } => Self::map_playcount(ctx, entries).await,
+            Self::MapStars { entries, .. } => Self::map_stars(ctx, entries).await,
         }
     }
 
@@ -60,20 +106,28 @@ impl GameStateKind {
         let rx = match self {
             Self::ScorePp {
                 mode,
+                source,
                 previous,
                 next,
             } => {
                 let mode = *mode;
                 mem::swap(previous, next);
 
-                *next = ScorePp::random(&ctx, mode, previous.pp, curr_score)
-                    .await
-                    .wrap_err("failed to create score pp entry")?;
+                match source {
+                    EntrySource::Random { rng, .. } => {
+                        *next = ScorePp::random(&ctx, mode, previous.pp, curr_score, rng)
+                            .await
+                            .wrap_err("failed to create score pp entry")?;
 
-                while previous == next {
-                    *next = ScorePp::random(&ctx, mode, previous.pp, curr_score)
-                        .await
-                        .wrap_err("failed to create score pp entry")?;
+                        while previous == next {
+                            *next = ScorePp::random(&ctx, mode, previous.pp, curr_score, rng)
+                                .await
+                                .wrap_err("failed to create score pp entry")?;
+                        }
+                    }
+                    EntrySource::Daily(daily) => {
+                        *next = daily.get(&ctx, curr_score as usize + 1).await?;
+                    }
                 }
 
                 debug!("{}pp vs {}pp", previous.pp, next.pp);
@@ -105,14 +159,20 @@ impl GameStateKind {
                 rx
             }
             Self::FarmMaps {
-                entries,
+                source,
                 previous,
                 next,
             } => {
                 mem::swap(previous, next);
-                *next = FarmMap::random(&ctx, entries, Some(previous.farm), curr_score)
-                    .await
-                    .wrap_err("failed to create farm map entry")?;
+
+                *next = match source {
+                    EntrySource::Random { data, rng } => {
+                        FarmMap::random(&ctx, data, Some(previous.farm), curr_score, rng)
+                            .await
+                            .wrap_err("failed to create farm map entry")?
+                    }
+                    EntrySource::Daily(daily) => daily.get(&ctx, curr_score as usize + 1).await?,
+                };
 
                 debug!("farm: {} vs {}", previous.farm, next.farm);
 
@@ -137,6 +197,57 @@ impl GameStateKind {
 
                 rx
             }
+            Self::RankPlayers {
+                mode,
+                previous,
+                next,
+            } => {
+                mem::swap(previous, next);
+                *next = RankPlayer::random(&ctx, *mode, Some(&*previous), curr_score)
+                    .await
+                    .wrap_err("failed to create rank player entry")?;
+
+                debug!("rank: {} vs {}", previous.rank, next.rank);
+
+                let user1 = previous.user_id;
+                let user2 = next.user_id;
+
+                spawn_image(async move { RankPlayer::image(&ctx, user1, user2).await })
+            }
+            Self::MapPlaycount {
+                entries,
+                previous,
+                next,
+            } => {
+                mem::swap(previous, next);
+                *next = PlaycountMap::random(&ctx, entries, Some(previous.map_id))
+                    .await
+                    .wrap_err("failed to create playcount map entry")?;
+
+                debug!("playcount: {} vs {}", previous.playcount, next.playcount);
+
+                let mapset1 = previous.mapset_id;
+                let mapset2 = next.mapset_id;
+
+                spawn_image(async move { PlaycountMap::image(&ctx, mapset1, mapset2).await })
+            }
+            Self::MapStars {
+                entries,
+                previous,
+                next,
+            } => {
+                mem::swap(previous, next);
+                *next = StarsMap::random(&ctx, entries, Some(previous.map_id))
+                    .await
+                    .wrap_err("failed to create stars map entry")?;
+
+                debug!("stars: {} vs {}", previous.stars, next.stars);
+
+                let mapset1 = previous.mapset_id;
+                let mapset2 = next.mapset_id;
+
+                spawn_image(async move { StarsMap::image(&ctx, mapset1, mapset2).await })
+            }
         };
 
         Ok(rx)
@@ -144,15 +255,25 @@ impl GameStateKind {
 
     pub async fn farm_maps(
         ctx: &Context,
-        entries: FarmEntries,
+        mut source: EntrySource<FarmMap, FarmEntries>,
     ) -> Result<(Self, Receiver<String>)> {
-        let previous = FarmMap::random(ctx, &entries, None, 0)
-            .await
-            .wrap_err("failed to create farm map entry")?;
+        let (previous, next) = match source {
+            EntrySource::Random {
+                ref data,
+                ref mut rng,
+            } => {
+                let previous = FarmMap::random(ctx, data, None, 0, rng)
+                    .await
+                    .wrap_err("failed to create farm map entry")?;
 
-        let next = FarmMap::random(ctx, &entries, Some(previous.farm), 0)
-            .await
-            .wrap_err("failed to create farm map entry")?;
+                let next = FarmMap::random(ctx, data, Some(previous.farm), 0, rng)
+                    .await
+                    .wrap_err("failed to create farm map entry")?;
+
+                (previous, next)
+            }
+            EntrySource::Daily(ref daily) => (daily.get(ctx, 0).await?, daily.get(ctx, 1).await?),
+        };
 
         debug!("farm: {} vs {}", previous.farm, next.farm);
 
@@ -173,7 +294,7 @@ impl GameStateKind {
         let _ = tx.send(url);
 
         let inner = Self::FarmMaps {
-            entries,
+            source,
             previous,
             next,
         };
@@ -181,18 +302,31 @@ impl GameStateKind {
         Ok((inner, rx))
     }
 
-    pub async fn score_pp(ctx: &Context, mode: GameMode) -> Result<(Self, Receiver<String>)> {
-        let (previous, mut next) = tokio::try_join!(
-            ScorePp::random(ctx, mode, 0.0, 0),
-            ScorePp::random(ctx, mode, 0.0, 0)
-        )
-        .wrap_err("failed to create score pp entry")?;
-
-        while next == previous {
-            next = ScorePp::random(ctx, mode, 0.0, 0)
-                .await
-                .wrap_err("failed to create score pp entry")?;
-        }
+    pub async fn score_pp(
+        ctx: &Context,
+        mode: GameMode,
+        mut source: EntrySource<ScorePp>,
+    ) -> Result<(Self, Receiver<String>)> {
+        let (previous, next) = match source {
+            EntrySource::Random { ref mut rng, .. } => {
+                let previous = ScorePp::random(ctx, mode, 0.0, 0, rng)
+                    .await
+                    .wrap_err("failed to create score pp entry")?;
+
+                let mut next = ScorePp::random(ctx, mode, 0.0, 0, rng)
+                    .await
+                    .wrap_err("failed to create score pp entry")?;
+
+                while next == previous {
+                    next = ScorePp::random(ctx, mode, 0.0, 0, rng)
+                        .await
+                        .wrap_err("failed to create score pp entry")?;
+                }
+
+                (previous, next)
+            }
+            EntrySource::Daily(ref daily) => (daily.get(ctx, 0).await?, daily.get(ctx, 1).await?),
+        };
 
         debug!("{}pp vs {}pp", previous.pp, next.pp);
 
@@ -217,6 +351,85 @@ impl GameStateKind {
 
         let inner = Self::ScorePp {
             mode,
+            source,
+            previous,
+            next,
+        };
+
+        Ok((inner, rx))
+    }
+
+    pub async fn rank_players(ctx: &Context, mode: GameMode) -> Result<(Self, Receiver<String>)> {
+        let previous = RankPlayer::random(ctx, mode, None, 0)
+            .await
+            .wrap_err("failed to create rank player entry")?;
+
+        let next = RankPlayer::random(ctx, mode, Some(&previous), 0)
+            .await
+            .wrap_err("failed to create rank player entry")?;
+
+        debug!("rank: {} vs {}", previous.rank, next.rank);
+
+        let (tx, rx) = oneshot::channel();
+        let url = await_image(RankPlayer::image(ctx, previous.user_id, next.user_id)).await;
+        let _ = tx.send(url);
+
+        let inner = Self::RankPlayers {
+            mode,
+            previous,
+            next,
+        };
+
+        Ok((inner, rx))
+    }
+
+    pub async fn map_playcount(
+        ctx: &Context,
+        entries: FarmEntries,
+    ) -> Result<(Self, Receiver<String>)> {
+        let previous = PlaycountMap::random(ctx, &entries, None)
+            .await
+            .wrap_err("failed to create playcount map entry")?;
+
+        let next = PlaycountMap::random(ctx, &entries, Some(previous.map_id))
+            .await
+            .wrap_err("failed to create playcount map entry")?;
+
+        debug!("playcount: {} vs {}", previous.playcount, next.playcount);
+
+        let (tx, rx) = oneshot::channel();
+        let url = await_image(PlaycountMap::image(ctx, previous.mapset_id, next.mapset_id)).await;
+        let _ = tx.send(url);
+
+        let inner = Self::MapPlaycount {
+            entries,
+            previous,
+            next,
+        };
+
+        Ok((inner, rx))
+    }
+
+    pub async fn map_stars(
+        ctx: &Context,
+        entries: FarmEntries,
+    ) -> Result<(Self, Receiver<String>)> {
+        let previous = StarsMap::random(ctx, &entries, None)
+            .await
+            .wrap_err("failed to create stars map entry")?;
+
+        let next = StarsMap::random(ctx, &entries, Some(previous.map_id))
+            .await
+            .wrap_err("failed to create stars map entry")?;
+
+        debug!("stars: {} vs {}", previous.stars, next.stars);
+
+        let (tx, rx) = oneshot::channel();
+        let url = await_image(StarsMap::image(ctx, previous.mapset_id, next.mapset_id)).await;
+        let _ = tx.send(url);
+
+        let inner = Self::MapStars {
+            entries,
             previous,
             next,
         };
@@ -232,6 +445,7 @@ impl GameStateKind {
                 mode,
                 previous,
                 next,
+                ..
             } => {
                 title.push_str("Score PP");
 
@@ -291,6 +505,65 @@ impl GameStateKind {
                     next_hp = next.hp,
                 );
 
+                EmbedBuilder::new().description(description)
+            }
+            Self::RankPlayers {
+                mode,
+                previous,
+                next,
+            } => {
+                title.push_str("Global rank");
+
+                match mode {
+                    GameMode::Osu => {}
+                    GameMode::Taiko => title.push_str(" (taiko)"),
+                    GameMode::Catch => title.push_str(" (ctb)"),
+                    GameMode::Mania => title.push_str(" (mania)"),
+                }
+
+                let description = format!(
+                    "**__Previous:__ {prev_player}** • Rank **#{prev_rank}**\n\
+                    **__Next:__ {next_player}** • Rank **#???**",
+                    prev_player = previous.player_string,
+                    prev_rank = with_comma_int(previous.rank),
+                    next_player = next.player_string,
+                );
+
+                EmbedBuilder::new().description(description)
+            }
+            Self::MapPlaycount { previous, next, .. } => {
+                title.push_str("Map playcount");
+
+                let description = format!(
+                    "**__Previous:__ {prev_map}**\n\
+                    `{prev_stars:.2}★` • Played **{prev_playcount}** time{prev_plural}\n\
+                    **__Next:__ {next_map}**\n\
+                    `{next_stars:.2}★` • Played **???** times",
+                    prev_map = previous.map_string,
+                    prev_stars = previous.stars,
+                    prev_playcount = with_comma_int(previous.playcount),
+                    prev_plural = if previous.playcount == 1 { "" } else { "s" },
+                    next_map = next.map_string,
+                    next_stars = next.stars,
+                );
+
+                EmbedBuilder::new().description(description)
+            }
+            Self::MapStars { previous, next, .. } => {
+                title.push_str("Star rating");
+
+                let description = format!(
+                    "**__Previous:__ {prev_map} {prev_mods}**\n\
+                    Star rating: **{prev_stars:.2}★**\n\
+                    **__Next:__ {next_map} {next_mods}**\n\
+                    Star rating: **???★**",
+                    prev_map = previous.map_string,
+                    prev_mods = previous.mods_string(),
+                    prev_stars = previous.stars,
+                    next_map = next.map_string,
+                    next_mods = next.mods_string(),
+                );
+
                 EmbedBuilder::new().description(description)
             }
         };
@@ -317,6 +590,29 @@ impl GameStateKind {
                     );
                 }
             }
+            Self::RankPlayers { next, .. } => {
+                if let Some(ref mut description) = embed.description {
+                    description.truncate(description.len() - 5);
+                    let _ = write!(description, "__{}__**", with_comma_int(next.rank));
+                }
+            }
+            Self::MapPlaycount { next, .. } => {
+                if let Some(ref mut description) = embed.description {
+                    description.truncate(description.len() - 11);
+                    let _ = write!(
+                        description,
+                        "__{}__** time{plural}",
+                        with_comma_int(next.playcount),
+                        plural = if next.playcount == 1 { "" } else { "s" }
+                    );
+                }
+            }
+            Self::MapStars { next, .. } => {
+                if let Some(ref mut description) = embed.description {
+                    description.truncate(description.len() - 8);
+                    let _ = write!(description, "__{:.2}__★**", next.stars);
+                }
+            }
         }
     }
 
@@ -324,6 +620,9 @@ impl GameStateKind {
         match self {
             Self::ScorePp { .. } => HlVersion::ScorePp,
             Self::FarmMaps { .. } => HlVersion::FarmMaps,
+            Self::RankPlayers { .. } => HlVersion::RankPlayers,
+            Self::MapPlaycount { .. } => HlVersion::MapPlaycount,
+            Self::MapStars { .. } => HlVersion::MapStars,
         }
     }
 
@@ -355,3 +654,26 @@ impl GameStateKind {
         Ok(attachment.url)
     }
 }
+
+/// Await the image url, defaulting to an empty url if the image could not be created
+async fn await_image(fut: impl Future<Output = Result<String>>) -> String {
+    match fut.await {
+        Ok(url) => url,
+        Err(err) => {
+            warn!("{:?}", err.wrap_err("failed to create image"));
+
+            String::new()
+        }
+    }
+}
+
+/// Create the image in the background so it's available when needed later
+fn spawn_image(fut: impl Future<Output = Result<String>> + Send + 'static) -> Receiver<String> {
+    let (tx, rx) = oneshot::channel();
+
+    tokio::spawn(async move {
+        let _ = tx.send(await_image(fut).await);
+    });
+
+    rx
+}
@@ -0,0 +1,82 @@
+use eyre::{ContextCompat, Result, WrapErr};
+use rand::prelude::SliceRandom;
+use rosu_v2::prelude::Beatmap;
+
+use crate::core::Context;
+
+use super::{farm_map::FarmEntries, kind::GameStateKind, split_covers};
+
+pub(super) struct PlaycountMap {
+    pub map_id: u32,
+    pub map_string: String,
+    pub mapset_id: u32,
+    pub stars: f32,
+    pub playcount: u32,
+}
+
+impl PlaycountMap {
+    pub async fn random(
+        ctx: &Context,
+        entries: &FarmEntries,
+        prev_map: Option<u32>,
+    ) -> Result<Self> {
+        let map_id = random_map_id(entries, prev_map)?;
+
+        // Always request the map so that its playcount is up to date
+        let map = ctx
+            .osu()
+            .beatmap()
+            .map_id(map_id)
+            .await
+            .wrap_err("failed to request beatmap")?;
+
+        if let Err(err) = ctx.psql().insert_beatmap(&map).await {
+            warn!("{:?}", err.wrap_err("Failed to insert map into database"));
+        }
+
+        Ok(Self::new(map))
+    }
+
+    pub async fn image(ctx: &Context, mapset1: u32, mapset2: u32) -> Result<String> {
+        let blipped = split_covers(ctx, mapset1, mapset2).await?;
+        let content = format!("{mapset1} ~ {mapset2}");
+
+        GameStateKind::upload_image(ctx, blipped.as_raw(), content).await
+    }
+
+    fn new(map: Beatmap) -> Self {
+        let mapset = map.mapset.as_ref().unwrap();
+
+        Self {
+            map_id: map.map_id,
+            map_string: format!(
+                "[{artist} - {title} [{version}]]({url})",
+                artist = mapset.artist,
+                title = mapset.title,
+                version = map.version,
+                url = map.url,
+            ),
+            mapset_id: map.mapset_id,
+            stars: map.stars,
+            playcount: map.playcount,
+        }
+    }
+}
+
+/// Choose a random map id of the osutracker entries that differs from the previous map
+pub(super) fn random_map_id(entries: &FarmEntries, prev_map: Option<u32>) -> Result<u32> {
+    let archived = entries.get();
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..5 {
+        let entry = archived
+            .choose(&mut rng)
+            .wrap_err("no osutracker entries")?;
+
+        if Some(entry.map_id) != prev_map {
+            return Ok(entry.map_id);
+        }
+    }
+
+    bail!("failed to choose a map different from {prev_map:?}")
+}
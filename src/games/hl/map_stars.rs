@@ -0,0 +1,94 @@
+use eyre::{Result, WrapErr};
+use rand::prelude::SliceRandom;
+use rosu_v2::prelude::GameMods;
+
+use crate::{core::Context, embeds::get_mods, pp::PpCalculator, util::numbers::round};
+
+use super::{
+    farm_map::FarmEntries, kind::GameStateKind, map_playcount::random_map_id, split_covers,
+};
+
+pub(super) struct StarsMap {
+    pub map_id: u32,
+    pub map_string: String,
+    pub mapset_id: u32,
+    pub mods: GameMods,
+    pub stars: f32,
+}
+
+impl StarsMap {
+    pub async fn random(
+        ctx: &Context,
+        entries: &FarmEntries,
+        prev_map: Option<u32>,
+    ) -> Result<Self> {
+        let map_id = random_map_id(entries, prev_map)?;
+
+        let mods = [
+            GameMods::NoMod,
+            GameMods::Hidden,
+            GameMods::HardRock,
+            GameMods::DoubleTime,
+            GameMods::Easy,
+            GameMods::HalfTime,
+            GameMods::Hidden | GameMods::HardRock,
+            GameMods::Hidden | GameMods::DoubleTime,
+        ];
+
+        let mods = *mods.choose(&mut rand::thread_rng()).unwrap();
+
+        let map = match ctx.psql().get_beatmap(map_id, true).await {
+            Ok(map) => map,
+            Err(_) => {
+                let map = ctx
+                    .osu()
+                    .beatmap()
+                    .map_id(map_id)
+                    .await
+                    .wrap_err("failed to request beatmap")?;
+
+                if let Err(err) = ctx.psql().insert_beatmap(&map).await {
+                    warn!("{:?}", err.wrap_err("Failed to insert map into database"));
+                }
+
+                map
+            }
+        };
+
+        let stars = PpCalculator::new(ctx, map_id)
+            .await
+            .wrap_err("failed to prepare pp calculator")?
+            .mods(mods)
+            .stars();
+
+        let mapset = map.mapset.as_ref().unwrap();
+
+        Ok(Self {
+            map_id,
+            map_string: format!(
+                "[{artist} - {title} [{version}]]({url})",
+                artist = mapset.artist,
+                title = mapset.title,
+                version = map.version,
+                url = map.url,
+            ),
+            mapset_id: map.mapset_id,
+            mods,
+            stars: round(stars as f32),
+        })
+    }
+
+    pub async fn image(ctx: &Context, mapset1: u32, mapset2: u32) -> Result<String> {
+        let blipped = split_covers(ctx, mapset1, mapset2).await?;
+        let content = format!("{mapset1} ~ {mapset2}");
+
+        GameStateKind::upload_image(ctx, blipped.as_raw(), content).await
+    }
+
+    pub fn mods_string(&self) -> String {
+        match get_mods(self.mods) {
+            mods if mods.is_empty() => "+NM".to_owned(),
+            mods => mods,
+        }
+    }
+}
@@ -1,17 +1,26 @@
 use std::fmt;
 
+use eyre::{Result, WrapErr};
+use image::{GenericImageView, ImageBuffer, Rgba};
 use twilight_interactions::command::{CommandOption, CreateOption};
 use twilight_model::{
     application::component::{button::ButtonStyle, ActionRow, Button, Component},
     channel::ReactionType,
 };
 
-use crate::util::Emote;
+use crate::{core::Context, util::Emote};
 
-pub use self::state::GameState;
+pub use self::{
+    daily::{HlDaily, HlDailyEntry},
+    state::GameState,
+};
 
+mod daily;
 mod farm_map;
 mod kind;
+mod map_playcount;
+mod map_stars;
+mod rank_player;
 mod score_pp;
 mod state;
 
@@ -27,6 +36,12 @@ pub enum HlVersion {
     ScorePp = 0,
     #[option(name = "Farm", value = "farm")]
     FarmMaps = 1,
+    #[option(name = "Rank", value = "rank")]
+    RankPlayers = 2,
+    #[option(name = "Playcount", value = "playcount")]
+    MapPlaycount = 3,
+    #[option(name = "Stars", value = "stars")]
+    MapStars = 4,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -128,3 +143,42 @@ impl HlComponents {
 fn mapset_cover(mapset_id: u32) -> String {
     format!("https://assets.ppy.sh/beatmaps/{mapset_id}/covers/cover.jpg")
 }
+
+/// Combine the left half of the first mapset's cover with the right half of the second one's
+async fn split_covers(
+    ctx: &Context,
+    mapset1: u32,
+    mapset2: u32,
+) -> Result<ImageBuffer<Rgba<u8>, Vec<u8>>> {
+    let cover1 = mapset_cover(mapset1);
+    let cover2 = mapset_cover(mapset2);
+
+    // Gather the map covers
+    let client = ctx.client();
+
+    let (bg_left, bg_right) = tokio::try_join!(
+        client.get_mapset_cover(&cover1),
+        client.get_mapset_cover(&cover2),
+    )
+    .wrap_err("failed to get mapset cover")?;
+
+    let bg_left =
+        image::load_from_memory(&bg_left).wrap_err("failed to load left bg from memory")?;
+
+    let bg_right =
+        image::load_from_memory(&bg_right).wrap_err("failed to load right bg from memory")?;
+
+    // Combine the images
+    let mut blipped = ImageBuffer::new(W, H);
+
+    let iter = blipped
+        .enumerate_pixels_mut()
+        .zip(bg_left.pixels())
+        .zip(bg_right.pixels());
+
+    for (((x, _, pixel), (.., left)), (.., right)) in iter {
+        *pixel = if x <= W / 2 { left } else { right };
+    }
+
+    Ok(blipped)
+}
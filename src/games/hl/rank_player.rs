@@ -0,0 +1,113 @@
+use eyre::{Result, WrapErr};
+use image::{imageops::FilterType, GenericImageView, ImageBuffer};
+use rosu_v2::prelude::GameMode;
+
+use crate::{
+    core::Context,
+    database::RankedOsuUser,
+    util::constants::{AVATAR_URL, OSU_BASE},
+};
+
+use super::{kind::GameStateKind, H, W};
+
+const MAX_RANK: u32 = 100_000;
+
+pub(super) struct RankPlayer {
+    pub user_id: u32,
+    pub player_string: String,
+    pub rank: u32,
+}
+
+impl RankPlayer {
+    /// The higher the current score, the closer the next rank will be to the previous one
+    pub async fn random(
+        ctx: &Context,
+        mode: GameMode,
+        prev: Option<&Self>,
+        curr_score: u32,
+    ) -> Result<Self> {
+        let (min_rank, max_rank, skip_user) = match prev {
+            Some(prev) => {
+                let spread = 1.0 + 2.0 / (1.0 + curr_score as f32 / 5.0);
+                let min_rank = (prev.rank as f32 / spread) as u32;
+                let max_rank = ((prev.rank as f32 * spread) as u32).min(MAX_RANK);
+
+                (min_rank, max_rank, prev.user_id)
+            }
+            None => (1, MAX_RANK, 0),
+        };
+
+        let user = ctx
+            .psql()
+            .random_ranked_osu_user(mode, min_rank, max_rank, skip_user)
+            .await
+            .wrap_err("failed to get random ranked user")?;
+
+        let user = match user {
+            Some(user) => user,
+            // Nobody cached within the bounds, try again without them
+            None => ctx
+                .psql()
+                .random_ranked_osu_user(mode, 1, u32::MAX >> 1, skip_user)
+                .await
+                .wrap_err("failed to get random ranked user")?
+                .ok_or_else(|| eyre!("no cached users with a global rank"))?,
+        };
+
+        Ok(Self::new(user, mode))
+    }
+
+    pub async fn image(ctx: &Context, user1: u32, user2: u32) -> Result<String> {
+        let pfp1 = format!("{AVATAR_URL}{user1}");
+        let pfp2 = format!("{AVATAR_URL}{user2}");
+
+        // Gather the profile pictures
+        let client = ctx.client();
+
+        let (pfp_left, pfp_right) =
+            tokio::try_join!(client.get_avatar(&pfp1), client.get_avatar(&pfp2))
+                .wrap_err("failed to get avatar")?;
+
+        let pfp_left = image::load_from_memory(&pfp_left)
+            .wrap_err("failed to load pfp1 from memory")?
+            .resize_to_fill(W / 2, H, FilterType::Triangle);
+
+        let pfp_right = image::load_from_memory(&pfp_right)
+            .wrap_err("failed to load pfp2 from memory")?
+            .resize_to_fill(W / 2, H, FilterType::Triangle);
+
+        // Combine the images
+        let mut blipped = ImageBuffer::new(W, H);
+
+        for (x, y, pixel) in pfp_left.pixels() {
+            blipped.put_pixel(x, y, pixel);
+        }
+
+        for (x, y, pixel) in pfp_right.pixels() {
+            blipped.put_pixel(W / 2 + x, y, pixel);
+        }
+
+        let content = format!("{user1} ~ {user2}");
+
+        GameStateKind::upload_image(ctx, blipped.as_raw(), content).await
+    }
+
+    fn new(user: RankedOsuUser, mode: GameMode) -> Self {
+        let RankedOsuUser {
+            user_id,
+            username,
+            country_code,
+            global_rank,
+        } = user;
+
+        let country_code = country_code.to_lowercase();
+
+        Self {
+            user_id,
+            player_string: format!(
+                ":flag_{country_code}: [{username}]({OSU_BASE}users/{user_id}/{mode})"
+            ),
+            rank: global_rank,
+        }
+    }
+}
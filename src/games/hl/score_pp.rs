@@ -2,7 +2,7 @@ use std::{cmp::Ordering, fmt::Display};
 
 use eyre::{Report, Result, WrapErr};
 use image::{GenericImageView, ImageBuffer};
-use rand::Rng;
+use rand::{rngs::StdRng, Rng};
 use rosu_v2::{
     model::rkyv::ArchivedUserCompact,
     prelude::{Beatmap, Beatmapset, CountryCode, GameMode, GameMods, Grade, Score, Username},
@@ -19,10 +19,11 @@ use crate::{
     },
 };
 
-use super::{kind::GameStateKind, H, W};
+use super::{kind::GameStateKind, HlDailyEntry, H, W};
 
 const ALPHA_THRESHOLD: u8 = 20;
 
+#[derive(Clone)]
 pub(super) struct ScorePp {
     user_id: u32,
     pub avatar_url: String,
@@ -46,19 +47,14 @@ impl ScorePp {
         mode: GameMode,
         prev_pp: f32,
         curr_score: u32,
+        rng: &mut StdRng,
     ) -> Result<Self> {
         let max_play = 25 - curr_score.min(24);
         let min_play = 24 - 2 * curr_score.min(12);
         let max_rank = 5000 - (mode != GameMode::Osu) as u32 * 1000;
 
-        let (rank, play): (u32, u32) = {
-            let mut rng = rand::thread_rng();
-
-            (
-                rng.gen_range(1..=max_rank),
-                rng.gen_range(min_play..max_play),
-            )
-        };
+        let rank = rng.gen_range(1..=max_rank);
+        let play = rng.gen_range(min_play..max_play);
 
         let page = ((rank - 1) / 50) + 1;
         let idx = (rank - 1) % 50;
@@ -88,10 +84,60 @@ impl ScorePp {
         });
 
         let play = plays.swap_remove(play as usize);
-        let map_id = play.map.as_ref().unwrap().map_id;
+        let map = Self::map(ctx, play.map.as_ref().unwrap().map_id).await?;
+
+        Ok(Self::new(player, map, play))
+    }
+
+    /// Recreate a stored entry of a daily challenge
+    pub async fn from_daily(ctx: &Context, entry: &HlDailyEntry) -> Result<Self> {
+        let user_id = entry
+            .user_id
+            .ok_or_else(|| eyre!("missing user id in daily entry"))?;
+
+        let mut score_fut = ctx
+            .osu()
+            .beatmap_user_score(entry.map_id, user_id)
+            .mode(GameMode::Osu);
+
+        if let Some(mods) = entry.mods {
+            score_fut = score_fut.mods(mods);
+        }
+
+        let user_fut = ctx.osu().user(user_id).mode(GameMode::Osu);
+
+        let (user, score) = tokio::try_join!(user_fut, score_fut)
+            .wrap_err("failed to get user or score of daily entry")?;
+
+        let player = UserCompact {
+            avatar_url: user.avatar_url,
+            country_code: user.country_code,
+            global_rank: user
+                .statistics
+                .and_then(|stats| stats.global_rank)
+                .unwrap_or(0),
+            user_id: user.user_id,
+            username: user.username,
+        };
+
+        let map = Self::map(ctx, entry.map_id).await?;
+
+        Ok(Self::new(player, map, score.score))
+    }
 
-        let map = match ctx.psql().get_beatmap(map_id, true).await {
-            Ok(map) => map,
+    /// The ids by which the entry can be stored for a daily challenge
+    pub fn daily_entry(&self) -> HlDailyEntry {
+        HlDailyEntry {
+            map_id: self.map_id,
+            user_id: Some(self.user_id),
+            mods: Some(self.mods),
+            farm: None,
+        }
+    }
+
+    async fn map(ctx: &Context, map_id: u32) -> Result<Beatmap> {
+        match ctx.psql().get_beatmap(map_id, true).await {
+            Ok(map) => Ok(map),
             Err(_) => match ctx.osu().beatmap().map_id(map_id).await {
                 Ok(map) => {
                     // Store map in DB
@@ -99,13 +145,11 @@ impl ScorePp {
                         warn!("{:?}", err.wrap_err("Failed to insert map into database"));
                     }
 
-                    map
+                    Ok(map)
                 }
-                Err(err) => return Err(Report::new(err).wrap_err("failed to request beatmap")),
+                Err(err) => Err(Report::new(err).wrap_err("failed to request beatmap")),
             },
-        };
-
-        Ok(Self::new(player, map, play))
+        }
     }
 
     pub async fn image(
@@ -2,6 +2,7 @@ use std::{mem, sync::Arc};
 
 use eyre::{ContextCompat, Report, Result, WrapErr};
 use rosu_v2::prelude::GameMode;
+use time::{Date, OffsetDateTime};
 use tokio::sync::oneshot::Receiver;
 use twilight_model::{
     channel::embed::Embed,
@@ -16,7 +17,7 @@ use crate::{
     util::{interaction::InteractionComponent, Authored},
 };
 
-use super::{kind::GameStateKind, HlGuess, HlVersion};
+use super::{daily::EntrySource, kind::GameStateKind, HlGuess, HlVersion};
 
 pub struct GameState {
     kind: GameStateKind,
@@ -26,6 +27,8 @@ pub struct GameState {
     pub guild: Option<Id<GuildMarker>>,
     pub current_score: u32,
     pub highscore: u32,
+    /// The day of the daily challenge if this game is one
+    pub daily: Option<Date>,
 }
 
 impl GameState {
@@ -35,7 +38,7 @@ impl GameState {
         mode: GameMode,
     ) -> Result<Self> {
         let user = origin.user_id()?.get();
-        let game_fut = GameStateKind::score_pp(ctx, mode);
+        let game_fut = GameStateKind::score_pp(ctx, mode, EntrySource::random(()));
 
         let highscore_fut = ctx
             .psql()
@@ -51,6 +54,7 @@ impl GameState {
             guild: origin.guild_id(),
             current_score: 0,
             highscore,
+            daily: None,
         })
     }
 
@@ -67,7 +71,7 @@ impl GameState {
         let (entries_res, highscore_res) = tokio::join!(entries_fut, highscore_fut);
         let highscore = highscore_res.wrap_err("failed to get highscore from database")?;
 
-        let (kind, rx) = GameStateKind::farm_maps(ctx, entries_res?)
+        let (kind, rx) = GameStateKind::farm_maps(ctx, EntrySource::random(entries_res?))
             .await
             .wrap_err("failed to create farm maps game state")?;
 
@@ -79,9 +83,142 @@ impl GameState {
             guild: origin.guild_id(),
             current_score: 0,
             highscore,
+            daily: None,
         })
     }
 
+    pub async fn rank_players(
+        ctx: &Context,
+        origin: &(dyn Authored + Sync),
+        mode: GameMode,
+    ) -> Result<Self> {
+        let user = origin.user_id()?.get();
+        let game_fut = GameStateKind::rank_players(ctx, mode);
+
+        let highscore_fut = ctx
+            .psql()
+            .get_higherlower_highscore(user, HlVersion::RankPlayers);
+
+        let ((kind, rx), highscore) = tokio::try_join!(game_fut, highscore_fut)?;
+
+        Ok(Self {
+            kind,
+            img_url_rx: Some(rx),
+            msg: Id::new(1),
+            channel: origin.channel_id(),
+            guild: origin.guild_id(),
+            current_score: 0,
+            highscore,
+            daily: None,
+        })
+    }
+
+    pub async fn map_playcount(ctx: &Context, origin: &(dyn Authored + Sync)) -> Result<Self> {
+        let user = origin.user_id()?.get();
+        let redis = ctx.redis();
+
+        let entries_fut = redis.osutracker_counts();
+
+        let highscore_fut = ctx
+            .psql()
+            .get_higherlower_highscore(user, HlVersion::MapPlaycount);
+
+        let (entries_res, highscore_res) = tokio::join!(entries_fut, highscore_fut);
+        let highscore = highscore_res.wrap_err("failed to get highscore from database")?;
+
+        let (kind, rx) = GameStateKind::map_playcount(ctx, entries_res?)
+            .await
+            .wrap_err("failed to create map playcount game state")?;
+
+        Ok(Self {
+            kind,
+            img_url_rx: Some(rx),
+            msg: Id::new(1),
+            channel: origin.channel_id(),
+            guild: origin.guild_id(),
+            current_score: 0,
+            highscore,
+            daily: None,
+        })
+    }
+
+    pub async fn map_stars(ctx: &Context, origin: &(dyn Authored + Sync)) -> Result<Self> {
+        let user = origin.user_id()?.get();
+        let redis = ctx.redis();
+
+        let entries_fut = redis.osutracker_counts();
+
+        let highscore_fut = ctx
+            .psql()
+            .get_higherlower_highscore(user, HlVersion::MapStars);
+
+        let (entries_res, highscore_res) = tokio::join!(entries_fut, highscore_fut);
+        let highscore = highscore_res.wrap_err("failed to get highscore from database")?;
+
+        let (kind, rx) = GameStateKind::map_stars(ctx, entries_res?)
+            .await
+            .wrap_err("failed to create map stars game state")?;
+
+        Ok(Self {
+            kind,
+            img_url_rx: Some(rx),
+            msg: Id::new(1),
+            channel: origin.channel_id(),
+            guild: origin.guild_id(),
+            current_score: 0,
+            highscore,
+            daily: None,
+        })
+    }
+
+    /// Create the daily challenge game of the given version.
+    ///
+    /// Entries are generated once per day and shared between all users
+    /// so everyone gets the same sequence of entries on the same day.
+    pub async fn daily(
+        ctx: &Context,
+        origin: &(dyn Authored + Sync),
+        version: HlVersion,
+    ) -> Result<Self> {
+        let day = Self::today();
+
+        let (kind, rx) = match version {
+            HlVersion::ScorePp => {
+                let daily = ctx.hl_daily().score_pp(day);
+
+                GameStateKind::score_pp(ctx, GameMode::Osu, EntrySource::Daily(daily))
+                    .await
+                    .wrap_err("failed to create daily score pp game state")?
+            }
+            HlVersion::FarmMaps => {
+                let daily = ctx.hl_daily().farm_maps(ctx, day).await?;
+
+                GameStateKind::farm_maps(ctx, EntrySource::Daily(daily))
+                    .await
+                    .wrap_err("failed to create daily farm maps game state")?
+            }
+            HlVersion::RankPlayers | HlVersion::MapPlaycount | HlVersion::MapStars => {
+                bail!("no daily challenge for this version")
+            }
+        };
+
+        Ok(Self {
+            kind,
+            img_url_rx: Some(rx),
+            msg: Id::new(1),
+            channel: origin.channel_id(),
+            guild: origin.guild_id(),
+            current_score: 0,
+            highscore: 0,
+            daily: Some(day),
+        })
+    }
+
+    /// The current day of the daily challenge
+    pub fn today() -> Date {
+        OffsetDateTime::now_utc().date()
+    }
+
     pub async fn restart(self, ctx: &Context, origin: &(dyn Authored + Sync)) -> Result<Self> {
         let user = origin.user_id()?.get();
         let version = self.kind.version();
@@ -99,6 +236,7 @@ impl GameState {
             guild: origin.guild_id(),
             current_score: 0,
             highscore,
+            daily: None,
         })
     }
 
@@ -145,10 +283,14 @@ impl GameState {
         let Self {
             current_score,
             highscore,
+            daily,
             ..
         } = self;
 
-        format!("Current score: {current_score} • Highscore: {highscore}")
+        match daily {
+            Some(day) => format!("Daily challenge {day} • Current score: {current_score}"),
+            None => format!("Current score: {current_score} • Highscore: {highscore}"),
+        }
     }
 
     pub fn reveal(&self, component: &mut InteractionComponent) -> Result<Embed> {
@@ -159,6 +301,29 @@ impl GameState {
         Ok(embed)
     }
 
+    pub fn version(&self) -> HlVersion {
+        self.kind.version()
+    }
+
+    /// Store the current score as the user's daily challenge score.
+    /// Does nothing if the game is not a daily challenge.
+    pub async fn update_daily(&self, ctx: &Context, user: Id<UserMarker>) -> Result<()> {
+        let day = match self.daily {
+            Some(day) => day,
+            None => return Ok(()),
+        };
+
+        ctx.psql()
+            .update_higherlower_daily_score(
+                user.get(),
+                self.kind.version(),
+                day,
+                self.current_score,
+            )
+            .await
+            .wrap_err("failed to update daily score")
+    }
+
     pub async fn new_highscore(&self, ctx: &Context, user: Id<UserMarker>) -> Result<bool> {
         let user = user.get();
         let version = self.kind.version();
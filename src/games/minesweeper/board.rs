@@ -0,0 +1,379 @@
+use std::{fmt::Write, ops::Range};
+
+use rand::prelude::SliceRandom;
+
+use crate::util::Matrix;
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Cell {
+    Num(u8),
+    Mine,
+    None,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum CellState {
+    Hidden,
+    Revealed,
+    Flagged,
+}
+
+impl Default for CellState {
+    fn default() -> Self {
+        Self::Hidden
+    }
+}
+
+pub enum RevealResult {
+    /// The cell was already revealed or is flagged
+    Ignored,
+    Continue,
+    Mine,
+    Won,
+}
+
+pub struct Board {
+    cells: Matrix<Cell>,
+    states: Matrix<CellState>,
+    mines: usize,
+    flags: usize,
+    revealed: usize,
+    /// Mines are only placed on the first reveal so that it's always safe
+    generated: bool,
+    exploded: Option<(usize, usize)>,
+}
+
+impl Board {
+    pub fn new(width: usize, height: usize, mines: usize) -> Self {
+        Self {
+            cells: Matrix::new(width, height),
+            states: Matrix::new(width, height),
+            mines,
+            flags: 0,
+            revealed: 0,
+            generated: false,
+            exploded: None,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    pub fn mines(&self) -> usize {
+        self.mines
+    }
+
+    pub fn flags(&self) -> usize {
+        self.flags
+    }
+
+    /// Whether the first cell has been revealed yet
+    pub fn started(&self) -> bool {
+        self.generated
+    }
+
+    /// Place mines everywhere except around the given coordinates
+    fn generate(&mut self, safe_x: usize, safe_y: usize) {
+        let (w, h) = (self.width(), self.height());
+
+        let is_safe = |x: usize, y: usize, radius: usize| {
+            x.abs_diff(safe_x) <= radius && y.abs_diff(safe_y) <= radius
+        };
+
+        // If there is not enough space, only keep the clicked cell itself free
+        let radius = if w * h >= self.mines + 9 { 1 } else { 0 };
+
+        let candidates: Vec<_> = (0..h)
+            .flat_map(|y| (0..w).map(move |x| (x, y)))
+            .filter(|&(x, y)| !is_safe(x, y, radius))
+            .collect();
+
+        let mut rng = rand::thread_rng();
+
+        for &(x, y) in candidates.choose_multiple(&mut rng, self.mines) {
+            self.cells[(x, y)] = Cell::Mine;
+        }
+
+        self.place_numbers();
+        self.generated = true;
+    }
+
+    /// Put the amount of neighboring mines into all cells that are not a mine
+    fn place_numbers(&mut self) {
+        let (w, h) = (self.width(), self.height());
+
+        for x in 0..w {
+            for y in 0..h {
+                if self.cells[(x, y)] == Cell::None {
+                    let mines = self.cells.count_neighbors(x, y, Cell::Mine);
+                    self.cells[(x, y)] = Cell::Num(mines);
+                }
+            }
+        }
+    }
+
+    pub fn reveal(&mut self, x: usize, y: usize) -> RevealResult {
+        if !self.generated {
+            self.generate(x, y);
+        }
+
+        if self.states[(x, y)] != CellState::Hidden {
+            return RevealResult::Ignored;
+        }
+
+        if self.cells[(x, y)] == Cell::Mine {
+            self.states[(x, y)] = CellState::Revealed;
+            self.exploded = Some((x, y));
+
+            return RevealResult::Mine;
+        }
+
+        let (w, h) = (self.width(), self.height());
+        let mut stack = vec![(x, y)];
+
+        // Flood-fill through all cells without neighboring mines
+        while let Some((x, y)) = stack.pop() {
+            if self.states[(x, y)] != CellState::Hidden {
+                continue;
+            }
+
+            self.states[(x, y)] = CellState::Revealed;
+            self.revealed += 1;
+
+            if self.cells[(x, y)] != Cell::Num(0) {
+                continue;
+            }
+
+            for cx in x.saturating_sub(1)..w.min(x + 2) {
+                for cy in y.saturating_sub(1)..h.min(y + 2) {
+                    if self.states[(cx, cy)] == CellState::Hidden {
+                        stack.push((cx, cy));
+                    }
+                }
+            }
+        }
+
+        if self.revealed == w * h - self.mines {
+            RevealResult::Won
+        } else {
+            RevealResult::Continue
+        }
+    }
+
+    /// Flag or unflag a hidden cell, returns `false` if the cell is already revealed
+    pub fn toggle_flag(&mut self, x: usize, y: usize) -> bool {
+        match self.states[(x, y)] {
+            CellState::Hidden => {
+                self.states[(x, y)] = CellState::Flagged;
+                self.flags += 1;
+            }
+            CellState::Flagged => {
+                self.states[(x, y)] = CellState::Hidden;
+                self.flags -= 1;
+            }
+            CellState::Revealed => return false,
+        }
+
+        true
+    }
+
+    /// Render the given columns of the board as code block.
+    ///
+    /// If `finished` is set, all mines will be shown.
+    pub fn render(
+        &self,
+        columns: Range<usize>,
+        selected: (Option<usize>, Option<usize>),
+        finished: bool,
+    ) -> String {
+        let line_len = 3 * columns.len() + 3;
+        let mut content = String::with_capacity(line_len * (self.height() + 1) + 8);
+        content.push_str("```\n  ");
+
+        for x in columns.clone() {
+            let _ = write!(content, "{:^3}", x + 1);
+        }
+
+        for y in 0..self.height() {
+            let _ = write!(content, "\n{} ", row_name(y));
+
+            for x in columns.clone() {
+                let symbol = self.symbol(x, y, finished);
+
+                if selected == (Some(x), Some(y)) {
+                    let _ = write!(content, "[{symbol}]");
+                } else {
+                    let _ = write!(content, " {symbol} ");
+                }
+            }
+        }
+
+        content.push_str("\n```");
+
+        content
+    }
+
+    fn symbol(&self, x: usize, y: usize, finished: bool) -> char {
+        if self.exploded == Some((x, y)) {
+            return 'X';
+        }
+
+        match (self.states[(x, y)], self.cells[(x, y)]) {
+            (CellState::Flagged, Cell::Mine) if finished => 'F',
+            (CellState::Flagged, _) if finished => 'x',
+            (CellState::Flagged, _) => 'F',
+            (CellState::Hidden, Cell::Mine) if finished => '*',
+            (CellState::Hidden, _) => '#',
+            (CellState::Revealed, Cell::Num(0)) => '.',
+            (CellState::Revealed, Cell::Num(n)) => (b'0' + n) as char,
+            (CellState::Revealed, Cell::Mine) => '*',
+            (CellState::Revealed, Cell::None) => unreachable!(),
+        }
+    }
+}
+
+/// Rows are named by letters, starting at `A`
+pub fn row_name(y: usize) -> char {
+    (b'A' + y as u8) as char
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Board with mines at the given coordinates that counts as started
+    fn board(width: usize, height: usize, mines: &[(usize, usize)]) -> Board {
+        let mut board = Board::new(width, height, mines.len());
+
+        for &(x, y) in mines {
+            board.cells[(x, y)] = Cell::Mine;
+        }
+
+        board.place_numbers();
+        board.generated = true;
+
+        board
+    }
+
+    /// 5x5 board whose middle column is full of mines
+    fn wall() -> Board {
+        let mines: Vec<_> = (0..5).map(|y| (2, y)).collect();
+
+        board(5, 5, &mines)
+    }
+
+    fn is_revealed(board: &Board, x: usize, y: usize) -> bool {
+        board.states[(x, y)] == CellState::Revealed
+    }
+
+    #[test]
+    fn flood_fill_stops_at_numbers() {
+        let mut board = wall();
+
+        assert!(matches!(board.reveal(0, 0), RevealResult::Continue));
+        assert_eq!(board.revealed, 10);
+
+        for y in 0..5 {
+            assert!(is_revealed(&board, 0, y));
+            assert!(is_revealed(&board, 1, y));
+            assert!(!is_revealed(&board, 2, y));
+            assert!(!is_revealed(&board, 3, y));
+        }
+    }
+
+    #[test]
+    fn number_reveals_single_cell() {
+        let mut board = wall();
+
+        assert!(matches!(board.reveal(1, 2), RevealResult::Continue));
+        assert_eq!(board.revealed, 1);
+        assert!(matches!(board.reveal(1, 2), RevealResult::Ignored));
+    }
+
+    #[test]
+    fn first_click_area_is_safe() {
+        for _ in 0..20 {
+            let mut board = Board::new(9, 9, 60);
+            assert!(!matches!(board.reveal(4, 4), RevealResult::Mine));
+
+            for x in 3..=5 {
+                for y in 3..=5 {
+                    assert!(board.cells[(x, y)] != Cell::Mine);
+                }
+            }
+
+            let mines = (0..9)
+                .flat_map(|y| (0..9).map(move |x| (x, y)))
+                .filter(|&coords| board.cells[coords] == Cell::Mine)
+                .count();
+
+            assert_eq!(mines, 60);
+        }
+    }
+
+    #[test]
+    fn crowded_board_keeps_clicked_cell_safe() {
+        let mut board = Board::new(3, 3, 8);
+
+        assert!(matches!(board.reveal(1, 1), RevealResult::Won));
+        assert!(board.cells[(1, 1)] == Cell::Num(8));
+    }
+
+    #[test]
+    fn mine_loses() {
+        let mut board = wall();
+
+        assert!(matches!(board.reveal(2, 3), RevealResult::Mine));
+        assert_eq!(board.exploded, Some((2, 3)));
+        assert_eq!(board.symbol(2, 3, true), 'X');
+        assert_eq!(board.symbol(2, 0, true), '*');
+    }
+
+    #[test]
+    fn revealing_all_safe_cells_wins() {
+        let mut board = wall();
+
+        assert!(matches!(board.reveal(0, 0), RevealResult::Continue));
+        assert!(matches!(board.reveal(4, 4), RevealResult::Won));
+    }
+
+    #[test]
+    fn flags() {
+        let mut board = wall();
+
+        assert!(board.toggle_flag(2, 0));
+        assert_eq!(board.flags(), 1);
+        assert!(matches!(board.reveal(2, 0), RevealResult::Ignored));
+
+        assert!(board.toggle_flag(2, 0));
+        assert_eq!(board.flags(), 0);
+
+        board.reveal(1, 0);
+        assert!(!board.toggle_flag(1, 0));
+        assert_eq!(board.flags(), 0);
+    }
+
+    #[test]
+    fn render_columns() {
+        let mut board = wall();
+        board.reveal(0, 0);
+
+        let rendered = board.render(0..2, (Some(1), Some(0)), false);
+        let expected = "```\n   1  2 \nA  . [2]\nB  .  3 \nC  .  3 \nD  .  3 \nE  .  2 \n```";
+        assert_eq!(rendered, expected);
+
+        let rendered = board.render(3..5, (None, None), false);
+        assert!(rendered.starts_with("```\n   4  5 \nA  #  # "));
+    }
+}
@@ -0,0 +1,205 @@
+use std::sync::Arc;
+
+use eyre::{Result, WrapErr};
+
+use crate::{
+    core::Context,
+    util::{builder::MessageBuilder, interaction::InteractionComponent, Authored, ComponentExt},
+};
+
+use super::{board::RevealResult, GameState};
+
+/// Column select menu
+pub async fn handle_minesweeper_column(
+    ctx: Arc<Context>,
+    component: InteractionComponent,
+) -> Result<()> {
+    let x = parse_value(&component);
+
+    update_game(&ctx, &component, |game| {
+        if let Some(x) = x {
+            game.select_column(x);
+        }
+    })
+    .await
+}
+
+/// Row select menu
+pub async fn handle_minesweeper_row(
+    ctx: Arc<Context>,
+    component: InteractionComponent,
+) -> Result<()> {
+    let y = parse_value(&component);
+
+    update_game(&ctx, &component, |game| {
+        if let Some(y) = y {
+            game.select_row(y);
+        }
+    })
+    .await
+}
+
+/// Flag button
+pub async fn handle_minesweeper_flag(
+    ctx: Arc<Context>,
+    component: InteractionComponent,
+) -> Result<()> {
+    update_game(&ctx, &component, |game| {
+        game.flag();
+    })
+    .await
+}
+
+/// Previous page button
+pub async fn handle_minesweeper_previous(
+    ctx: Arc<Context>,
+    component: InteractionComponent,
+) -> Result<()> {
+    update_game(&ctx, &component, GameState::previous_page).await
+}
+
+/// Next page button
+pub async fn handle_minesweeper_next(
+    ctx: Arc<Context>,
+    component: InteractionComponent,
+) -> Result<()> {
+    update_game(&ctx, &component, GameState::next_page).await
+}
+
+/// Reveal button
+pub async fn handle_minesweeper_reveal(
+    ctx: Arc<Context>,
+    component: InteractionComponent,
+) -> Result<()> {
+    let user = component.user_id()?;
+    let mut guard = ctx.minesweeper_games().lock(&user).await;
+
+    let game = match guard.get_mut() {
+        Some(game) if game.msg == component.message.id => game,
+        Some(_) | None => return Ok(()),
+    };
+
+    let builder = match game.reveal() {
+        Some(RevealResult::Mine) => {
+            let game = guard.remove().unwrap();
+            drop(guard);
+
+            let embed = game.finished_embed("💥 You hit a mine, better luck next time!");
+
+            MessageBuilder::new().embed(embed).components(Vec::new())
+        }
+        Some(RevealResult::Won) => {
+            let game = guard.remove().unwrap();
+            drop(guard);
+
+            let time = game.elapsed_ms();
+            let difficulty = game.difficulty;
+            let user_id = user.get();
+
+            let best = ctx
+                .psql()
+                .get_minesweeper_best(user_id, difficulty)
+                .await
+                .wrap_err("failed to get minesweeper personal best")?;
+
+            let best_text = match best {
+                Some(best) if best <= time => {
+                    format!("Your personal best is **{}**.", format_time(best))
+                }
+                _ => {
+                    ctx.psql()
+                        .upsert_minesweeper_best(user_id, difficulty, time)
+                        .await
+                        .wrap_err("failed to upsert minesweeper personal best")?;
+
+                    "That's a new personal best :tada:".to_owned()
+                }
+            };
+
+            let text = format!(
+                "You cleared the board in **{}**! {best_text}",
+                format_time(time)
+            );
+
+            let embed = game.finished_embed(&text);
+
+            MessageBuilder::new().embed(embed).components(Vec::new())
+        }
+        Some(RevealResult::Continue | RevealResult::Ignored) | None => MessageBuilder::new()
+            .embed(game.embed())
+            .components(game.components()),
+    };
+
+    component
+        .callback(&ctx, builder)
+        .await
+        .wrap_err("failed to callback")?;
+
+    Ok(())
+}
+
+/// Give up button
+pub async fn handle_minesweeper_give_up(
+    ctx: Arc<Context>,
+    component: InteractionComponent,
+) -> Result<()> {
+    let user = component.user_id()?;
+
+    let game = {
+        let mut guard = ctx.minesweeper_games().lock(&user).await;
+
+        match guard.get() {
+            Some(game) if game.msg == component.message.id => guard.remove().unwrap(),
+            Some(_) | None => return Ok(()),
+        }
+    };
+
+    let embed = game.finished_embed("You gave up");
+    let builder = MessageBuilder::new().embed(embed).components(Vec::new());
+
+    component
+        .callback(&ctx, builder)
+        .await
+        .wrap_err("failed to callback")?;
+
+    Ok(())
+}
+
+/// Apply `f` on the user's game and update the message accordingly
+async fn update_game<F>(ctx: &Context, component: &InteractionComponent, f: F) -> Result<()>
+where
+    F: FnOnce(&mut GameState),
+{
+    let user = component.user_id()?;
+    let mut guard = ctx.minesweeper_games().lock(&user).await;
+
+    let game = match guard.get_mut() {
+        Some(game) if game.msg == component.message.id => game,
+        Some(_) | None => return Ok(()),
+    };
+
+    f(game);
+
+    let builder = MessageBuilder::new()
+        .embed(game.embed())
+        .components(game.components());
+
+    component
+        .callback(ctx, builder)
+        .await
+        .wrap_err("failed to callback")?;
+
+    Ok(())
+}
+
+fn parse_value(component: &InteractionComponent) -> Option<usize> {
+    component
+        .data
+        .values
+        .first()
+        .and_then(|value| value.parse().ok())
+}
+
+fn format_time(ms: u32) -> String {
+    format!("{}.{:03}s", ms / 1000, ms % 1000)
+}
@@ -0,0 +1,41 @@
+use twilight_interactions::command::{CommandOption, CreateOption};
+
+pub use self::state::GameState;
+
+mod board;
+mod state;
+
+pub mod components;
+
+#[derive(Copy, Clone, CommandOption, CreateOption)]
+pub enum Difficulty {
+    #[option(name = "easy", value = "easy")]
+    Easy = 0,
+    #[option(name = "medium", value = "medium")]
+    Medium = 1,
+    #[option(name = "hard", value = "hard")]
+    Hard = 2,
+    #[option(name = "expert", value = "expert")]
+    Expert = 3,
+}
+
+impl Difficulty {
+    /// Width, height, and amount of mines
+    fn dimensions(self) -> (usize, usize, usize) {
+        match self {
+            Self::Easy => (6, 6, 6),
+            Self::Medium => (8, 8, 12),
+            Self::Hard => (9, 11, 20),
+            Self::Expert => (13, 13, 40),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Easy => "Easy",
+            Self::Medium => "Medium",
+            Self::Hard => "Hard",
+            Self::Expert => "Expert",
+        }
+    }
+}
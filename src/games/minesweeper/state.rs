@@ -0,0 +1,318 @@
+use std::{fmt::Write, ops::Range};
+
+use tokio::time::Instant;
+use twilight_model::{
+    application::component::{
+        button::ButtonStyle, select_menu::SelectMenuOption, ActionRow, Button, Component,
+        SelectMenu,
+    },
+    channel::embed::Embed,
+    id::{
+        marker::{ChannelMarker, MessageMarker},
+        Id,
+    },
+};
+
+use crate::util::{
+    builder::{EmbedBuilder, FooterBuilder},
+    Emote,
+};
+
+use super::{
+    board::{row_name, Board, RevealResult},
+    Difficulty,
+};
+
+/// Boards with more columns than this are split into multiple pages
+const MAX_PAGE_WIDTH: usize = 9;
+
+pub struct GameState {
+    board: Board,
+    pub difficulty: Difficulty,
+    pub msg: Id<MessageMarker>,
+    pub channel: Id<ChannelMarker>,
+    page: usize,
+    selected_x: Option<usize>,
+    selected_y: Option<usize>,
+    /// Set on the first reveal
+    start: Option<Instant>,
+}
+
+impl GameState {
+    pub fn new(difficulty: Difficulty, channel: Id<ChannelMarker>) -> Self {
+        let (width, height, mines) = difficulty.dimensions();
+
+        Self {
+            board: Board::new(width, height, mines),
+            difficulty,
+            msg: Id::new(1),
+            channel,
+            page: 0,
+            selected_x: None,
+            selected_y: None,
+            start: None,
+        }
+    }
+
+    fn pages(&self) -> usize {
+        (self.board.width() + MAX_PAGE_WIDTH - 1) / MAX_PAGE_WIDTH
+    }
+
+    /// The columns of the current page
+    fn columns(&self) -> Range<usize> {
+        let width = self.board.width();
+        let pages = self.pages();
+        let page_width = (width + pages - 1) / pages;
+        let start = self.page * page_width;
+
+        start..(start + page_width).min(width)
+    }
+
+    pub fn select_column(&mut self, x: usize) {
+        if x < self.board.width() {
+            self.selected_x = Some(x);
+        }
+    }
+
+    pub fn select_row(&mut self, y: usize) {
+        if y < self.board.height() {
+            self.selected_y = Some(y);
+        }
+    }
+
+    pub fn previous_page(&mut self) {
+        self.page = self.page.saturating_sub(1);
+        self.deselect_offpage();
+    }
+
+    pub fn next_page(&mut self) {
+        self.page = (self.page + 1).min(self.pages() - 1);
+        self.deselect_offpage();
+    }
+
+    fn deselect_offpage(&mut self) {
+        let columns = self.columns();
+
+        if self.selected_x.filter(|x| columns.contains(x)).is_none() {
+            self.selected_x = None;
+        }
+    }
+
+    /// Reveal the selected cell, returns `None` if no cell is selected
+    pub fn reveal(&mut self) -> Option<RevealResult> {
+        let (x, y) = self.selected_x.zip(self.selected_y)?;
+
+        if !self.board.started() {
+            self.start = Some(Instant::now());
+        }
+
+        Some(self.board.reveal(x, y))
+    }
+
+    /// Toggle the flag of the selected cell, returns `false` if nothing changed
+    pub fn flag(&mut self) -> bool {
+        match self.selected_x.zip(self.selected_y) {
+            Some((x, y)) => self.board.toggle_flag(x, y),
+            None => false,
+        }
+    }
+
+    /// Milliseconds since the first reveal
+    pub fn elapsed_ms(&self) -> u32 {
+        self.start
+            .map_or(0, |start| start.elapsed().as_millis() as u32)
+    }
+
+    pub fn embed(&self) -> Embed {
+        let selected = (self.selected_x, self.selected_y);
+        let description = self.board.render(self.columns(), selected, false);
+
+        let mut footer = format!(
+            "Mines: {} • Flags: {}",
+            self.board.mines(),
+            self.board.flags()
+        );
+
+        if let Some((x, y)) = self.selected_x.zip(self.selected_y) {
+            let _ = write!(footer, " • Selected: {}{}", row_name(y), x + 1);
+        }
+
+        if self.pages() > 1 {
+            let _ = write!(footer, " • Page {}/{}", self.page + 1, self.pages());
+        }
+
+        EmbedBuilder::new()
+            .description(description)
+            .footer(FooterBuilder::new(footer))
+            .title(self.title())
+            .build()
+    }
+
+    /// Embed showing the full board and all mines, followed by the given text
+    pub fn finished_embed(&self, text: &str) -> Embed {
+        let columns = 0..self.board.width();
+        let mut description = self.board.render(columns, (None, None), true);
+        description.push('\n');
+        description.push_str(text);
+
+        EmbedBuilder::new()
+            .description(description)
+            .title(self.title())
+            .build()
+    }
+
+    fn title(&self) -> String {
+        let (w, h, _) = self.difficulty.dimensions();
+
+        format!("Minesweeper: {} ({w}x{h})", self.difficulty.name())
+    }
+
+    pub fn components(&self) -> Vec<Component> {
+        let column_options = self
+            .columns()
+            .map(|x| SelectMenuOption {
+                default: self.selected_x == Some(x),
+                description: None,
+                emoji: None,
+                label: format!("Column {}", x + 1),
+                value: x.to_string(),
+            })
+            .collect();
+
+        let column_menu = SelectMenu {
+            custom_id: "minesweeper_column".to_owned(),
+            disabled: false,
+            max_values: Some(1),
+            min_values: Some(1),
+            options: column_options,
+            placeholder: Some("Select a column".to_owned()),
+        };
+
+        let row_options = (0..self.board.height())
+            .map(|y| SelectMenuOption {
+                default: self.selected_y == Some(y),
+                description: None,
+                emoji: None,
+                label: format!("Row {}", row_name(y)),
+                value: y.to_string(),
+            })
+            .collect();
+
+        let row_menu = SelectMenu {
+            custom_id: "minesweeper_row".to_owned(),
+            disabled: false,
+            max_values: Some(1),
+            min_values: Some(1),
+            options: row_options,
+            placeholder: Some("Select a row".to_owned()),
+        };
+
+        let no_selection = self.selected_x.zip(self.selected_y).is_none();
+
+        let reveal = Button {
+            custom_id: Some("minesweeper_reveal".to_owned()),
+            disabled: no_selection,
+            emoji: None,
+            label: Some("Reveal".to_owned()),
+            style: ButtonStyle::Success,
+            url: None,
+        };
+
+        let flag = Button {
+            custom_id: Some("minesweeper_flag".to_owned()),
+            disabled: no_selection,
+            emoji: None,
+            label: Some("Flag".to_owned()),
+            style: ButtonStyle::Primary,
+            url: None,
+        };
+
+        let mut buttons = vec![Component::Button(reveal), Component::Button(flag)];
+
+        if self.pages() > 1 {
+            let previous = Button {
+                custom_id: Some("minesweeper_previous".to_owned()),
+                disabled: self.page == 0,
+                emoji: Some(Emote::SingleStepBack.reaction_type()),
+                label: None,
+                style: ButtonStyle::Secondary,
+                url: None,
+            };
+
+            let next = Button {
+                custom_id: Some("minesweeper_next".to_owned()),
+                disabled: self.page + 1 == self.pages(),
+                emoji: Some(Emote::SingleStep.reaction_type()),
+                label: None,
+                style: ButtonStyle::Secondary,
+                url: None,
+            };
+
+            buttons.push(Component::Button(previous));
+            buttons.push(Component::Button(next));
+        }
+
+        let give_up = Button {
+            custom_id: Some("minesweeper_give_up".to_owned()),
+            disabled: false,
+            emoji: None,
+            label: Some("Give up".to_owned()),
+            style: ButtonStyle::Danger,
+            url: None,
+        };
+
+        buttons.push(Component::Button(give_up));
+
+        vec![
+            Component::ActionRow(ActionRow {
+                components: vec![Component::SelectMenu(column_menu)],
+            }),
+            Component::ActionRow(ActionRow {
+                components: vec![Component::SelectMenu(row_menu)],
+            }),
+            Component::ActionRow(ActionRow {
+                components: buttons,
+            }),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_boards_have_one_page() {
+        for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+            let state = GameState::new(difficulty, Id::new(1));
+
+            assert_eq!(state.pages(), 1);
+            assert_eq!(state.columns(), 0..state.board.width());
+        }
+    }
+
+    #[test]
+    fn expert_paging() {
+        let mut state = GameState::new(Difficulty::Expert, Id::new(1));
+
+        assert_eq!(state.pages(), 2);
+        assert_eq!(state.columns(), 0..7);
+
+        state.previous_page();
+        assert_eq!(state.page, 0);
+
+        state.select_column(3);
+        state.next_page();
+        assert_eq!(state.columns(), 7..13);
+        assert_eq!(state.selected_x, None);
+
+        state.select_column(12);
+        state.next_page();
+        assert_eq!(state.page, 1);
+        assert_eq!(state.selected_x, Some(12));
+
+        state.previous_page();
+        assert_eq!(state.columns(), 0..7);
+        assert_eq!(state.selected_x, None);
+    }
+}
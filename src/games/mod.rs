@@ -1,2 +1,3 @@
 pub mod bg;
 pub mod hl;
+pub mod minesweeper;
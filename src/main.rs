@@ -136,6 +136,14 @@ async fn async_main() -> Result<()> {
     let background_ctx = Arc::clone(&ctx);
     tokio::spawn(Context::background_loop(background_ctx));
 
+    // Spawn leaderboard refresh worker
+    let leaderboard_ctx = Arc::clone(&ctx);
+    tokio::spawn(Context::leaderboard_refresh_loop(leaderboard_ctx));
+
+    // Spawn weekly digest worker
+    let digest_ctx = Arc::clone(&ctx);
+    tokio::spawn(Context::digest_loop(digest_ctx));
+
     #[cfg(feature = "matchlive")]
     {
         // Spawn osu match ticker worker
@@ -217,8 +225,8 @@ async fn async_main() -> Result<()> {
 
     #[cfg(feature = "matchlive")]
     {
-        let count = ctx.notify_match_live_shutdown().await;
-        info!("Stopped match tracking in {count} channels");
+        let count = ctx.pause_match_live().await;
+        info!("Paused match tracking in {count} channels");
     }
 
     let resume_data = ctx.cluster.down_resumable();
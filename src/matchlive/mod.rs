@@ -4,12 +4,19 @@ use std::{slice, time::Duration};
 
 use eyre::{Context as EyreContext, Report, Result};
 use tokio::time::{interval, MissedTickBehavior};
-use twilight_model::id::{
-    marker::{ChannelMarker, MessageMarker},
-    Id,
+use twilight_model::{
+    channel::embed::Embed,
+    id::{
+        marker::{ChannelMarker, MessageMarker},
+        Id,
+    },
 };
 
-use crate::{core::Context, embeds::MatchLiveEmbed};
+use crate::{
+    commands::osu::{process_match, MatchCostFormula},
+    core::Context,
+    embeds::{EmbedData, MatchCostEmbed, MatchLiveEmbed},
+};
 
 pub use self::types::*;
 
@@ -76,3 +83,27 @@ pub async fn send_match_messages(
 
     Ok(last_msg.id)
 }
+
+/// Creates the embed containing the team scores and match costs
+/// of all finished games beyond the warmups
+pub fn scoreboard_embed(tracked: &TrackedMatch, warmups: usize) -> Option<Embed> {
+    let games = tracked
+        .games
+        .get(warmups..)
+        .filter(|games| !games.is_empty())?;
+
+    let osu_match = &tracked.osu_match;
+    let finished = osu_match.end_time.is_some();
+    let match_result = process_match(games, finished, &tracked.users, MatchCostFormula::Bathbot);
+
+    let embed = MatchCostEmbed::from_parts(
+        osu_match.match_id,
+        &osu_match.name,
+        finished,
+        &tracked.users,
+        None,
+        Some(match_result),
+    )?;
+
+    Some(embed.build())
+}
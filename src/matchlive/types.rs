@@ -1,5 +1,7 @@
+use std::collections::HashMap as StdHashMap;
+
 use hashbrown::HashMap;
-use rosu_v2::prelude::OsuMatch;
+use rosu_v2::prelude::{MatchEvent, MatchGame, OsuMatch, UserCompact};
 use smallvec::SmallVec;
 use tokio::sync::Mutex;
 use twilight_model::id::{
@@ -51,11 +53,20 @@ pub struct Channel {
     pub id: Id<ChannelMarker>,
     /// Last msg in the channel
     pub msg_id: Id<MessageMarker>,
+    /// Amount of games to ignore for the scoreboard
+    pub warmups: usize,
+    /// Msg containing the scoreboard, edited after each finished game
+    pub scoreboard: Option<Id<MessageMarker>>,
 }
 
 impl Channel {
-    pub fn new(id: Id<ChannelMarker>, msg_id: Id<MessageMarker>) -> Self {
-        Self { id, msg_id }
+    pub fn new(id: Id<ChannelMarker>, msg_id: Id<MessageMarker>, warmups: usize) -> Self {
+        Self {
+            id,
+            msg_id,
+            warmups,
+            scoreboard: None,
+        }
     }
 }
 
@@ -79,13 +90,54 @@ pub struct TrackedMatch {
     pub osu_match: OsuMatch,
     /// All embeds of the match
     pub embeds: Vec<MatchLiveEmbed>,
+    /// All finished games of the match
+    pub games: Vec<MatchGame>,
+    /// All users that took part in the match so far
+    pub users: StdHashMap<u32, UserCompact>,
 }
 
 impl TrackedMatch {
     pub fn new(osu_match: OsuMatch, embeds: MatchLiveEmbeds) -> Self {
+        let games = Self::finished_games(&osu_match).cloned().collect();
+        let users = osu_match.users.clone();
+
         Self {
             osu_match,
             embeds: embeds.into_vec(),
+            games,
+            users,
         }
     }
+
+    /// Add the newly finished games of the update.
+    ///
+    /// Returns whether any game finished.
+    pub fn update_games(&mut self, next_match: &OsuMatch) -> bool {
+        let users = next_match
+            .users
+            .iter()
+            .map(|(id, user)| (*id, user.clone()));
+        self.users.extend(users);
+
+        let len = self.games.len();
+
+        for game in Self::finished_games(next_match) {
+            if self.games.iter().all(|g| g.game_id != game.game_id) {
+                self.games.push(game.clone());
+            }
+        }
+
+        self.games.len() > len
+    }
+
+    fn finished_games(osu_match: &OsuMatch) -> impl Iterator<Item = &MatchGame> {
+        osu_match.events.iter().filter_map(|event| match event {
+            MatchEvent::Game { game, .. } if game.end_time.is_some() => {
+                let game: &MatchGame = game;
+
+                Some(game)
+            }
+            _ => None,
+        })
+    }
 }
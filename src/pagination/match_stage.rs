@@ -0,0 +1,24 @@
+use command_macros::pagination;
+use twilight_model::channel::embed::Embed;
+
+use crate::{
+    commands::osu::{MatchStageView, StageStats},
+    embeds::{EmbedData, MatchStageEmbed},
+};
+
+use super::Pages;
+
+#[pagination(per_page = 10, total = "total")]
+pub struct MatchStagePagination {
+    stats: StageStats,
+    view: MatchStageView,
+    title: String,
+    formula: &'static str,
+    total: usize,
+}
+
+impl MatchStagePagination {
+    pub fn build_page(&mut self, pages: &Pages) -> Embed {
+        MatchStageEmbed::new(&self.stats, self.view, &self.title, self.formula, pages).build()
+    }
+}
@@ -21,14 +21,17 @@ use crate::{
 
 pub use self::{
     badges::*, command_count::*, common::*, country_snipe_list::*, leaderboard::*, map::*,
-    map_search::*, match_compare::*, medal_recent::*, medals_common::*, medals_list::*,
-    medals_missing::*, most_played::*, most_played_common::*, nochoke::*, osekai_medal_count::*,
-    osekai_medal_rarity::*, osustats_globals::*, osustats_list::*, osutracker_countrytop::*,
-    osutracker_mappers::*, osutracker_maps::*, osutracker_mapsets::*, osutracker_mods::*,
-    player_snipe_list::*, profile::*, ranking::*, ranking_countries::*, recent_list::*, scores::*,
-    sniped_difference::*, top::*, top_if::*,
+    map_search::*, match_compare::*, match_stage::*, medal_recent::*, medals_common::*,
+    medals_list::*, medals_missing::*, most_played::*, most_played_common::*, nochoke::*,
+    osekai_medal_count::*, osekai_medal_rarity::*, osustats_globals::*, osustats_list::*,
+    osutracker_countrytop::*, osutracker_mappers::*, osutracker_maps::*, osutracker_mapsets::*,
+    osutracker_mods::*, player_snipe_list::*, profile::*, ranking::*, ranking_countries::*,
+    recent_list::*, scores::*, sniped_difference::*, top::*, top_if::*,
 };
 
+#[cfg(feature = "osutracking")]
+pub use self::track_history::*;
+
 mod badges;
 mod command_count;
 mod common;
@@ -37,6 +40,7 @@ mod leaderboard;
 mod map;
 mod map_search;
 mod match_compare;
+mod match_stage;
 mod medal_recent;
 mod medals_common;
 mod medals_list;
@@ -63,6 +67,9 @@ mod sniped_difference;
 mod top;
 mod top_if;
 
+#[cfg(feature = "osutracking")]
+mod track_history;
+
 pub mod components;
 
 pub enum PaginationKind {
@@ -74,6 +81,7 @@ pub enum PaginationKind {
     Map(Box<MapPagination>),
     MapSearch(Box<MapSearchPagination>),
     MatchCompare(Box<MatchComparePagination>),
+    MatchStage(Box<MatchStagePagination>),
     MedalCount(Box<MedalCountPagination>),
     MedalRarity(Box<MedalRarityPagination>),
     MedalRecent(Box<MedalRecentPagination>),
@@ -101,6 +109,8 @@ pub enum PaginationKind {
     TopCondensed(Box<TopCondensedPagination>),
     TopIf(Box<TopIfPagination>),
     TopSingle(Box<TopSinglePagination>),
+    #[cfg(feature = "osutracking")]
+    TrackHistory(Box<TrackHistoryPagination>),
 }
 
 impl PaginationKind {
@@ -114,6 +124,7 @@ impl PaginationKind {
             Self::Map(kind) => kind.build_page(ctx, pages).await,
             Self::MapSearch(kind) => kind.build_page(ctx, pages).await,
             Self::MatchCompare(kind) => Ok(kind.build_page(pages)),
+            Self::MatchStage(kind) => Ok(kind.build_page(pages)),
             Self::MedalCount(kind) => Ok(kind.build_page(pages)),
             Self::MedalRarity(kind) => Ok(kind.build_page(pages)),
             Self::MedalRecent(kind) => Ok(kind.build_page(pages)),
@@ -141,6 +152,8 @@ impl PaginationKind {
             Self::TopCondensed(kind) => Ok(kind.build_page(ctx, pages).await),
             Self::TopIf(kind) => Ok(kind.build_page(pages).await),
             Self::TopSingle(kind) => kind.build_page(ctx, pages).await,
+            #[cfg(feature = "osutracking")]
+            Self::TrackHistory(kind) => Ok(kind.build_page(pages)),
         }
     }
 }
@@ -0,0 +1,22 @@
+use command_macros::pagination;
+use twilight_model::channel::embed::Embed;
+
+use crate::{
+    database::TrackingNotification,
+    embeds::{EmbedData, TrackHistoryEmbed},
+};
+
+use super::Pages;
+
+#[pagination(per_page = 10, entries = "notifs")]
+pub struct TrackHistoryPagination {
+    notifs: Vec<TrackingNotification>,
+}
+
+impl TrackHistoryPagination {
+    pub fn build_page(&mut self, pages: &Pages) -> Embed {
+        let end_idx = self.notifs.len().min(pages.index + pages.per_page);
+
+        TrackHistoryEmbed::new(&self.notifs[pages.index..end_idx], pages).build()
+    }
+}
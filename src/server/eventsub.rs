@@ -0,0 +1,448 @@
+#![cfg(feature = "twitchtracking")]
+
+use std::sync::Arc;
+
+use eyre::{ContextCompat, Result, WrapErr};
+use hyper::{
+    body::HttpBody,
+    header::{CONTENT_LENGTH, CONTENT_TYPE},
+    Body, Request, Response, StatusCode,
+};
+use ring::hmac;
+use routerify::ext::RequestExt;
+use serde::Deserialize;
+use time::{format_description::well_known::Rfc3339, Duration, OffsetDateTime};
+
+use crate::{
+    custom_client::TwitchSubscription,
+    tracking::{TwitchEvent, TwitchEventSub},
+};
+
+use super::{Context_, HandlerResult};
+
+const MESSAGE_ID: &str = "Twitch-Eventsub-Message-Id";
+const MESSAGE_TIMESTAMP: &str = "Twitch-Eventsub-Message-Timestamp";
+const MESSAGE_SIGNATURE: &str = "Twitch-Eventsub-Message-Signature";
+const MESSAGE_TYPE: &str = "Twitch-Eventsub-Message-Type";
+
+/// EventSub payloads are only a few KB so anything larger is rejected before buffering
+const MAX_BODY_SIZE: usize = 64 * 1024;
+
+#[derive(Deserialize)]
+struct EventSubPayload {
+    subscription: TwitchSubscription,
+    /// Only present for verification requests
+    challenge: Option<String>,
+}
+
+/// Headers that accompany every EventSub message
+struct EventSubHeaders {
+    message_id: String,
+    timestamp: String,
+    signature: String,
+    kind: String,
+}
+
+pub async fn eventsub_handler(req: Request<Body>) -> HandlerResult {
+    let Context_(ctx) = req.data().unwrap();
+    let ctx = Arc::clone(ctx);
+    let eventsub = ctx.twitch_eventsub();
+
+    let secret = match eventsub.secret() {
+        Some(secret) => secret,
+        None => return respond(StatusCode::NOT_FOUND, "EventSub is disabled"),
+    };
+
+    let header = |name| {
+        req.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned)
+    };
+
+    let headers = (
+        header(MESSAGE_ID),
+        header(MESSAGE_TIMESTAMP),
+        header(MESSAGE_SIGNATURE),
+        header(MESSAGE_TYPE),
+    );
+
+    let headers = match headers {
+        (Some(message_id), Some(timestamp), Some(signature), Some(kind)) => EventSubHeaders {
+            message_id,
+            timestamp,
+            signature,
+            kind,
+        },
+        _ => return respond(StatusCode::BAD_REQUEST, "Missing EventSub headers"),
+    };
+
+    let content_len = header(CONTENT_LENGTH).and_then(|len| len.parse::<usize>().ok());
+
+    if content_len.map_or(false, |len| len > MAX_BODY_SIZE) {
+        return respond(StatusCode::PAYLOAD_TOO_LARGE, "Body too large");
+    }
+
+    let bytes = match read_body(req.into_body()).await? {
+        Some(bytes) => bytes,
+        None => return respond(StatusCode::PAYLOAD_TOO_LARGE, "Body too large"),
+    };
+
+    handle_message(
+        eventsub,
+        secret,
+        &headers,
+        &bytes,
+        OffsetDateTime::now_utc(),
+    )
+}
+
+/// Verify the message and pass it on to the [`TwitchEventSub`]
+fn handle_message(
+    eventsub: &TwitchEventSub,
+    secret: &str,
+    headers: &EventSubHeaders,
+    bytes: &[u8],
+    now: OffsetDateTime,
+) -> HandlerResult {
+    let EventSubHeaders {
+        message_id,
+        timestamp,
+        signature,
+        kind,
+    } = headers;
+
+    if !verify_signature(secret, message_id, timestamp, bytes, signature) {
+        return respond(StatusCode::FORBIDDEN, "Invalid signature");
+    }
+
+    if is_expired(timestamp, now) {
+        return respond(StatusCode::FORBIDDEN, "Expired message");
+    }
+
+    let payload: EventSubPayload = serde_json::from_slice(bytes).wrap_err_with(|| {
+        let body = String::from_utf8_lossy(bytes);
+
+        format!("failed to deserialize EventSub payload: {body}")
+    })?;
+
+    match kind.as_str() {
+        "webhook_callback_verification" => {
+            let challenge = payload.challenge.wrap_err("missing EventSub challenge")?;
+            debug!("Verified twitch subscription {}", payload.subscription.id);
+            eventsub.confirm(payload.subscription);
+
+            let response = Response::builder()
+                .header(CONTENT_TYPE, "text/plain")
+                .body(Body::from(challenge))?;
+
+            Ok(response)
+        }
+        "notification" => {
+            // Twitch may resend notifications
+            if eventsub.register_message(message_id) {
+                let subscription = payload.subscription;
+                let user_id = subscription.condition.broadcaster_user_id;
+
+                match TwitchEvent::new(&subscription.kind, user_id) {
+                    Some(event) => eventsub.forward(event),
+                    None => warn!("Received unexpected twitch event `{}`", subscription.kind),
+                }
+            }
+
+            respond(StatusCode::NO_CONTENT, "")
+        }
+        "revocation" => {
+            let subscription = payload.subscription;
+
+            warn!(
+                "Twitch revoked `{}` subscription for user {}: {}",
+                subscription.kind, subscription.condition.broadcaster_user_id, subscription.status
+            );
+
+            eventsub.revoke(&subscription);
+
+            respond(StatusCode::NO_CONTENT, "")
+        }
+        _ => respond(StatusCode::BAD_REQUEST, "Unknown message type"),
+    }
+}
+
+/// Reject messages whose timestamp is more than 10 minutes off
+/// in either direction to prevent replay attacks
+fn is_expired(timestamp: &str, now: OffsetDateTime) -> bool {
+    OffsetDateTime::parse(timestamp, &Rfc3339).map_or(true, |timestamp| {
+        (now - timestamp).abs() > Duration::minutes(10)
+    })
+}
+
+fn respond(status: StatusCode, content: &'static str) -> HandlerResult {
+    let response = Response::builder()
+        .status(status)
+        .body(Body::from(content))?;
+
+    Ok(response)
+}
+
+/// Read the body, returns `None` if it exceeds [`MAX_BODY_SIZE`].
+///
+/// The `Content-Length` header is not required to be present,
+/// e.g. for chunked bodies, so the size is checked while reading.
+async fn read_body(mut body: Body) -> Result<Option<Vec<u8>>> {
+    let mut bytes = Vec::new();
+
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.wrap_err("failed to read EventSub body")?;
+
+        if bytes.len() + chunk.len() > MAX_BODY_SIZE {
+            return Ok(None);
+        }
+
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok(Some(bytes))
+}
+
+/// Check that the signature is the HMAC-SHA256 of the message id,
+/// timestamp, and body, using the subscription secret as key.
+fn verify_signature(
+    secret: &str,
+    message_id: &str,
+    timestamp: &str,
+    body: &[u8],
+    signature: &str,
+) -> bool {
+    let tag = match signature.strip_prefix("sha256=").and_then(decode_hex) {
+        Some(tag) => tag,
+        None => return false,
+    };
+
+    let mut message = Vec::with_capacity(message_id.len() + timestamp.len() + body.len());
+    message.extend_from_slice(message_id.as_bytes());
+    message.extend_from_slice(timestamp.as_bytes());
+    message.extend_from_slice(body);
+
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+
+    hmac::verify(&key, &message, &tag).is_ok()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Write;
+
+    use serde_json::{json, Value};
+
+    use crate::tracking::SUBSCRIPTION_KINDS;
+
+    use super::*;
+
+    const SIGNATURE: &str =
+        "sha256=f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8";
+
+    #[test]
+    fn test_valid_signature() {
+        let body = b"jumps over the lazy dog";
+
+        assert!(verify_signature(
+            "key",
+            "The quick ",
+            "brown fox ",
+            body,
+            SIGNATURE
+        ));
+    }
+
+    #[test]
+    fn test_invalid_signature() {
+        let body = b"jumps over the lazy cat";
+
+        assert!(!verify_signature(
+            "key",
+            "The quick ",
+            "brown fox ",
+            body,
+            SIGNATURE
+        ));
+
+        assert!(!verify_signature("key", "", "", b"", "sha256=xyz"));
+        assert!(!verify_signature("key", "", "", b"", "md5=00"));
+    }
+
+    const SECRET: &str = "0123456789abcdef";
+    const CALLBACK: &str = "https://localhost/twitch/eventsub";
+    const TIMESTAMP: &str = "2022-12-10T12:00:00Z";
+    const USER: u64 = 1234;
+
+    fn now() -> OffsetDateTime {
+        OffsetDateTime::parse(TIMESTAMP, &Rfc3339).unwrap()
+    }
+
+    fn eventsub() -> TwitchEventSub {
+        TwitchEventSub::new(CALLBACK.to_owned(), Some(SECRET.to_owned()))
+    }
+
+    fn subscription(id: &str, kind: &str) -> Value {
+        json!({
+            "id": id,
+            "status": "enabled",
+            "type": kind,
+            "condition": { "broadcaster_user_id": USER.to_string() },
+            "transport": { "method": "webhook", "callback": CALLBACK },
+        })
+    }
+
+    fn send(
+        eventsub: &TwitchEventSub,
+        kind: &str,
+        message_id: &str,
+        body: Value,
+    ) -> Response<Body> {
+        let bytes = body.to_string().into_bytes();
+
+        let mut message = Vec::new();
+        message.extend_from_slice(message_id.as_bytes());
+        message.extend_from_slice(TIMESTAMP.as_bytes());
+        message.extend_from_slice(&bytes);
+
+        let key = hmac::Key::new(hmac::HMAC_SHA256, SECRET.as_bytes());
+        let tag = hmac::sign(&key, &message);
+
+        let signature = tag
+            .as_ref()
+            .iter()
+            .fold(String::from("sha256="), |mut hex, byte| {
+                let _ = write!(hex, "{byte:02x}");
+
+                hex
+            });
+
+        let headers = EventSubHeaders {
+            message_id: message_id.to_owned(),
+            timestamp: TIMESTAMP.to_owned(),
+            signature,
+            kind: kind.to_owned(),
+        };
+
+        handle_message(eventsub, SECRET, &headers, &bytes, now()).unwrap()
+    }
+
+    fn confirm_all(eventsub: &TwitchEventSub) {
+        for (i, kind) in SUBSCRIPTION_KINDS.into_iter().enumerate() {
+            let body = json!({
+                "subscription": subscription(&format!("sub{i}"), kind),
+                "challenge": "challenge",
+            });
+
+            send(
+                eventsub,
+                "webhook_callback_verification",
+                &format!("msg{i}"),
+                body,
+            );
+        }
+    }
+
+    #[test]
+    fn test_expired_timestamp() {
+        let now = now();
+
+        assert!(!is_expired(TIMESTAMP, now + Duration::minutes(5)));
+        assert!(!is_expired(TIMESTAMP, now - Duration::minutes(5)));
+        assert!(is_expired(TIMESTAMP, now + Duration::minutes(11)));
+        assert!(is_expired(TIMESTAMP, now - Duration::minutes(11)));
+        assert!(is_expired("yesterday", now));
+    }
+
+    #[tokio::test]
+    async fn test_challenge_response() {
+        let eventsub = eventsub();
+
+        let body = json!({
+            "subscription": subscription("sub", "stream.online"),
+            "challenge": "pogchamp-kappa-360noscope-vohiyo",
+        });
+
+        let response = send(&eventsub, "webhook_callback_verification", "msg", body);
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(bytes.as_ref(), b"pogchamp-kappa-360noscope-vohiyo");
+
+        // Only one of the subscription kinds is verified so far
+        assert!(!eventsub.is_subscribed(USER));
+
+        confirm_all(&eventsub);
+        assert!(eventsub.is_subscribed(USER));
+    }
+
+    #[test]
+    fn test_duplicate_message() {
+        let eventsub = eventsub();
+        let mut rx = eventsub.take_receiver().unwrap();
+
+        let body = json!({ "subscription": subscription("sub", "stream.online") });
+
+        let response = send(&eventsub, "notification", "msg", body.clone());
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = send(&eventsub, "notification", "msg", body);
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        assert!(matches!(rx.try_recv(), Ok(TwitchEvent::Online(USER))));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_revocation() {
+        let eventsub = eventsub();
+        confirm_all(&eventsub);
+        assert!(eventsub.is_subscribed(USER));
+
+        let mut sub = subscription("sub1", SUBSCRIPTION_KINDS[1]);
+        sub["status"] = "authorization_revoked".into();
+
+        let response = send(
+            &eventsub,
+            "revocation",
+            "revoke",
+            json!({ "subscription": sub }),
+        );
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        // The user needs to be polled again
+        assert!(!eventsub.is_subscribed(USER));
+    }
+
+    #[test]
+    fn test_tampered_message() {
+        let eventsub = eventsub();
+        let bytes = json!({ "subscription": subscription("sub", "stream.online") }).to_string();
+
+        let headers = EventSubHeaders {
+            message_id: "msg".to_owned(),
+            timestamp: TIMESTAMP.to_owned(),
+            signature: SIGNATURE.to_owned(),
+            kind: "notification".to_owned(),
+        };
+
+        let response =
+            handle_message(&eventsub, SECRET, &headers, bytes.as_bytes(), now()).unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
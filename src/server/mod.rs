@@ -34,6 +34,7 @@ pub use self::auth::{
 };
 
 mod auth;
+mod eventsub;
 
 pub async fn run_server(ctx: Arc<Context>, shutdown_rx: Receiver<()>) {
     let ip = BotConfig::get().server.internal_ip;
@@ -97,7 +98,7 @@ fn router(ctx: Arc<Context>) -> Router<Body, Report> {
         .register_template_file("auth", path)
         .expect("failed to register auth template to handlebars");
 
-    Router::builder()
+    let builder = Router::builder()
         .data(Client(client))
         .data(Context_(ctx))
         .data(Handlebars_(handlebars))
@@ -113,7 +114,12 @@ fn router(ctx: Arc<Context>) -> Router<Body, Report> {
         .get("/auth/twitch", auth_twitch_handler)
         .get("/auth/auth.css", auth_css_handler)
         .get("/auth/icon.svg", auth_icon_handler)
-        .get("/osudirect/:mapset_id", osudirect_handler)
+        .get("/osudirect/:mapset_id", osudirect_handler);
+
+    #[cfg(feature = "twitchtracking")]
+    let builder = builder.post("/twitch/eventsub", eventsub::eventsub_handler);
+
+    builder
         .any(handle_404)
         .err_handler(error_handler)
         .build()
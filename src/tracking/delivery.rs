@@ -0,0 +1,196 @@
+#![cfg(any(feature = "osutracking", feature = "twitchtracking"))]
+
+use eyre::{Report, Result, WrapErr};
+use twilight_http::{
+    api_error::{ApiError, GeneralApiError},
+    error::ErrorType,
+    Error as TwilightError,
+};
+use twilight_model::{
+    channel::embed::Embed,
+    id::{
+        marker::{ChannelMarker, MessageMarker},
+        Id,
+    },
+};
+
+use crate::{
+    database::TrackingWebhook,
+    util::constants::{UNKNOWN_CHANNEL, UNKNOWN_WEBHOOK},
+    Context,
+};
+
+pub enum DeliveryError {
+    /// The channel no longer exists so its tracks should be removed
+    UnknownChannel,
+    Other(Report),
+}
+
+impl From<TwilightError> for DeliveryError {
+    fn from(err: TwilightError) -> Self {
+        match api_error_code(&err) {
+            Some(UNKNOWN_CHANNEL) => Self::UnknownChannel,
+            _ => Self::Other(Report::new(err)),
+        }
+    }
+}
+
+/// A notification that was delivered to a channel
+pub struct DeliveredNotification {
+    pub channel: Id<ChannelMarker>,
+    pub message: Id<MessageMarker>,
+    /// The webhook through which the notification was executed, if any
+    webhook: Option<TrackingWebhook>,
+}
+
+impl DeliveredNotification {
+    /// Replace the notification's embed
+    pub async fn edit(&self, ctx: &Context, embed: Embed) -> Result<()> {
+        let embeds = [embed];
+
+        match self.webhook {
+            Some(ref webhook) => {
+                ctx.http
+                    .update_webhook_message(webhook.webhook_id, &webhook.token, self.message)
+                    .embeds(Some(&embeds))
+                    .wrap_err("invalid embed")?
+                    .exec()
+                    .await
+                    .wrap_err("failed to update webhook message")?;
+            }
+            None => {
+                ctx.http
+                    .update_message(self.channel, self.message)
+                    .embeds(Some(&embeds))
+                    .wrap_err("invalid embed")?
+                    .exec()
+                    .await
+                    .wrap_err("failed to update message")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Send a tracking notification to the channel.
+///
+/// If a webhook is set for the channel, the notification will be executed through it.
+/// Otherwise, or if the webhook was deleted, the bot sends the notification itself.
+pub async fn deliver_notification(
+    ctx: &Context,
+    channel: Id<ChannelMarker>,
+    embed: Embed,
+    content: Option<&str>,
+) -> Result<DeliveredNotification, DeliveryError> {
+    let embeds = [embed];
+
+    if let Some(webhook) = ctx.tracking_webhook(channel) {
+        match execute_webhook(ctx, &webhook, &embeds, content).await {
+            Ok(message) => {
+                let notif = DeliveredNotification {
+                    channel,
+                    message,
+                    webhook: Some(webhook),
+                };
+
+                return Ok(notif);
+            }
+            Err(DeliveryError::Other(report)) => {
+                let is_unknown_webhook = report
+                    .downcast_ref::<TwilightError>()
+                    .and_then(api_error_code)
+                    .map_or(false, |code| code == UNKNOWN_WEBHOOK);
+
+                if !is_unknown_webhook {
+                    return Err(DeliveryError::Other(report));
+                }
+
+                // The webhook was deleted so fall back to regular messages from now on
+                if let Err(err) = ctx.remove_tracking_webhook(channel).await {
+                    let wrap = format!("Failed to remove unknown webhook of channel {channel}");
+                    warn!("{:?}", err.wrap_err(wrap));
+                } else {
+                    debug!("Removed unknown tracking webhook of channel {channel}");
+                }
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    let mut req = ctx
+        .http
+        .create_message(channel)
+        .embeds(&embeds)
+        .map_err(|err| DeliveryError::Other(Report::new(err).wrap_err("invalid embed")))?;
+
+    if let Some(content) = content {
+        req = req
+            .content(content)
+            .map_err(|err| DeliveryError::Other(Report::new(err).wrap_err("invalid content")))?;
+    }
+
+    let message = req
+        .exec()
+        .await?
+        .model()
+        .await
+        .map_err(|err| DeliveryError::Other(Report::new(err).wrap_err("invalid message")))?;
+
+    let notif = DeliveredNotification {
+        channel,
+        message: message.id,
+        webhook: None,
+    };
+
+    Ok(notif)
+}
+
+async fn execute_webhook(
+    ctx: &Context,
+    webhook: &TrackingWebhook,
+    embeds: &[Embed],
+    content: Option<&str>,
+) -> Result<Id<MessageMarker>, DeliveryError> {
+    let mut req = ctx
+        .http
+        .execute_webhook(webhook.webhook_id, &webhook.token)
+        .embeds(embeds)
+        .map_err(|err| DeliveryError::Other(Report::new(err).wrap_err("invalid embed")))?;
+
+    if let Some(content) = content {
+        req = req
+            .content(content)
+            .map_err(|err| DeliveryError::Other(Report::new(err).wrap_err("invalid content")))?;
+    }
+
+    if let Some(ref username) = webhook.username {
+        req = req.username(username).map_err(|err| {
+            DeliveryError::Other(Report::new(err).wrap_err("invalid webhook username"))
+        })?;
+    }
+
+    if let Some(ref avatar_url) = webhook.avatar_url {
+        req = req.avatar_url(avatar_url);
+    }
+
+    let message = req
+        .wait()
+        .exec()
+        .await?
+        .model()
+        .await
+        .map_err(|err| DeliveryError::Other(Report::new(err).wrap_err("invalid message")))?;
+
+    Ok(message.id)
+}
+
+fn api_error_code(err: &TwilightError) -> Option<u64> {
+    match err.kind() {
+        ErrorType::Response {
+            error: ApiError::General(GeneralApiError { code, .. }),
+            ..
+        } => Some(*code),
+        _ => None,
+    }
+}
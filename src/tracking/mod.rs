@@ -1,3 +1,6 @@
+#[cfg(any(feature = "osutracking", feature = "twitchtracking"))]
+pub use self::delivery::{deliver_notification, DeliveredNotification, DeliveryError};
+
 #[cfg(feature = "osutracking")]
 pub use self::osu::{
     osu_loop::{osu_tracking_loop, process_osu_tracking},
@@ -7,5 +10,9 @@ pub use self::osu::{
 #[cfg(feature = "twitchtracking")]
 pub use self::twitch::twitch_loop::twitch_tracking_loop;
 
+#[cfg(all(feature = "server", feature = "twitchtracking"))]
+pub use self::twitch::eventsub::{TwitchEvent, TwitchEventSub, SUBSCRIPTION_KINDS};
+
+mod delivery;
 mod osu;
 mod twitch;
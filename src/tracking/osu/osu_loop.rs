@@ -3,14 +3,14 @@ use std::{borrow::Cow, sync::Arc};
 use eyre::Report;
 use hashbrown::HashMap;
 use rosu_v2::{
-    prelude::{GameMode, OsuError, Score, User},
+    prelude::{
+        GameMode, OsuError,
+        RankStatus::{Approved, Loved, Qualified, Ranked},
+        Score, User,
+    },
     OsuResult,
 };
 use time::OffsetDateTime;
-use twilight_http::{
-    api_error::{ApiError, GeneralApiError},
-    error::ErrorType as TwilightErrorType,
-};
 use twilight_model::{
     channel::embed::Embed,
     id::{marker::ChannelMarker, Id},
@@ -18,33 +18,53 @@ use twilight_model::{
 
 use crate::{
     commands::osu::prepare_score,
+    database::{TrackingFilters, TrackingNotification},
     embeds::{EmbedData, TrackNotificationEmbed},
-    util::{constants::UNKNOWN_CHANNEL, hasher::IntHasher},
+    pp::PpCalculator,
+    tracking::{deliver_notification, DeliveryError},
+    util::hasher::IntHasher,
     Context,
 };
 
-use super::osu_queue::TrackingEntry;
+use super::osu_queue::{TrackingEntry, TrackingKind};
 
 #[cold]
 pub async fn osu_tracking_loop(ctx: Arc<Context>) {
     loop {
         if let Some((entry, amount)) = ctx.tracking().pop().await {
-            let TrackingEntry { user_id, mode } = entry;
+            let TrackingEntry {
+                user_id,
+                mode,
+                kind,
+            } = entry;
+
+            let scores_fut = ctx.osu().user_scores(user_id);
 
-            let scores_fut = ctx
-                .osu()
-                .user_scores(user_id)
-                .best()
-                .mode(mode)
-                .limit(amount);
+            let scores_fut = match kind {
+                TrackingKind::Top => scores_fut.best(),
+                TrackingKind::Recent => scores_fut.recent().include_fails(false),
+            };
 
-            match scores_fut.await {
-                Ok(mut scores) => {
+            match scores_fut.mode(mode).limit(amount).await {
+                Ok(mut scores) => match kind {
                     // * Note: If scores are empty, (user_id, mode) will not be reset into the tracking queue
-                    if !scores.is_empty() {
-                        process_osu_tracking(&ctx, &mut scores, None).await
+                    TrackingKind::Top => {
+                        if !scores.is_empty() {
+                            process_osu_tracking(&ctx, &mut scores, None).await
+                        }
                     }
-                }
+                    // Users without recent passes would otherwise drop out of the queue
+                    TrackingKind::Recent if scores.is_empty() => {
+                        let interval_fut = ctx.tracking().update_interval(entry, &[], ctx.psql());
+
+                        if let Err(err) = interval_fut.await {
+                            warn!("{:?}", err.wrap_err("Failed to update tracking interval"));
+                        }
+
+                        ctx.tracking().reset(entry).await
+                    }
+                    TrackingKind::Recent => process_tracking(&ctx, entry, &mut scores, None).await,
+                },
                 Err(OsuError::NotFound) => {
                     warn!(
                         "got 404 while retrieving scores for ({user_id},{mode}), don't reset entry",
@@ -57,41 +77,61 @@ pub async fn osu_tracking_loop(ctx: Arc<Context>) {
                 }
                 Err(err) => {
                     let wrap = format!(
-                        "osu!api issue while retrieving user ({user_id},{mode}) for tracking",
+                        "osu!api issue while retrieving user ({user_id},{mode},{kind}) for tracking",
                     );
                     let report = Report::new(err).wrap_err(wrap);
                     warn!("{report:?}");
-                    ctx.tracking().reset(user_id, mode).await;
+                    ctx.tracking().reset(entry).await;
                 }
             }
         }
     }
 }
 
+/// Process the top scores of a user
 pub async fn process_osu_tracking(ctx: &Context, scores: &mut [Score], user: Option<&User>) {
     // Make sure scores is not empty
-    let (user_id, mode, new_last) = match scores.iter().max_by_key(|s| s.ended_at) {
-        Some(score) => (score.user_id, score.mode, score.ended_at),
+    let entry = match scores.first() {
+        Some(score) => TrackingEntry {
+            user_id: score.user_id,
+            mode: score.mode,
+            kind: TrackingKind::Top,
+        },
+        None => return,
+    };
+
+    process_tracking(ctx, entry, scores, user).await;
+}
+
+async fn process_tracking(
+    ctx: &Context,
+    entry: TrackingEntry,
+    scores: &mut [Score],
+    user: Option<&User>,
+) {
+    let TrackingEntry { user_id, mode, .. } = entry;
+
+    // Make sure scores is not empty
+    let new_last = match scores.iter().map(|score| score.ended_at).max() {
+        Some(new_last) => new_last,
         None => return,
     };
 
     // Make sure the user is being tracked in general
-    let (last, channels) = match ctx.tracking().get_tracked(user_id, mode).await {
+    let (last, channels) = match ctx.tracking().get_tracked(entry).await {
         Some(tuple) => tuple,
         None => return,
     };
 
     // Make sure the user is being tracked in any channel
-    let max = match channels.values().max() {
-        Some(max) => *max,
+    let max = match channels.values().map(|filters| filters.limit).max() {
+        Some(max) => max,
         None => return,
     };
 
-    // If new top score, update the date
+    // If new tracked score, update the date
     if new_last > last {
-        let update_fut = ctx
-            .tracking()
-            .update_last_date(user_id, mode, new_last, ctx.psql());
+        let update_fut = ctx.tracking().update_last_date(entry, new_last, ctx.psql());
 
         if let Err(err) = update_fut.await {
             let wrap = format!("Failed to update tracking date for user ({user_id},{mode})");
@@ -99,9 +139,17 @@ pub async fn process_osu_tracking(ctx: &Context, scores: &mut [Score], user: Opt
         }
     }
 
-    ctx.tracking().reset(user_id, mode).await;
+    // Check active users more frequently than inactive ones
+    let interval_fut = ctx.tracking().update_interval(entry, scores, ctx.psql());
 
-    let mut user = TrackUser::new(user_id, mode, user);
+    if let Err(err) = interval_fut.await {
+        let wrap = format!("Failed to update tracking interval for user ({user_id},{mode})");
+        warn!("{:?}", err.wrap_err(wrap));
+    }
+
+    ctx.tracking().reset(entry).await;
+
+    let mut user = TrackUser::new(entry, user);
 
     // Process scores
     match score_loop(ctx, &mut user, max, last, scores, &channels).await {
@@ -115,7 +163,7 @@ pub async fn process_osu_tracking(ctx: &Context, scores: &mut [Score], user: Opt
         Err(err) => {
             let report = Report::new(err).wrap_err("osu!api error while tracking");
             warn!("{report:?}");
-            ctx.tracking().reset(user_id, mode).await;
+            ctx.tracking().reset(entry).await;
         }
     }
 }
@@ -126,7 +174,7 @@ async fn score_loop(
     max: usize,
     last: OffsetDateTime,
     scores: &mut [Score],
-    channels: &HashMap<Id<ChannelMarker>, usize, IntHasher>,
+    channels: &HashMap<Id<ChannelMarker>, TrackingFilters, IntHasher>,
 ) -> OsuResult<()> {
     for (idx, score) in (1..).zip(scores.iter_mut()).take(max) {
         // Skip if its an older score
@@ -134,6 +182,29 @@ async fn score_loop(
             continue;
         }
 
+        // Check the cheap filters first so nothing is calculated for scores that are dropped anyway
+        let candidates: Vec<_> = channels
+            .iter()
+            .filter(|(_, filters)| filters.matches(score, idx))
+            .collect();
+
+        if candidates.is_empty() {
+            continue;
+        }
+
+        if user.entry.kind == TrackingKind::Recent {
+            match is_notable_pass(ctx, score).await {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(err) => {
+                    let report = Report::new(err).wrap_err("failed to check recent pass");
+                    warn!("{report:?}");
+
+                    continue;
+                }
+            }
+        }
+
         let requires_combo = score.map.as_ref().map_or(false, |m| {
             matches!(m.mode, GameMode::Osu | GameMode::Catch) && m.max_combo.is_none()
         });
@@ -147,84 +218,165 @@ async fn score_loop(
             }
         }
 
+        // Only calculate mod-adjusted stars if some channel filters by them
+        let stars = if candidates
+            .iter()
+            .any(|(_, filters)| filters.min_stars.is_some())
+        {
+            match score.map.as_ref().map(|map| map.map_id) {
+                Some(map_id) => match PpCalculator::new(ctx, map_id).await {
+                    Ok(calc) => Some(calc.mods(score.mods).stars() as f32),
+                    Err(err) => {
+                        warn!("{:?}", err.wrap_err("Failed to get pp calculator"));
+
+                        None
+                    }
+                },
+                None => None,
+            }
+        } else {
+            None
+        };
+
+        let mut notified = Vec::new();
+
         // Send the embed to each tracking channel
-        for (&channel, &limit) in channels.iter() {
-            if idx > limit {
+        for (&channel, filters) in candidates {
+            if !filters.matches_stars(score, stars) {
                 continue;
             }
 
             let embed = user.embed(ctx, score, idx).await?;
 
-            // Try to build and send the message
-            match ctx.http.create_message(channel).embeds(&[embed]) {
-                Ok(msg_fut) => {
-                    if let Err(err) = msg_fut.exec().await {
-                        if let TwilightErrorType::Response { error, .. } = err.kind() {
-                            if let ApiError::General(GeneralApiError {
-                                code: UNKNOWN_CHANNEL,
-                                ..
-                            }) = error
-                            {
-                                let remove_fut =
-                                    ctx.tracking().remove_channel(channel, None, ctx.psql());
-
-                                if let Err(err) = remove_fut.await {
-                                    let wrap = format!(
-                                        "Failed to remove osu tracks from unknown channel {channel}",
-                                    );
-
-                                    warn!("{:?}", err.wrap_err(wrap));
-                                }
-                            } else {
-                                warn!(
-                                    "Error from API while sending osu notif (channel {channel}): {error}",
-                                )
-                            }
-                        } else {
-                            let wrap = format!("error while sending osu notif (channel {channel})");
-                            let report = Report::new(err).wrap_err(wrap);
-                            warn!("{report:?}");
-                        }
+            match deliver_notification(ctx, channel, embed, None).await {
+                Ok(_) => notified.push(channel),
+                Err(DeliveryError::UnknownChannel) => {
+                    let remove_fut = ctx.tracking().remove_channel(channel, None, ctx.psql());
+
+                    if let Err(err) = remove_fut.await {
+                        let wrap =
+                            format!("Failed to remove osu tracks from unknown channel {channel}");
+
+                        warn!("{:?}", err.wrap_err(wrap));
                     }
                 }
-                Err(err) => {
-                    let report =
-                        Report::new(err).wrap_err("invalid embed for osu!tracking notification");
-                    warn!("{report:?}");
+                Err(DeliveryError::Other(err)) => {
+                    let wrap = format!("error while sending osu notif (channel {channel})");
+                    warn!("{:?}", err.wrap_err(wrap));
                 }
             }
         }
+
+        if notified.is_empty() {
+            continue;
+        }
+
+        // Remember the notification so it can be looked up later on
+        if let Some(notif) = user.notification(score, idx) {
+            let insert_fut = ctx
+                .psql()
+                .insert_osu_tracking_notification(&notif, &notified);
+
+            if let Err(err) = insert_fut.await {
+                warn!(
+                    "{:?}",
+                    err.wrap_err("Failed to store tracking notification")
+                );
+            }
+        }
     }
 
     Ok(())
 }
 
+/// Recent passes are only notified if they're a new #1 on the map or the user's first pass of it.
+///
+/// Only maps with a leaderboard can be checked so passes on other maps are never notable.
+async fn is_notable_pass(ctx: &Context, score: &Score) -> OsuResult<bool> {
+    let map = match score.map {
+        Some(ref map) if matches!(map.status, Ranked | Loved | Qualified | Approved) => map,
+        _ => return Ok(false),
+    };
+
+    let user_score = ctx
+        .osu()
+        .beatmap_user_score(map.map_id, score.user_id)
+        .mode(score.mode)
+        .await?;
+
+    if user_score.pos == 1
+        && score.score_id.is_some()
+        && user_score.score.score_id == score.score_id
+    {
+        return Ok(true);
+    }
+
+    let scores = ctx
+        .osu()
+        .beatmap_user_scores(map.map_id, score.user_id)
+        .mode(score.mode)
+        .await?;
+
+    Ok(scores.iter().all(|s| s.ended_at >= score.ended_at))
+}
+
 struct TrackUser<'u> {
-    user_id: u32,
-    mode: GameMode,
+    entry: TrackingEntry,
     user: Option<Cow<'u, User>>,
 }
 
 impl<'u> TrackUser<'u> {
     #[inline]
-    fn new(user_id: u32, mode: GameMode, user: Option<&'u User>) -> Self {
+    fn new(entry: TrackingEntry, user: Option<&'u User>) -> Self {
         Self {
-            user_id,
-            mode,
+            entry,
             user: user.map(Cow::Borrowed),
         }
     }
 
     async fn embed(&mut self, ctx: &Context, score: &Score, idx: usize) -> OsuResult<Embed> {
+        let TrackingEntry {
+            user_id,
+            mode,
+            kind,
+        } = self.entry;
+
         let data = if let Some(user) = self.user.as_deref() {
-            TrackNotificationEmbed::new(user, score, idx, ctx).await
+            TrackNotificationEmbed::new(user, score, kind, idx, ctx).await
         } else {
-            let user = ctx.osu().user(self.user_id).mode(self.mode).await?;
+            let user = ctx.osu().user(user_id).mode(mode).await?;
             let user = self.user.get_or_insert(Cow::Owned(user));
 
-            TrackNotificationEmbed::new(user.as_ref(), score, idx, ctx).await
+            TrackNotificationEmbed::new(user.as_ref(), score, kind, idx, ctx).await
         };
 
         Ok(data.build())
     }
+
+    /// Requires the user to be available i.e. `embed` must have been called beforehand
+    fn notification(&self, score: &Score, idx: usize) -> Option<TrackingNotification> {
+        let user = self.user.as_deref()?;
+        let score_id = score.score_id?;
+        let map = score.map.as_ref()?;
+        let mapset = score.mapset.as_ref()?;
+
+        let map_title = format!("{} - {} [{}]", mapset.artist, mapset.title, map.version);
+
+        let notif = TrackingNotification {
+            user_id: self.entry.user_id,
+            username: user.username.clone(),
+            mode: self.entry.mode,
+            score_id,
+            map_id: map.map_id,
+            mapset_id: map.mapset_id,
+            map_title,
+            pp: score.pp,
+            kind: self.entry.kind,
+            top_idx: idx,
+            ended_at: score.ended_at,
+            notified_at: OffsetDateTime::now_utc(),
+        };
+
+        Some(notif)
+    }
 }
@@ -1,5 +1,6 @@
 use std::{
     cmp::Reverse,
+    fmt,
     sync::atomic::{AtomicBool, Ordering},
     time::Duration as StdDuration,
 };
@@ -12,11 +13,15 @@ use hashbrown::hash_map::{DefaultHashBuilder, Entry, HashMap};
 use once_cell::sync::OnceCell;
 use parking_lot::RwLock;
 use priority_queue::PriorityQueue;
-use rosu_v2::model::GameMode;
+use rosu_v2::prelude::{GameMode, Score};
 use tokio::{sync::Mutex, time};
 use twilight_model::id::{marker::ChannelMarker, Id};
 
-use crate::{database::TrackingUser, util::hasher::IntHasher, Database};
+use crate::{
+    database::{TrackingFilters, TrackingUser},
+    util::hasher::IntHasher,
+    Database,
+};
 
 static OSU_TRACKING_INTERVAL: OnceCell<Duration> = OnceCell::with_value(Duration::minutes(150));
 
@@ -24,10 +29,41 @@ pub fn default_tracking_interval() -> Duration {
     unsafe { *OSU_TRACKING_INTERVAL.get_unchecked() }
 }
 
+/// Lower bound for the interval in which a user is checked
+const MIN_INTERVAL: Duration = Duration::minutes(15);
+
+/// Factor of the base interval that serves as upper bound for inactive users
+const MAX_INTERVAL_FACTOR: i32 = 4;
+
+/// Minimum delay between two pops so that tracking won't hog the osu!api
+const POP_DELAY: StdDuration = StdDuration::from_millis(200);
+
+/// Calculate the interval in which a user should be checked based on
+/// how many of their tracked scores were set recently.
+///
+/// Inactive users are checked every `MAX_INTERVAL_FACTOR * base`,
+/// each recent score shortens that interval down to `MIN_INTERVAL`.
+fn activity_interval(base: Duration, kind: TrackingKind, scores: &[Score]) -> Duration {
+    let window = match kind {
+        TrackingKind::Top => Duration::days(30),
+        // Recent scores only go back 24 hours anyway
+        TrackingKind::Recent => Duration::days(1),
+    };
+
+    let threshold = OffsetDateTime::now_utc() - window;
+    let active = scores
+        .iter()
+        .filter(|score| score.ended_at > threshold)
+        .count();
+    let max = (base * MAX_INTERVAL_FACTOR).max(MIN_INTERVAL);
+
+    (max / (active as i32 + 1)).clamp(MIN_INTERVAL, max)
+}
+
 type TrackingQueue =
     Mutex<PriorityQueue<TrackingEntry, Reverse<OffsetDateTime>, DefaultHashBuilder>>;
 
-type Channels = HashMap<Id<ChannelMarker>, usize, IntHasher>;
+type Channels = HashMap<Id<ChannelMarker>, TrackingFilters, IntHasher>;
 
 pub struct TrackingStats {
     pub next_pop: TrackingEntry,
@@ -37,13 +73,16 @@ pub struct TrackingStats {
     pub interval: i64,
     pub tracking: bool,
     pub wait_interval: i64,
-    pub ms_per_track: i64,
+    pub min_user_interval: i64,
+    pub avg_user_interval: i64,
+    pub max_user_interval: i64,
 }
 
 #[derive(Copy, Clone, Eq, Hash, PartialEq)]
 pub struct TrackingEntry {
     pub user_id: u32,
     pub mode: GameMode,
+    pub kind: TrackingKind,
 }
 
 impl From<&TrackingUser> for TrackingEntry {
@@ -52,6 +91,35 @@ impl From<&TrackingUser> for TrackingEntry {
         Self {
             user_id: user.user_id,
             mode: user.mode,
+            kind: user.kind,
+        }
+    }
+}
+
+/// Which scores of a user are being polled for notifications
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum TrackingKind {
+    /// Changes within the user's top scores
+    Top = 0,
+    /// New passes among the user's recent scores
+    Recent = 1,
+}
+
+impl From<i16> for TrackingKind {
+    #[inline]
+    fn from(kind: i16) -> Self {
+        match kind {
+            1 => Self::Recent,
+            _ => Self::Top,
+        }
+    }
+}
+
+impl fmt::Display for TrackingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Top => f.write_str("top"),
+            Self::Recent => f.write_str("recent"),
         }
     }
 }
@@ -88,19 +156,17 @@ impl OsuTracking {
         *self.queue.interval.read()
     }
 
-    pub async fn reset(&self, user_id: u32, mode: GameMode) {
-        self.queue.reset(user_id, mode).await;
+    pub async fn reset(&self, entry: TrackingEntry) {
+        self.queue.reset(entry).await;
     }
 
     pub async fn update_last_date(
         &self,
-        user_id: u32,
-        mode: GameMode,
+        entry: TrackingEntry,
         new_date: OffsetDateTime,
         psql: &Database,
     ) -> Result<()> {
-        if self.queue.update_last_date(user_id, mode, new_date).await {
-            let entry = TrackingEntry { user_id, mode };
+        if self.queue.update_last_date(entry, new_date).await {
             psql.update_osu_tracking_date(&entry, new_date)
                 .await
                 .wrap_err("failed to update database entry")?;
@@ -109,12 +175,26 @@ impl OsuTracking {
         Ok(())
     }
 
-    pub async fn get_tracked(
+    /// Adjust the interval of the entry based on the activity within the given scores
+    pub async fn update_interval(
         &self,
-        user_id: u32,
-        mode: GameMode,
-    ) -> Option<(OffsetDateTime, Channels)> {
-        self.queue.get_tracked(user_id, mode).await
+        entry: TrackingEntry,
+        scores: &[Score],
+        psql: &Database,
+    ) -> Result<()> {
+        let interval = activity_interval(self.interval(), entry.kind, scores);
+
+        if self.queue.update_interval(entry, interval).await {
+            psql.update_osu_tracking_interval(&entry, interval)
+                .await
+                .wrap_err("failed to update database entry")?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_tracked(&self, entry: TrackingEntry) -> Option<(OffsetDateTime, Channels)> {
+        self.queue.get_tracked(entry).await
     }
 
     pub async fn pop(&self) -> Option<(TrackingEntry, usize)> {
@@ -122,8 +202,8 @@ impl OsuTracking {
     }
 
     pub async fn remove_user_all(&self, user_id: u32, psql: &Database) -> Result<()> {
-        for mode in self.queue.remove_user_all(user_id).await {
-            psql.remove_osu_tracking(user_id, mode)
+        for (mode, kind) in self.queue.remove_user_all(user_id).await {
+            psql.remove_osu_tracking(user_id, mode, kind)
                 .await
                 .wrap_err("failed to remove entry from database")?;
         }
@@ -135,10 +215,11 @@ impl OsuTracking {
         &self,
         user_id: u32,
         mode: Option<GameMode>,
+        kind: Option<TrackingKind>,
         channel: Id<ChannelMarker>,
         psql: &Database,
     ) -> Result<()> {
-        let remove_entries = self.queue.remove_user(user_id, mode, channel).await;
+        let remove_entries = self.queue.remove_user(user_id, mode, kind, channel).await;
         self.remove(remove_entries, psql).await?;
 
         Ok(())
@@ -159,16 +240,24 @@ impl OsuTracking {
 
     async fn remove(&self, remove: Vec<RemoveEntry>, psql: &Database) -> Result<()> {
         for remove_entry in remove {
-            let TrackingEntry { user_id, mode } = remove_entry.entry;
+            let TrackingEntry {
+                user_id,
+                mode,
+                kind,
+            } = remove_entry.entry;
 
             if remove_entry.no_longer_tracked {
-                psql.remove_osu_tracking(user_id, mode).await?;
+                psql.remove_osu_tracking(user_id, mode, kind).await?;
             } else {
                 let guard = self.queue.users.lock(&remove_entry.entry).await;
 
                 if let Some(user) = guard.get() {
-                    psql.update_osu_tracking(user_id, mode, user.last_top_score, &user.channels)
-                        .await?;
+                    psql.update_osu_tracking(
+                        &remove_entry.entry,
+                        user.last_top_score,
+                        &user.channels,
+                    )
+                    .await?;
                 }
             }
         }
@@ -178,31 +267,31 @@ impl OsuTracking {
 
     pub async fn add(
         &self,
-        user_id: u32,
-        mode: GameMode,
+        entry: TrackingEntry,
         last_top_score: OffsetDateTime,
         channel: Id<ChannelMarker>,
-        limit: usize,
+        filters: TrackingFilters,
         psql: &Database,
     ) -> Result<bool> {
+        let interval = self.interval();
+
         let added = self
             .queue
-            .add(user_id, mode, last_top_score, channel, limit)
+            .add(entry, last_top_score, channel, filters, interval)
             .await;
 
         match added {
             AddEntry::AddedNew => {
-                psql.insert_osu_tracking(user_id, mode, last_top_score, channel, limit)
+                psql.insert_osu_tracking(&entry, last_top_score, channel, filters, interval)
                     .await
                     .wrap_err("failed to insert entry")?;
             }
             AddEntry::NotAdded => return Ok(false),
-            AddEntry::Added | AddEntry::UpdatedLimit => {
-                let entry = TrackingEntry { user_id, mode };
+            AddEntry::Added | AddEntry::UpdatedFilters => {
                 let guard = self.queue.users.lock(&entry).await;
 
                 if let Some(user) = guard.get() {
-                    psql.update_osu_tracking(user_id, mode, user.last_top_score, &user.channels)
+                    psql.update_osu_tracking(&entry, user.last_top_score, &user.channels)
                         .await?;
                 } else {
                     return Ok(false);
@@ -213,7 +302,7 @@ impl OsuTracking {
         Ok(true)
     }
 
-    pub async fn list(&self, channel: Id<ChannelMarker>) -> Vec<(u32, GameMode, usize)> {
+    pub async fn list(&self, channel: Id<ChannelMarker>) -> Vec<(TrackingEntry, TrackingFilters)> {
         self.queue.list(channel).await
     }
 
@@ -239,10 +328,17 @@ impl OsuTrackingQueue {
             .wrap_err("failed to get tracking entries from database")?;
 
         let now = OffsetDateTime::now_utc();
+        let len = users.len() as f64;
 
+        // Spread out the initial checks so not all users are due at once
         let queue = users
             .iter()
-            .map(|(entry, _)| (*entry, Reverse(now)))
+            .enumerate()
+            .map(|(i, (entry, user))| {
+                let due = now + user.interval * (i as f64 / len);
+
+                (*entry, Reverse(due))
+            })
             .collect();
 
         let users = users.into_iter().collect();
@@ -256,24 +352,40 @@ impl OsuTrackingQueue {
         })
     }
 
-    /// Put the entry at the end of the queue
-    async fn reset(&self, user_id: u32, mode: GameMode) {
+    /// Put the entry back into the queue, due once the user's interval passed
+    async fn reset(&self, entry: TrackingEntry) {
+        let interval = match self.users.lock(&entry).await.get() {
+            Some(user) => user.interval,
+            None => *self.interval.read(),
+        };
+
         let now = OffsetDateTime::now_utc();
         *self.last_date.lock().await = now;
-        let entry = TrackingEntry { user_id, mode };
-        self.queue.lock().await.push_decrease(entry, Reverse(now));
+        self.queue
+            .lock()
+            .await
+            .push_decrease(entry, Reverse(now + interval));
+    }
+
+    /// Returns whether the interval of the entry changed
+    async fn update_interval(&self, entry: TrackingEntry, interval: Duration) -> bool {
+        self.users
+            .lock(&entry)
+            .await
+            .get_mut()
+            .filter(|user| user.interval != interval)
+            .map_or(false, |user| {
+                user.interval = interval;
+
+                true
+            })
     }
 
     /// Returns whether the entry was updated
-    /// i.e. if `new_date` comes after the latest top play of the user
-    async fn update_last_date(
-        &self,
-        user_id: u32,
-        mode: GameMode,
-        new_date: OffsetDateTime,
-    ) -> bool {
+    /// i.e. if `new_date` comes after the latest tracked play of the user
+    async fn update_last_date(&self, entry: TrackingEntry, new_date: OffsetDateTime) -> bool {
         self.users
-            .lock(&TrackingEntry { user_id, mode })
+            .lock(&entry)
             .await
             .get_mut()
             .filter(|user| new_date > user.last_top_score)
@@ -284,15 +396,11 @@ impl OsuTrackingQueue {
             })
     }
 
-    /// Returns all channels in which a user is tracked for a mode
-    /// and also the date time of the user's last top score
-    async fn get_tracked(
-        &self,
-        user_id: u32,
-        mode: GameMode,
-    ) -> Option<(OffsetDateTime, Channels)> {
+    /// Returns all channels in which a user is tracked for a mode and kind
+    /// and also the date time of the user's last tracked score
+    async fn get_tracked(&self, entry: TrackingEntry) -> Option<(OffsetDateTime, Channels)> {
         self.users
-            .lock(&TrackingEntry { user_id, mode })
+            .lock(&entry)
             .await
             .get()
             .map(|user| (user.last_top_score, user.channels.to_owned()))
@@ -300,43 +408,70 @@ impl OsuTrackingQueue {
 
     /// Pop a user from the queue to be checked for tracking
     async fn pop(&self) -> Option<(TrackingEntry, usize)> {
-        let len = self.queue.lock().await.len();
+        let next_due = self.queue.lock().await.peek().map(|(_, due)| due.0);
+
+        let next_due = match next_due {
+            Some(due) if !self.stop_tracking.load(Ordering::Acquire) => due,
+            _ => {
+                time::sleep(StdDuration::from_secs(5)).await;
 
-        if len == 0 || self.stop_tracking.load(Ordering::Acquire) {
-            time::sleep(StdDuration::from_secs(5)).await;
+                return None;
+            }
+        };
+
+        let wait = next_due - OffsetDateTime::now_utc();
+
+        // Don't sleep for too long in case new users are added in the meanwhile
+        if wait.is_positive() {
+            let ms = wait.whole_milliseconds().min(5000);
+            time::sleep(StdDuration::from_millis(ms as u64)).await;
 
             return None;
         }
 
-        let last_date = *self.last_date.lock().await;
-        let interval = last_date + *self.interval.read() - OffsetDateTime::now_utc();
-        let ms_per_track = interval.whole_milliseconds() as f32 / len as f32;
-        time::sleep(StdDuration::from_millis(ms_per_track as u64)).await;
+        time::sleep(POP_DELAY).await;
 
         // Pop user and return them
         loop {
-            let entry = self.queue.lock().await.pop().map(|(entry, _)| entry)?;
+            let entry = {
+                let mut queue = self.queue.lock().await;
+                let now = OffsetDateTime::now_utc();
+
+                match queue.peek() {
+                    Some((_, due)) if due.0 <= now => queue.pop().map(|(entry, _)| entry),
+                    _ => None,
+                }
+            }?;
+
             let guard = self.users.lock(&entry).await;
 
-            if let Some(amount) = guard.get().and_then(|u| u.channels.values().max().copied()) {
+            let amount = guard
+                .get()
+                .and_then(|u| u.channels.values().map(|filters| filters.limit).max());
+
+            if let Some(amount) = amount {
                 return Some((entry, amount));
             }
         }
     }
 
-    /// Returns all game modes for which the user was tracked in some channel
-    async fn remove_user_all(&self, user_id: u32) -> Vec<GameMode> {
+    /// Returns all game modes and kinds for which the user was tracked in some channel
+    async fn remove_user_all(&self, user_id: u32) -> Vec<(GameMode, TrackingKind)> {
         let mut to_remove = Vec::with_capacity(2);
         let mut stream = self.users.iter();
 
         while let Some(guard) = stream.next().await {
             if guard.key().user_id == user_id {
-                to_remove.push(guard.key().mode);
+                to_remove.push((guard.key().mode, guard.key().kind));
             }
         }
 
-        for &mode in to_remove.iter() {
-            let entry = TrackingEntry { user_id, mode };
+        for &(mode, kind) in to_remove.iter() {
+            let entry = TrackingEntry {
+                user_id,
+                mode,
+                kind,
+            };
 
             self.queue.lock().await.remove(&entry);
             self.users.lock(&entry).await.remove();
@@ -345,11 +480,12 @@ impl OsuTrackingQueue {
         to_remove
     }
 
-    /// Returns all game modes for which the user was tracked the channel
+    /// Returns all entries for which the user was tracked the channel
     async fn remove_user(
         &self,
         user_id: u32,
         mode: Option<GameMode>,
+        kind: Option<TrackingKind>,
         channel: Id<ChannelMarker>,
     ) -> Vec<RemoveEntry> {
         let mut removed = Vec::with_capacity(2);
@@ -358,6 +494,7 @@ impl OsuTrackingQueue {
         while let Some(mut guard) = stream.next().await {
             if guard.key().user_id == user_id
                 && mode.map_or(true, |m| guard.key().mode == m)
+                && kind.map_or(true, |k| guard.key().kind == k)
                 && guard.value_mut().remove_channel(channel)
             {
                 removed.push(RemoveEntry::from(guard.key()));
@@ -415,41 +552,39 @@ impl OsuTrackingQueue {
     /// Returns whether the entry has been newly added, updated, or not added at all
     async fn add(
         &self,
-        user_id: u32,
-        mode: GameMode,
+        key: TrackingEntry,
         last_top_score: OffsetDateTime,
         channel: Id<ChannelMarker>,
-        limit: usize,
+        filters: TrackingFilters,
+        interval: Duration,
     ) -> AddEntry {
-        let key = TrackingEntry { user_id, mode };
         let mut guard = self.users.own(key).await;
 
         match guard.entry() {
             Entry::Occupied(mut entry) => match entry.get().channels.get(&channel) {
-                Some(old_limit) => match *old_limit == limit {
+                Some(old_filters) => match *old_filters == filters {
                     true => AddEntry::NotAdded,
                     false => {
-                        entry.get_mut().channels.insert(channel, limit);
+                        entry.get_mut().channels.insert(channel, filters);
 
-                        AddEntry::UpdatedLimit
+                        AddEntry::UpdatedFilters
                     }
                 },
                 None => {
-                    entry.get_mut().channels.insert(channel, limit);
+                    entry.get_mut().channels.insert(channel, filters);
 
                     AddEntry::Added
                 }
             },
             Entry::Vacant(entry) => {
                 let tracking_user =
-                    TrackingUser::new(user_id, mode, last_top_score, channel, limit);
+                    TrackingUser::new(key, last_top_score, channel, filters, interval);
 
                 entry.insert(tracking_user);
 
                 let now = OffsetDateTime::now_utc();
                 *self.last_date.lock().await = now;
-                let entry = TrackingEntry { user_id, mode };
-                self.queue.lock().await.push(entry, Reverse(now));
+                self.queue.lock().await.push(key, Reverse(now + interval));
 
                 AddEntry::AddedNew
             }
@@ -457,28 +592,27 @@ impl OsuTrackingQueue {
     }
 
     /// Returns all entries that are tracked in the channel
-    async fn list(&self, channel: Id<ChannelMarker>) -> Vec<(u32, GameMode, usize)> {
+    async fn list(&self, channel: Id<ChannelMarker>) -> Vec<(TrackingEntry, TrackingFilters)> {
         self.users
             .iter()
             .filter_map(|guard| {
-                let limit = match guard.value().channels.get(&channel) {
-                    Some(limit) => *limit,
+                let filters = match guard.value().channels.get(&channel) {
+                    Some(filters) => *filters,
                     None => return futures::future::ready(None),
                 };
 
-                let TrackingEntry { user_id, mode } = guard.key();
-
-                futures::future::ready(Some((*user_id, *mode, limit)))
+                futures::future::ready(Some((*guard.key(), filters)))
             })
             .collect()
             .await
     }
 
     async fn stats(&self) -> TrackingStats {
-        let (next_pop, queue) = {
+        let (next_pop, next_due, queue) = {
             let guard = self.queue.lock().await;
+            let (&key, due) = guard.peek().unwrap();
 
-            (guard.peek().map(|(&key, _)| key).unwrap(), guard.len())
+            (key, due.0, guard.len())
         };
 
         let users = self.users.len().await;
@@ -486,8 +620,19 @@ impl OsuTrackingQueue {
         let interval = *self.interval.read();
         let tracking = !self.stop_tracking.load(Ordering::Acquire);
 
-        let wait_interval = last_pop + interval - OffsetDateTime::now_utc();
-        let ms_per_track = wait_interval.whole_milliseconds() as f32 / queue as f32;
+        let wait_interval = next_due - OffsetDateTime::now_utc();
+
+        let mut min_user_interval = i64::MAX;
+        let mut max_user_interval = 0;
+        let mut sum_user_interval = 0;
+        let mut stream = self.users.iter();
+
+        while let Some(guard) = stream.next().await {
+            let secs = guard.value().interval.whole_seconds();
+            min_user_interval = min_user_interval.min(secs);
+            max_user_interval = max_user_interval.max(secs);
+            sum_user_interval += secs;
+        }
 
         TrackingStats {
             next_pop,
@@ -497,7 +642,9 @@ impl OsuTrackingQueue {
             interval: interval.whole_seconds(),
             tracking,
             wait_interval: wait_interval.whole_seconds(),
-            ms_per_track: ms_per_track as i64,
+            min_user_interval,
+            avg_user_interval: sum_user_interval / users.max(1) as i64,
+            max_user_interval,
         }
     }
 }
@@ -521,5 +668,5 @@ pub enum AddEntry {
     AddedNew,
     Added,
     NotAdded,
-    UpdatedLimit,
+    UpdatedFilters,
 }
@@ -0,0 +1,436 @@
+#![cfg(feature = "server")]
+
+use std::collections::VecDeque;
+
+use eyre::{Result, WrapErr};
+use futures::future::{BoxFuture, FutureExt};
+use hashbrown::HashMap;
+use parking_lot::Mutex;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+use crate::{
+    core::BotConfig,
+    custom_client::{CustomClient, TwitchSubscription},
+    util::hasher::IntHasher,
+};
+
+/// Events that each tracked user is subscribed to
+pub const SUBSCRIPTION_KINDS: [&str; 3] = ["stream.online", "stream.offline", "channel.update"];
+
+/// Amount of message ids to remember in order to ignore duplicate notifications
+const RECENT_MESSAGES: usize = 100;
+
+/// Events received through EventSub notifications, containing the twitch user id
+#[derive(Debug)]
+pub enum TwitchEvent {
+    Online(u64),
+    Offline(u64),
+    /// The title or category changed
+    Update(u64),
+}
+
+impl TwitchEvent {
+    pub fn new(kind: &str, user_id: u64) -> Option<Self> {
+        match kind {
+            "stream.online" => Some(Self::Online(user_id)),
+            "stream.offline" => Some(Self::Offline(user_id)),
+            "channel.update" => Some(Self::Update(user_id)),
+            _ => None,
+        }
+    }
+}
+
+/// Twitch API requests that are required to manage subscriptions
+pub trait EventSubApi: Sync {
+    fn get_subscriptions(&self) -> BoxFuture<'_, Result<Vec<TwitchSubscription>>>;
+
+    fn create_subscription<'a>(
+        &'a self,
+        kind: &'a str,
+        user_id: u64,
+        callback: &'a str,
+        secret: &'a str,
+    ) -> BoxFuture<'a, Result<TwitchSubscription>>;
+
+    fn delete_subscription<'a>(&'a self, id: &'a str) -> BoxFuture<'a, Result<()>>;
+}
+
+impl EventSubApi for CustomClient {
+    fn get_subscriptions(&self) -> BoxFuture<'_, Result<Vec<TwitchSubscription>>> {
+        self.get_twitch_subscriptions().boxed()
+    }
+
+    fn create_subscription<'a>(
+        &'a self,
+        kind: &'a str,
+        user_id: u64,
+        callback: &'a str,
+        secret: &'a str,
+    ) -> BoxFuture<'a, Result<TwitchSubscription>> {
+        self.create_twitch_subscription(kind, user_id, callback, secret)
+            .boxed()
+    }
+
+    fn delete_subscription<'a>(&'a self, id: &'a str) -> BoxFuture<'a, Result<()>> {
+        self.delete_twitch_subscription(id).boxed()
+    }
+}
+
+struct Subscription {
+    id: String,
+    kind: String,
+    /// Set once twitch verified the subscription
+    enabled: bool,
+}
+
+/// Manages EventSub subscriptions of tracked twitch users.
+///
+/// Users are only polled by the tracking loop if they're missing an enabled
+/// subscription for any of the [`SUBSCRIPTION_KINDS`].
+///
+/// If no secret is configured, EventSub is disabled and all users are polled.
+pub struct TwitchEventSub {
+    callback: String,
+    secret: Option<String>,
+    /// Twitch user ids mapped to their subscriptions
+    subscriptions: Mutex<HashMap<u64, Vec<Subscription>, IntHasher>>,
+    recent_messages: Mutex<VecDeque<String>>,
+    tx: UnboundedSender<TwitchEvent>,
+    rx: Mutex<Option<UnboundedReceiver<TwitchEvent>>>,
+}
+
+impl TwitchEventSub {
+    pub fn new(callback: String, secret: Option<String>) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        Self {
+            callback,
+            secret,
+            subscriptions: Mutex::new(HashMap::default()),
+            recent_messages: Mutex::new(VecDeque::with_capacity(RECENT_MESSAGES)),
+            tx,
+            rx: Mutex::new(Some(rx)),
+        }
+    }
+
+    /// Create the manager based on the bot's config
+    pub fn from_config() -> Self {
+        let config = BotConfig::get();
+        let callback = format!("{}/twitch/eventsub", config.server.external_url);
+
+        Self::new(callback, config.tokens.twitch_eventsub_secret.clone())
+    }
+
+    /// Secret to verify notifications, `None` if EventSub is disabled
+    pub fn secret(&self) -> Option<&str> {
+        self.secret.as_deref()
+    }
+
+    /// The receiver can only be taken once, namely by the tracking loop
+    pub fn take_receiver(&self) -> Option<UnboundedReceiver<TwitchEvent>> {
+        self.rx.lock().take()
+    }
+
+    /// Whether all events of the user are received through enabled subscriptions
+    pub fn is_subscribed(&self, user_id: u64) -> bool {
+        self.subscriptions
+            .lock()
+            .get(&user_id)
+            .map_or(false, |subscriptions| {
+                SUBSCRIPTION_KINDS.iter().all(|kind| {
+                    subscriptions
+                        .iter()
+                        .any(|sub| sub.enabled && sub.kind == *kind)
+                })
+            })
+    }
+
+    /// Create subscriptions for all kinds that the user is not yet subscribed to
+    pub async fn subscribe(&self, api: &impl EventSubApi, user_id: u64) -> Result<()> {
+        let secret = match self.secret {
+            Some(ref secret) => secret,
+            None => return Ok(()),
+        };
+
+        for kind in SUBSCRIPTION_KINDS {
+            let exists = self
+                .subscriptions
+                .lock()
+                .get(&user_id)
+                .map_or(false, |subscriptions| {
+                    subscriptions.iter().any(|sub| sub.kind == kind)
+                });
+
+            if exists {
+                continue;
+            }
+
+            let subscription = api
+                .create_subscription(kind, user_id, &self.callback, secret)
+                .await
+                .wrap_err_with(|| format!("failed to create `{kind}` subscription"))?;
+
+            self.insert(subscription, false);
+        }
+
+        Ok(())
+    }
+
+    /// Delete all subscriptions of the user
+    pub async fn unsubscribe(&self, api: &impl EventSubApi, user_id: u64) -> Result<()> {
+        let subscriptions = self.subscriptions.lock().remove(&user_id);
+
+        for sub in subscriptions.into_iter().flatten() {
+            api.delete_subscription(&sub.id)
+                .await
+                .wrap_err_with(|| format!("failed to delete subscription {}", sub.id))?;
+        }
+
+        Ok(())
+    }
+
+    /// Synchronize subscriptions with the tracked users.
+    ///
+    /// Keeps enabled subscriptions of tracked users, deletes all others,
+    /// and subscribes to tracked users that are missing subscriptions.
+    #[cold]
+    pub async fn sync(&self, api: &impl EventSubApi, tracked: Vec<u64>) -> Result<()> {
+        if self.secret.is_none() {
+            return Ok(());
+        }
+
+        let subscriptions = api
+            .get_subscriptions()
+            .await
+            .wrap_err("failed to get subscriptions")?;
+
+        for sub in subscriptions {
+            // Subscriptions of other instances with the same twitch client
+            if sub.transport.callback.as_deref() != Some(self.callback.as_str()) {
+                continue;
+            }
+
+            let keep = sub.status == "enabled"
+                && SUBSCRIPTION_KINDS.contains(&sub.kind.as_str())
+                && tracked.contains(&sub.condition.broadcaster_user_id);
+
+            if keep {
+                self.insert(sub, true);
+            } else if let Err(err) = api.delete_subscription(&sub.id).await {
+                warn!("{:?}", err.wrap_err("failed to delete stale subscription"));
+            }
+        }
+
+        for user_id in tracked {
+            if let Err(err) = self.subscribe(api, user_id).await {
+                let wrap = format!("failed to subscribe to twitch user {user_id}");
+                warn!("{:?}", err.wrap_err(wrap));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Twitch verified the subscription so its notifications will arrive from now on
+    pub fn confirm(&self, subscription: TwitchSubscription) {
+        self.insert(subscription, true);
+    }
+
+    /// Twitch revoked the subscription so the user needs to be polled again
+    pub fn revoke(&self, subscription: &TwitchSubscription) {
+        let user_id = subscription.condition.broadcaster_user_id;
+
+        if let Some(subscriptions) = self.subscriptions.lock().get_mut(&user_id) {
+            subscriptions.retain(|sub| sub.id != subscription.id);
+        }
+    }
+
+    fn insert(&self, subscription: TwitchSubscription, enabled: bool) {
+        let mut guard = self.subscriptions.lock();
+        let user_id = subscription.condition.broadcaster_user_id;
+        let subscriptions = guard.entry(user_id).or_default();
+
+        // The verification might arrive before the creation response
+        match subscriptions
+            .iter_mut()
+            .find(|sub| sub.id == subscription.id)
+        {
+            Some(sub) => sub.enabled |= enabled,
+            None => subscriptions.push(Subscription {
+                id: subscription.id,
+                kind: subscription.kind,
+                enabled,
+            }),
+        }
+    }
+
+    /// Returns `false` if a message with the same id was handled recently
+    pub fn register_message(&self, message_id: &str) -> bool {
+        let mut recent = self.recent_messages.lock();
+
+        if recent.iter().any(|id| id == message_id) {
+            return false;
+        }
+
+        if recent.len() == RECENT_MESSAGES {
+            recent.pop_front();
+        }
+
+        recent.push_back(message_id.to_owned());
+
+        true
+    }
+
+    pub fn forward(&self, event: TwitchEvent) {
+        if self.tx.send(event).is_err() {
+            warn!("Received twitch event but the tracking loop is not running");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const CALLBACK: &str = "https://localhost/twitch/eventsub";
+
+    fn subscription(id: &str, status: &str, kind: &str, user_id: u64) -> TwitchSubscription {
+        let subscription = json!({
+            "id": id,
+            "status": status,
+            "type": kind,
+            "condition": { "broadcaster_user_id": user_id.to_string() },
+            "transport": { "method": "webhook", "callback": CALLBACK },
+        });
+
+        serde_json::from_value(subscription).unwrap()
+    }
+
+    /// Mock of the twitch API that records all requests
+    #[derive(Default)]
+    struct MockApi {
+        subscriptions: Vec<TwitchSubscription>,
+        fail_create: bool,
+        created: Mutex<Vec<(String, u64)>>,
+        deleted: Mutex<Vec<String>>,
+    }
+
+    impl EventSubApi for MockApi {
+        fn get_subscriptions(&self) -> BoxFuture<'_, Result<Vec<TwitchSubscription>>> {
+            let subscriptions = self.subscriptions.clone();
+
+            async move { Ok(subscriptions) }.boxed()
+        }
+
+        fn create_subscription<'a>(
+            &'a self,
+            kind: &'a str,
+            user_id: u64,
+            callback: &'a str,
+            _: &'a str,
+        ) -> BoxFuture<'a, Result<TwitchSubscription>> {
+            async move {
+                ensure!(!self.fail_create, "failed with status code 503");
+                assert_eq!(callback, CALLBACK);
+
+                self.created.lock().push((kind.to_owned(), user_id));
+                let id = format!("{kind}-{user_id}");
+
+                Ok(subscription(
+                    &id,
+                    "webhook_callback_verification_pending",
+                    kind,
+                    user_id,
+                ))
+            }
+            .boxed()
+        }
+
+        fn delete_subscription<'a>(&'a self, id: &'a str) -> BoxFuture<'a, Result<()>> {
+            async move {
+                self.deleted.lock().push(id.to_owned());
+
+                Ok(())
+            }
+            .boxed()
+        }
+    }
+
+    fn eventsub() -> TwitchEventSub {
+        TwitchEventSub::new(CALLBACK.to_owned(), Some("0123456789abcdef".to_owned()))
+    }
+
+    #[tokio::test]
+    async fn test_sync() {
+        let mut other_instance = subscription("other", "enabled", "stream.online", 1);
+        other_instance.transport.callback = Some("https://elsewhere/twitch/eventsub".to_owned());
+
+        let api = MockApi {
+            subscriptions: vec![
+                subscription("keep", "enabled", "stream.online", 1),
+                other_instance,
+                subscription("untracked", "enabled", "stream.online", 3),
+                subscription(
+                    "failed",
+                    "webhook_callback_verification_failed",
+                    "stream.online",
+                    2,
+                ),
+            ],
+            ..Default::default()
+        };
+
+        let eventsub = eventsub();
+        eventsub.sync(&api, vec![1, 2]).await.unwrap();
+
+        assert_eq!(*api.deleted.lock(), ["untracked", "failed"]);
+
+        let created = api.created.lock().clone();
+        assert_eq!(created.len(), 5);
+        assert!(!created.contains(&("stream.online".to_owned(), 1)));
+        assert!(created.contains(&("stream.online".to_owned(), 2)));
+
+        // New subscriptions are only used once twitch verified them
+        assert!(!eventsub.is_subscribed(1));
+
+        for kind in ["stream.offline", "channel.update"] {
+            let id = format!("{kind}-1");
+            eventsub.confirm(subscription(&id, "enabled", kind, 1));
+        }
+
+        assert!(eventsub.is_subscribed(1));
+        assert!(!eventsub.is_subscribed(2));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_failure() {
+        let api = MockApi {
+            fail_create: true,
+            ..Default::default()
+        };
+
+        let eventsub = eventsub();
+        assert!(eventsub.subscribe(&api, 1).await.is_err());
+
+        // The user keeps being polled
+        assert!(!eventsub.is_subscribed(1));
+    }
+
+    #[tokio::test]
+    async fn test_disabled() {
+        let api = MockApi {
+            subscriptions: vec![subscription("sub", "enabled", "stream.online", 1)],
+            ..Default::default()
+        };
+
+        let eventsub = TwitchEventSub::new(CALLBACK.to_owned(), None);
+        eventsub.sync(&api, vec![1]).await.unwrap();
+        eventsub.subscribe(&api, 2).await.unwrap();
+
+        assert!(api.created.lock().is_empty());
+        assert!(api.deleted.lock().is_empty());
+        assert!(!eventsub.is_subscribed(1));
+    }
+}
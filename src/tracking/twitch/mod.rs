@@ -1,3 +1,4 @@
 #![cfg(feature = "twitchtracking")]
 
+pub mod eventsub;
 pub mod twitch_loop;
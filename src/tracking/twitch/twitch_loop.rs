@@ -1,147 +1,328 @@
 use std::{fmt::Write, sync::Arc};
 
-use eyre::Report;
 use hashbrown::{HashMap, HashSet};
 use rand::Rng;
+use time::OffsetDateTime;
 use tokio::time::{interval, Duration};
-use twilight_http::{
-    api_error::{ApiError, GeneralApiError},
-    error::ErrorType,
+use twilight_model::{
+    channel::embed::{Embed, EmbedField},
+    id::{marker::ChannelMarker, Id},
 };
-use twilight_model::id::{marker::ChannelMarker, Id};
+
+#[cfg(feature = "server")]
+use super::eventsub::TwitchEvent;
 
 use crate::{
     custom_client::TwitchStream,
+    database::StreamTrack,
     embeds::{EmbedData, TwitchNotifEmbed},
-    util::{constants::UNKNOWN_CHANNEL, hasher::IntHasher},
+    tracking::{deliver_notification, DeliveredNotification, DeliveryError},
+    util::hasher::IntHasher,
     Context,
 };
 
+/// A stream that is currently live
+struct OnlineStream {
+    started_at: OffsetDateTime,
+    /// Channels that were already notified about this stream
+    notified: HashSet<Id<ChannelMarker>, IntHasher>,
+    /// Notifications that should be edited once the stream ends
+    to_edit: Vec<(DeliveredNotification, Embed)>,
+}
+
+impl OnlineStream {
+    fn new(started_at: OffsetDateTime) -> Self {
+        Self {
+            started_at,
+            notified: HashSet::default(),
+            to_edit: Vec::new(),
+        }
+    }
+}
+
+type OnlineStreams = HashMap<u64, OnlineStream, IntHasher>;
+
 #[cold]
 pub async fn twitch_tracking_loop(ctx: Arc<Context>) {
-    let mut online_streams = HashSet::with_hasher(IntHasher);
+    let mut online_streams = OnlineStreams::default();
     let mut interval = interval(Duration::from_secs(10 * 60));
     interval.tick().await;
 
+    #[cfg(feature = "server")]
+    {
+        let eventsub = ctx.twitch_eventsub();
+
+        // Syncing takes a few requests per user so it shouldn't delay the first poll
+        let sync_ctx = Arc::clone(&ctx);
+
+        tokio::spawn(async move {
+            let tracked = sync_ctx.tracked_users();
+            let sync_fut = sync_ctx.twitch_eventsub().sync(sync_ctx.client(), tracked);
+
+            if let Err(err) = sync_fut.await {
+                warn!("{:?}", err.wrap_err("Failed to sync twitch subscriptions"));
+            }
+        });
+
+        let mut events = eventsub
+            .take_receiver()
+            .expect("twitch event receiver was already taken");
+
+        // Users whose online event arrived before the api listed their stream
+        let mut awaiting = HashMap::<u64, u8, IntHasher>::default();
+        let mut retry_interval = tokio::time::interval(Duration::from_secs(60));
+        retry_interval.tick().await;
+
+        // Streams that were already live on startup won't cause an event
+        // so the first poll includes all users
+        let mut first_poll = true;
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    let mut user_ids = ctx.tracked_users();
+
+                    // Only poll users that don't receive events through subscriptions
+                    if !first_poll {
+                        user_ids.retain(|&user_id| !eventsub.is_subscribed(user_id));
+                    }
+
+                    first_poll = false;
+                    check_streams(&ctx, &mut online_streams, &user_ids).await;
+                }
+                _ = retry_interval.tick(), if !awaiting.is_empty() => {
+                    let user_ids: Vec<_> = awaiting.keys().copied().collect();
+                    check_streams(&ctx, &mut online_streams, &user_ids).await;
+
+                    awaiting.retain(|user_id, attempts| {
+                        *attempts += 1;
+
+                        !online_streams.contains_key(user_id) && *attempts < 10
+                    });
+                }
+                Some(event) = events.recv() => match event {
+                    TwitchEvent::Online(user_id) => {
+                        check_streams(&ctx, &mut online_streams, &[user_id]).await;
+
+                        if !online_streams.contains_key(&user_id) {
+                            awaiting.insert(user_id, 0);
+                        }
+                    }
+                    TwitchEvent::Offline(user_id) => {
+                        awaiting.remove(&user_id);
+
+                        if let Some(stream) = online_streams.remove(&user_id) {
+                            if !stream.to_edit.is_empty() {
+                                edit_ended_notifs(&ctx, user_id, stream).await;
+                            }
+                        }
+                    }
+                    // Filters might match now
+                    TwitchEvent::Update(user_id) => {
+                        if online_streams.contains_key(&user_id) {
+                            check_streams(&ctx, &mut online_streams, &[user_id]).await;
+                        }
+                    }
+                },
+            }
+        }
+    }
+
+    #[cfg(not(feature = "server"))]
     loop {
         interval.tick().await;
 
         // Get data about what needs to be tracked for which channel
         let user_ids = ctx.tracked_users();
 
-        // Get stream data about all streams that need to be tracked
-        let mut streams = match ctx.client().get_twitch_streams(&user_ids).await {
-            Ok(streams) => streams,
-            Err(err) => {
-                warn!("{:?}", err.wrap_err("Failed to retrieve streams"));
+        check_streams(&ctx, &mut online_streams, &user_ids).await;
+    }
+}
+
+/// Request the streams of the given users and send notifications to all
+/// channels whose filters match.
+async fn check_streams(ctx: &Context, online_streams: &mut OnlineStreams, user_ids: &[u64]) {
+    if user_ids.is_empty() {
+        return;
+    }
+
+    // Get stream data about all streams that need to be tracked
+    let mut streams = match ctx.client().get_twitch_streams(user_ids).await {
+        Ok(streams) => streams,
+        Err(err) => {
+            warn!("{:?}", err.wrap_err("Failed to retrieve streams"));
+
+            return;
+        }
+    };
 
-                continue;
+    // Filter streams whether they're live
+    streams.retain(TwitchStream::is_live);
+    let now_online: HashSet<_, IntHasher> = streams.iter().map(|stream| stream.user_id).collect();
+
+    // Handle streams that went offline
+    let ended: Vec<_> = user_ids
+        .iter()
+        .filter(|user_id| online_streams.contains_key(*user_id) && !now_online.contains(*user_id))
+        .copied()
+        .collect();
+
+    for user_id in ended {
+        if let Some(stream) = online_streams.remove(&user_id) {
+            if !stream.to_edit.is_empty() {
+                edit_ended_notifs(ctx, user_id, stream).await;
             }
+        }
+    }
+
+    // Gather all channels that have not been notified yet but whose filters match.
+    // Since the category or title may change during the stream, this is checked
+    // on every iteration instead of only when the stream comes online.
+    let mut pending = Vec::new();
+
+    for stream in streams {
+        let tracks = match ctx.tracked_channels_for(stream.user_id) {
+            Some(tracks) => tracks,
+            None => continue,
         };
 
-        // Filter streams whether they're live
-        streams.retain(TwitchStream::is_live);
-        let now_online: HashSet<_, IntHasher> =
-            streams.iter().map(|stream| stream.user_id).collect();
+        let online = online_streams
+            .entry(stream.user_id)
+            .or_insert_with(|| OnlineStream::new(stream.started_at));
+
+        let tracks: Vec<_> = tracks
+            .into_iter()
+            .filter(|track| !online.notified.contains(&track.channel))
+            .filter(|track| track.settings.matches(stream.game_id, &stream.title))
+            .collect();
 
-        // If there was no activity change since last time, don't do anything
-        if now_online == online_streams {
-            continue;
+        if !tracks.is_empty() {
+            pending.push((stream, tracks));
         }
+    }
 
-        // Filter streams whether its already known they're live
-        streams.retain(|stream| !online_streams.contains(&stream.user_id));
+    // Nothing to do if no channel needs to be notified
+    if pending.is_empty() {
+        return;
+    }
 
-        // Nothing to do if streams is empty
-        // (i.e. the change was that streamers went offline)
-        if streams.is_empty() {
-            online_streams = now_online;
+    let ids: Vec<_> = pending.iter().map(|(stream, _)| stream.user_id).collect();
 
-            continue;
-        }
+    // TODO: IntHasher
+    let users: HashMap<_, _> = match ctx.client().get_twitch_users(&ids).await {
+        Ok(users) => users.into_iter().map(|u| (u.user_id, u)).collect(),
+        Err(err) => {
+            warn!("{:?}", err.wrap_err("Failed to retrieve twitch users"));
 
-        let ids: Vec<_> = streams.iter().map(|s| s.user_id).collect();
+            return;
+        }
+    };
 
-        // TODO: IntHasher
-        let users: HashMap<_, _> = match ctx.client().get_twitch_users(&ids).await {
-            Ok(users) => users.into_iter().map(|u| (u.user_id, u)).collect(),
-            Err(err) => {
-                warn!("{:?}", err.wrap_err("Failed to retrieve twitch users"));
+    // Generate random width and height to avoid discord caching the thumbnail url
+    let (width, height) = {
+        let mut rng = rand::thread_rng();
 
-                continue;
-            }
-        };
+        let width: u32 = rng.gen_range(350..=370);
+        let height: u32 = rng.gen_range(175..=185);
 
-        // Generate random width and height to avoid discord caching the thumbnail url
-        let (width, height) = {
-            let mut rng = rand::thread_rng();
+        (width, height)
+    };
 
-            let width: u32 = rng.gen_range(350..=370);
-            let height: u32 = rng.gen_range(175..=185);
+    // Process each stream by notifying all corresponding channels
+    for (mut stream, tracks) in pending {
+        let user = match users.get(&stream.user_id) {
+            Some(user) => user,
+            None => continue,
+        };
 
-            (width, height)
+        let online = match online_streams.get_mut(&stream.user_id) {
+            Some(online) => online,
+            None => continue,
         };
 
-        // Process each stream by notifying all corresponding channels
-        for mut stream in streams {
-            let channels = match ctx.tracked_channels_for(stream.user_id) {
-                Some(channels) => channels,
-                None => continue,
-            };
+        // Adjust streams' thumbnail url
+        let url_len = stream.thumbnail_url.len();
+        stream.thumbnail_url.truncate(url_len - 20); // cut off "{width}x{height}.jpg"
+        let _ = write!(stream.thumbnail_url, "{width}x{height}.jpg");
 
-            // Adjust streams' thumbnail url
-            let url_len = stream.thumbnail_url.len();
-            stream.thumbnail_url.truncate(url_len - 20); // cut off "{width}x{height}.jpg"
-            let _ = write!(stream.thumbnail_url, "{width}x{height}.jpg");
+        let data = TwitchNotifEmbed::new(&stream, user);
 
-            let data = TwitchNotifEmbed::new(&stream, &users[&stream.user_id]);
+        for track in tracks {
+            // Only consider the channel notified if the send succeeded
+            // so that failed notifications are retried on the next check
+            if let Some(notif) = send_notif(ctx, &data, &track).await {
+                online.notified.insert(track.channel);
 
-            for channel in channels {
-                send_notif(&ctx, &data, channel).await;
+                if track.settings.vod_edit {
+                    online.to_edit.push((notif, data.to_owned().build()));
+                }
             }
         }
-
-        online_streams = now_online;
     }
 }
 
-async fn send_notif(ctx: &Context, data: &TwitchNotifEmbed, channel: Id<ChannelMarker>) {
+async fn send_notif(
+    ctx: &Context,
+    data: &TwitchNotifEmbed,
+    track: &StreamTrack,
+) -> Option<DeliveredNotification> {
+    let channel = track.channel;
     let embed = data.to_owned().build();
+    let content = track.settings.role.map(|role| format!("<@&{role}>"));
 
-    match ctx.http.create_message(channel).embeds(&[embed]) {
-        Ok(msg_fut) => {
-            if let Err(err) = msg_fut.exec().await {
-                if let ErrorType::Response { error, .. } = err.kind() {
-                    match error {
-                        ApiError::General(GeneralApiError {
-                            code: UNKNOWN_CHANNEL,
-                            ..
-                        }) => {
-                            if let Err(err) = ctx.psql().remove_channel_tracks(channel.get()).await
-                            {
-                                let wrap = format!(
-                                    "Failed to remove stream tracks from unknown channel {channel}"
-                                );
-
-                                warn!("{:?}", err.wrap_err(wrap));
-                            } else {
-                                debug!("Removed twitch tracking of unknown channel {channel}");
-                            }
-                        }
-                        err => warn!(
-                            "Error from API while sending twitch notif (channel {channel}): {err}"
-                        ),
-                    }
-                } else {
-                    let wrap = format!("error while sending twitch notif (channel {channel})");
-                    let report = Report::new(err).wrap_err(wrap);
-                    warn!("{report:?}");
-                }
+    match deliver_notification(ctx, channel, embed, content.as_deref()).await {
+        Ok(notif) => return Some(notif),
+        Err(DeliveryError::UnknownChannel) => {
+            if let Err(err) = ctx.psql().remove_channel_tracks(channel.get()).await {
+                let wrap = format!("Failed to remove stream tracks from unknown channel {channel}");
+                warn!("{:?}", err.wrap_err(wrap));
+            } else {
+                debug!("Removed twitch tracking of unknown channel {channel}");
             }
         }
+        Err(DeliveryError::Other(err)) => {
+            let wrap = format!("error while sending twitch notif (channel {channel})");
+            warn!("{:?}", err.wrap_err(wrap));
+        }
+    }
+
+    None
+}
+
+/// Edit the notifications of an ended stream to show its duration and VOD
+async fn edit_ended_notifs(ctx: &Context, user_id: u64, stream: OnlineStream) {
+    let duration = (OffsetDateTime::now_utc() - stream.started_at).whole_minutes();
+    let mut value = format!("Streamed for {}h{:02}m", duration / 60, duration % 60);
+
+    match ctx.client().get_last_twitch_vod(user_id).await {
+        // Make sure the VOD belongs to this stream and not a previous one
+        Ok(Some(vod)) if vod.created_at >= stream.started_at - time::Duration::minutes(5) => {
+            let _ = write!(value, " • [VOD]({})", vod.url);
+        }
+        Ok(_) => {}
         Err(err) => {
-            let report = Report::new(err).wrap_err("invalid embed for twitch notif");
-            warn!("{report:?}");
+            let wrap = format!("Failed to get last twitch vod of user {user_id}");
+            warn!("{:?}", err.wrap_err(wrap));
+        }
+    }
+
+    for (notif, mut embed) in stream.to_edit {
+        if let Some(ref mut author) = embed.author {
+            author.name = "Was live on twitch:".to_owned();
+        }
+
+        embed.fields.push(EmbedField {
+            inline: false,
+            name: "Stream ended".to_owned(),
+            value: value.clone(),
+        });
+
+        if let Err(err) = notif.edit(ctx, embed).await {
+            let wrap = format!(
+                "Failed to edit twitch notif of ended stream (channel {})",
+                notif.channel
+            );
+            warn!("{:?}", err.wrap_err(wrap));
         }
     }
 }
@@ -68,4 +68,4 @@ macro_rules! bitflag_impls {
 }
 
 bitflag_impls!(MapsetTags, 32);
-bitflag_impls!(Effects, 8);
+bitflag_impls!(Effects, 16);
@@ -21,9 +21,14 @@ pub const TWITCH_STREAM_ENDPOINT: &str = "https://api.twitch.tv/helix/streams";
 pub const TWITCH_USERS_ENDPOINT: &str = "https://api.twitch.tv/helix/users";
 #[cfg(feature = "twitch")]
 pub const TWITCH_VIDEOS_ENDPOINT: &str = "https://api.twitch.tv/helix/videos";
+#[cfg(all(feature = "server", feature = "twitchtracking"))]
+pub const TWITCH_EVENTSUB_ENDPOINT: &str = "https://api.twitch.tv/helix/eventsub/subscriptions";
 #[cfg(feature = "twitch")]
 pub const TWITCH_OAUTH: &str = "https://id.twitch.tv/oauth2/token";
 
+/// Game id of osu! on twitch
+pub const TWITCH_OSU_GAME_ID: u64 = 21465;
+
 // Error messages
 pub const GENERAL_ISSUE: &str = "Something went wrong, blame bade";
 pub const OSU_API_ISSUE: &str = "Some issue with the osu api, blame bade";
@@ -43,6 +48,9 @@ pub const MESSAGE_TOO_OLD_TO_BULK_DELETE: u64 = 50034;
 #[cfg(any(feature = "osutracking", feature = "twitchtracking"))]
 pub const UNKNOWN_CHANNEL: u64 = 10003;
 
+#[cfg(any(feature = "osutracking", feature = "twitchtracking"))]
+pub const UNKNOWN_WEBHOOK: u64 = 10015;
+
 // Misc
 pub const SYMBOLS: [&str; 6] = ["♔", "♕", "♖", "♗", "♘", "♙"];
 pub const INVITE_LINK: &str = "https://discord.com/api/oauth2/authorize?client_id=297073686916366336&permissions=36776045632&scope=bot%20applications.commands";
@@ -2,14 +2,15 @@ use std::borrow::Cow;
 
 use twilight_http::response::{marker::EmptyBody, ResponseFuture};
 use twilight_model::{
-    channel::Message,
+    channel::{message::MessageFlags, Message},
     http::interaction::{InteractionResponse, InteractionResponseData, InteractionResponseType},
 };
 
 use crate::{
     core::Context,
     util::{
-        builder::{MessageBuilder, ModalBuilder},
+        builder::{EmbedBuilder, MessageBuilder, ModalBuilder},
+        constants::RED,
         interaction::InteractionComponent,
     },
 };
@@ -30,6 +31,14 @@ pub trait ComponentExt {
 
     /// Acknowledge a component by responding with a modal.
     fn modal(&self, ctx: &Context, modal: ModalBuilder) -> ResponseFuture<EmptyBody>;
+
+    /// Acknowledge the component by responding with an error message
+    /// that only the user can see, leaving the message itself untouched.
+    fn error_callback(
+        &self,
+        ctx: &Context,
+        content: impl Into<String>,
+    ) -> ResponseFuture<EmptyBody>;
 }
 
 impl ComponentExt for InteractionComponent {
@@ -80,4 +89,28 @@ impl ComponentExt for InteractionComponent {
             .create_response(self.id, &self.token, &response)
             .exec()
     }
+
+    #[inline]
+    fn error_callback(
+        &self,
+        ctx: &Context,
+        content: impl Into<String>,
+    ) -> ResponseFuture<EmptyBody> {
+        let embed = EmbedBuilder::new().description(content).color(RED).build();
+
+        let data = InteractionResponseData {
+            embeds: Some(vec![embed]),
+            flags: Some(MessageFlags::EPHEMERAL),
+            ..Default::default()
+        };
+
+        let response = InteractionResponse {
+            kind: InteractionResponseType::ChannelMessageWithSource,
+            data: Some(data),
+        };
+
+        ctx.interaction()
+            .create_response(self.id, &self.token, &response)
+            .exec()
+    }
 }
@@ -1,7 +1,8 @@
 use eyre::{ContextCompat, Result};
 use twilight_model::{
     application::interaction::{
-        application_command::CommandData, message_component::MessageComponentInteractionData,
+        application_command::{CommandData, CommandDataOption, CommandOptionValue},
+        message_component::MessageComponentInteractionData,
         modal::ModalInteractionData,
     },
     channel::Message,
@@ -27,6 +28,40 @@ pub struct InteractionCommand {
     pub user: Option<User>,
 }
 
+impl InteractionCommand {
+    /// Name and current input of the option that is being autocompleted
+    pub fn focused_option(&self) -> Option<(&str, &str)> {
+        fn focused(options: &[CommandDataOption]) -> Option<(&str, &str)> {
+            options.iter().find_map(|option| match option.value {
+                CommandOptionValue::Focused(ref value, _) => {
+                    Some((option.name.as_str(), value.as_str()))
+                }
+                CommandOptionValue::SubCommand(ref options)
+                | CommandOptionValue::SubCommandGroup(ref options) => focused(options),
+                _ => None,
+            })
+        }
+
+        focused(&self.data.options)
+    }
+
+    /// Value of the string option with the given name, including options of subcommands
+    pub fn string_option(&self, name: &str) -> Option<&str> {
+        fn find<'o>(options: &'o [CommandDataOption], name: &str) -> Option<&'o str> {
+            options.iter().find_map(|option| match option.value {
+                CommandOptionValue::String(ref value) if option.name == name => {
+                    Some(value.as_str())
+                }
+                CommandOptionValue::SubCommand(ref options)
+                | CommandOptionValue::SubCommandGroup(ref options) => find(options, name),
+                _ => None,
+            })
+        }
+
+        find(&self.data.options, name)
+    }
+}
+
 #[derive(Debug)]
 pub struct InteractionComponent {
     pub permissions: Option<Permissions>,
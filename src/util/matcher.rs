@@ -7,6 +7,9 @@ use twilight_model::id::{
     Id,
 };
 
+#[cfg(any(feature = "osutracking", feature = "twitchtracking"))]
+use twilight_model::id::marker::WebhookMarker;
+
 use super::{constants::OSU_BASE, osu::ModSelection};
 
 pub fn is_custom_emote(msg: &str) -> bool {
@@ -131,6 +134,19 @@ pub fn get_osu_match_id(msg: &str) -> Option<u32> {
         .and_then(|c| c.as_str().parse::<u32>().ok())
 }
 
+#[cfg(any(feature = "osutracking", feature = "twitchtracking"))]
+pub fn get_webhook_url(msg: &str) -> Option<(Id<WebhookMarker>, &str)> {
+    WEBHOOK_URL_MATCHER
+        .get()
+        .captures(msg)
+        .and_then(|c| c.get(1).zip(c.get(2)))
+        .and_then(|(id, token)| {
+            let id = id.as_str().parse().ok().and_then(Id::new_checked)?;
+
+            Some((id, token.as_str()))
+        })
+}
+
 pub fn get_mods(msg: &str) -> Option<ModSelection> {
     let selection = if let Some(captures) = MOD_PLUS_MATCHER.get().captures(msg) {
         let mods = GameMods::from_str(captures.get(1)?.as_str()).ok()?;
@@ -184,8 +200,8 @@ impl Regex {
 }
 
 macro_rules! define_regex {
-    ($($name:ident: $pat:literal;)*) => {
-        $( static $name: Regex = Regex::new($pat); )*
+    ($($(#[$meta:meta])* $name:ident: $pat:literal;)*) => {
+        $( $(#[$meta])* static $name: Regex = Regex::new($pat); )*
     }
 }
 
@@ -214,6 +230,9 @@ define_regex! {
     SEVEN_TWO_SEVEN: "(?P<num>7[.,]?2[.,]?7)";
 
     OSU_SCORE_URL_MATCHER: r"https://osu.ppy.sh/scores/(osu|taiko|mania|fruits)/(\d+)";
+
+    #[cfg(any(feature = "osutracking", feature = "twitchtracking"))]
+    WEBHOOK_URL_MATCHER: r"^https://(?:(?:canary|ptb)\.)?discord(?:app)?\.com/api(?:/v\d+)?/webhooks/(\d+)/([\w-]+)$";
 }
 
 pub static QUERY_SYNTAX_REGEX: Regex =
@@ -1,5 +1,6 @@
 use std::{
     borrow::Cow,
+    fmt,
     iter::{self, Copied, Map},
     path::PathBuf,
     slice::Iter,
@@ -102,6 +103,18 @@ impl ModSelection {
     }
 }
 
+/// Formats the selection in the same syntax that is used to parse it,
+/// i.e. `+mods`, `+mods!`, or `-mods!`
+impl fmt::Display for ModSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Include(mods) => write!(f, "+{mods}"),
+            Self::Exclude(mods) => write!(f, "-{mods}!"),
+            Self::Exact(mods) => write!(f, "+{mods}!"),
+        }
+    }
+}
+
 pub fn flag_url(country_code: &str) -> String {
     // format!("{OSU_BASE}/images/flags/{country_code}.png") // from osu itself but outdated
     format!("https://osuflags.omkserver.nl/{country_code}-256.png") // kelderman
@@ -315,7 +328,7 @@ pub fn pp_missing(start: f32, goal: f32, pps: impl IntoPpIter) -> (f32, usize) {
     calculate_remaining(0, goal, top, bot)
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum MapIdType {
     Map(u32),
     Set(u32),
@@ -329,14 +342,19 @@ impl MapIdType {
 
     /// Looks for map or mapset id
     pub fn from_msg(msg: &Message) -> Option<Self> {
-        if msg.content.chars().all(|c| c.is_numeric()) {
-            return Self::from_embeds(&msg.embeds);
+        Self::from_content(&msg.content, &msg.embeds)
+    }
+
+    /// Looks for map or mapset id, prioritizing the content over the embeds
+    pub fn from_content(content: &str, embeds: &[Embed]) -> Option<Self> {
+        if content.chars().all(|c| c.is_numeric()) {
+            return Self::from_embeds(embeds);
         }
 
-        matcher::get_osu_map_id(&msg.content)
+        matcher::get_osu_map_id(content)
             .map(Self::Map)
-            .or_else(|| matcher::get_osu_mapset_id(&msg.content).map(Self::Set))
-            .or_else(|| Self::from_embeds(&msg.embeds))
+            .or_else(|| matcher::get_osu_mapset_id(content).map(Self::Set))
+            .or_else(|| Self::from_embeds(embeds))
     }
 
     /// Looks for map or mapset id
@@ -374,11 +392,16 @@ impl MapIdType {
 
     /// Only looks for map id
     pub fn map_from_msg(msg: &Message) -> Option<u32> {
-        if msg.content.chars().all(|c| c.is_numeric()) {
-            return Self::map_from_embeds(&msg.embeds);
+        Self::map_from_content(&msg.content, &msg.embeds)
+    }
+
+    /// Only looks for map id, prioritizing the content over the embeds
+    pub fn map_from_content(content: &str, embeds: &[Embed]) -> Option<u32> {
+        if content.chars().all(|c| c.is_numeric()) {
+            return Self::map_from_embeds(embeds);
         }
 
-        matcher::get_osu_map_id(&msg.content).or_else(|| Self::map_from_embeds(&msg.embeds))
+        matcher::get_osu_map_id(content).or_else(|| Self::map_from_embeds(embeds))
     }
 
     /// Only looks for map id